@@ -3,6 +3,8 @@
 
 mod access;
 mod block;
+#[cfg(test)]
+mod builder_tests;
 mod call;
 mod execution;
 mod input_state_ref;
@@ -16,9 +18,12 @@ pub use self::block::BlockHead;
 use crate::{
     error::Error,
     evm::opcodes::{gen_associated_ops, gen_associated_steps},
-    operation::{self, CallContextField, Operation, RWCounter, StartOp, StorageOp, RW},
+    operation::{self, CallContextField, Operation, RWCounter, StartOp, StorageOp, TxLogField, RW},
     rpc::GethClient,
-    util::KECCAK_CODE_HASH_EMPTY,
+    util::{
+        ordered_trie_root, GethTraceSanityCheckLevel, GETH_TRACE_CHECK_LEVEL,
+        KECCAK_CODE_HASH_EMPTY,
+    },
 };
 pub use access::{Access, AccessSet, AccessValue, CodeSource};
 pub use block::{Block, BlockContext};
@@ -26,22 +31,23 @@ pub use call::{Call, CallContext, CallKind};
 use core::fmt::Debug;
 use eth_types::{
     self,
-    evm_types::{GasCost, OpcodeId},
+    evm_types::{GasCost, OpcodeId, MAX_REFUND_QUOTIENT_OF_GAS_USED},
     geth_types::{self, TxType},
     sign_types::{pk_bytes_le, pk_bytes_swap_endianness, SignData},
     state_db::{self, CodeDB, StateDB},
-    Address, GethExecTrace, ToBigEndian, ToWord, Word, H256,
+    Address, GethExecTrace, ToAddress, ToBigEndian, ToWord, Word, H256,
 };
 use ethers_providers::JsonRpcClient;
 pub use execution::{
     BigModExp, CopyAccessList, CopyBytes, CopyDataType, CopyEvent, CopyEventStepsBuilder, CopyStep,
     EcAddOp, EcMulOp, EcPairingOp, EcPairingPair, ExecState, ExecStep, ExpEvent, ExpStep,
-    NumberOrHash, PrecompileEvent, PrecompileEvents, N_BYTES_PER_PAIR, N_PAIRING_PER_OP, SHA256,
+    GasBreakdown, NumberOrHash, PrecompileEvent, PrecompileEvents, N_BYTES_PER_PAIR,
+    N_PAIRING_PER_OP, SHA256,
 };
 use hex::decode_to_slice;
 
 use eth_types::{sign_types::get_dummy_tx, utils::hash_code_keccak};
-use ethers_core::utils::keccak256;
+use ethers_core::utils::{keccak256, rlp::RlpStream};
 pub use input_state_ref::CircuitInputStateRef;
 use itertools::Itertools;
 use log::warn;
@@ -148,6 +154,80 @@ impl Default for CircuitsParams {
     }
 }
 
+/// A log emitted during the block, reconstructed from the
+/// [`TxLogOp`](crate::operation::TxLogOp)s of the block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogEntry {
+    /// Id of the transaction emitting the log, starting at 1.
+    pub tx_id: usize,
+    /// Id of the log within the transaction, starting at 1.
+    pub log_id: usize,
+    /// Address of the contract emitting the log.
+    pub address: Address,
+    /// Topics of the log.
+    pub topics: Vec<H256>,
+    /// Data of the log.
+    pub data: Vec<u8>,
+}
+
+/// Steps of one opcode whose gas cost disagrees with the gas consumed in the
+/// geth trace, as reported by [`CircuitInputBuilder::gas_mismatch_report`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpcodeGasMismatch {
+    /// Opcode of the mismatching steps.
+    pub opcode: OpcodeId,
+    /// Number of mismatching steps.
+    pub count: usize,
+    /// Program counters of the first mismatching steps.
+    pub example_pcs: Vec<usize>,
+}
+
+/// Gas cost of a successful opcode step as modeled by the builder: the
+/// constant gas of the opcode plus the memory expansion and cold access costs
+/// the builder computed in the step's [`GasBreakdown`]. `None` for opcodes with
+/// further input dependent costs, and for calls whose cost in the trace
+/// includes the gas forwarded to the callee.
+fn modeled_gas_cost(step: &ExecStep) -> Option<u64> {
+    let ExecState::Op(op) = step.exec_state else {
+        return None;
+    };
+    if step.error.is_some() || op.is_call_or_create() || op.is_log() {
+        return None;
+    }
+    let warm_cost = match op {
+        OpcodeId::EXP
+        | OpcodeId::SHA3
+        | OpcodeId::CALLDATACOPY
+        | OpcodeId::CODECOPY
+        | OpcodeId::RETURNDATACOPY
+        | OpcodeId::EXTCODECOPY
+        | OpcodeId::SSTORE
+        | OpcodeId::RETURN
+        | OpcodeId::REVERT
+        | OpcodeId::SELFDESTRUCT => return None,
+        // The constant gas of SLOAD doesn't include the warm access.
+        OpcodeId::SLOAD => GasCost::WARM_ACCESS.as_u64(),
+        _ => 0,
+    };
+    let breakdown = &step.gas_breakdown;
+    Some(
+        op.constant_gas_cost().as_u64()
+            + warm_cost
+            + breakdown.memory_expansion
+            + breakdown.access_list,
+    )
+}
+
+/// Callback observing each [`ExecStep`] as the [`CircuitInputBuilder`]
+/// generates it.
+pub struct StepObserver(Box<dyn FnMut(&ExecStep) + Send>);
+
+impl Debug for StepObserver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("StepObserver")
+    }
+}
+
 /// Builder to generate a complete circuit input from data gathered from a geth
 /// instance. This structure is the centre of the crate and is intended to be
 /// the only entry point to it. The `CircuitInputBuilder` works in several
@@ -176,6 +256,16 @@ pub struct CircuitInputBuilder {
     pub block: Block,
     /// Block Context
     pub block_ctx: BlockContext,
+    /// Maximum number of execution steps of a single transaction, if any.
+    pub max_steps: Option<usize>,
+    /// Observer of the generated execution steps, if any.
+    step_observer: Option<StepObserver>,
+    /// Geth trace sanity check level, [`GETH_TRACE_CHECK_LEVEL`] by default.
+    /// With [`GethTraceSanityCheckLevel::Check`] the mismatches against the
+    /// geth trace are collected in [`CircuitInputBuilder::geth_mismatches`]
+    /// instead of panicking, see
+    /// [`CircuitInputBuilder::with_strict_geth_check`].
+    pub geth_trace_check_level: GethTraceSanityCheckLevel,
     #[cfg(feature = "scroll")]
     /// Initial Zktrie Status for a incremental updating
     pub mpt_init_state: Option<ZktrieState>,
@@ -190,10 +280,54 @@ impl<'a> CircuitInputBuilder {
             code_db,
             block: block.clone(),
             block_ctx: BlockContext::new(),
+            max_steps: None,
+            step_observer: None,
+            geth_trace_check_level: *GETH_TRACE_CHECK_LEVEL,
             #[cfg(feature = "scroll")]
             mpt_init_state: Default::default(),
         }
     }
+
+    /// Reject a transaction with more than `max_steps` steps with
+    /// [`Error::StepLimitExceeded`], before any of its steps is built.
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = Some(max_steps);
+        self
+    }
+
+    /// Panic at the first mismatch against the geth trace if `strict`, or
+    /// otherwise log it and collect it in
+    /// [`CircuitInputBuilder::geth_mismatches`].
+    pub fn with_strict_geth_check(mut self, strict: bool) -> Self {
+        self.geth_trace_check_level = if strict {
+            GethTraceSanityCheckLevel::Strict
+        } else {
+            GethTraceSanityCheckLevel::Check
+        };
+        self
+    }
+
+    /// Call `observer` with each [`ExecStep`] of a transaction as it's
+    /// generated, from its BeginTx step to its EndTx step. This replaces any
+    /// previously set observer.
+    pub fn set_step_observer(&mut self, observer: impl FnMut(&ExecStep) + Send + 'static) {
+        self.step_observer = Some(StepObserver(Box::new(observer)));
+    }
+
+    /// Mismatches against the geth trace collected so far with the
+    /// [`GethTraceSanityCheckLevel::Check`] level.
+    pub fn geth_mismatches(&self) -> &[String] {
+        &self.block_ctx.geth_mismatches
+    }
+
+    fn observe_steps(&mut self, steps: &[ExecStep]) {
+        if let Some(StepObserver(observer)) = self.step_observer.as_mut() {
+            for step in steps {
+                observer(step);
+            }
+        }
+    }
+
     /// Create a new CircuitInputBuilder from the given `eth_block` and
     /// `constants`.
     pub fn new_from_headers(
@@ -224,6 +358,7 @@ impl<'a> CircuitInputBuilder {
             block_ctx: &mut self.block_ctx,
             tx,
             tx_ctx,
+            geth_trace_check_level: self.geth_trace_check_level,
         }
     }
 
@@ -234,19 +369,13 @@ impl<'a> CircuitInputBuilder {
         is_success: bool,
     ) -> Result<Transaction, Error> {
         let call_id = self.block_ctx.rwc.0;
+        let block_index = self.block.txs.len();
 
-        self.block_ctx.call_map.insert(
-            call_id,
-            (
-                eth_tx
-                    .transaction_index
-                    .ok_or(Error::EthTypeError(eth_types::Error::IncompleteBlock))?
-                    .as_u64() as usize,
-                0,
-            ),
-        );
+        self.block_ctx.call_map.insert(call_id, (block_index, 0));
 
-        Transaction::new(call_id, &self.sdb, &mut self.code_db, eth_tx, is_success)
+        let mut tx = Transaction::new(call_id, &self.sdb, &mut self.code_db, eth_tx, is_success)?;
+        tx.block_index = block_index;
+        Ok(tx)
     }
 
     /// Iterate over all generated CallContext RwCounterEndOfReversion
@@ -294,7 +423,19 @@ impl<'a> CircuitInputBuilder {
             eth_block.number,
             eth_block.transactions.len()
         );
+        // gas used by the txs of this block handled so far
+        let mut block_gas_used = 0u64;
         for (tx_index, tx) in eth_block.transactions.iter().enumerate() {
+            let block_gas_left = eth_block.gas_limit.saturating_sub(block_gas_used.into());
+            if tx.gas > block_gas_left {
+                log::error!(
+                    "tx {:?} gas limit {} exceeds block gas left {}",
+                    tx.hash,
+                    tx.gas,
+                    block_gas_left
+                );
+                return Err(Error::TxGasLimitExceedsBlockGasLeft(tx.gas, block_gas_left));
+            }
             let chunk_tx_idx = self.block.txs.len();
             if self.block.txs.len() >= self.block.circuits_params.max_txs {
                 if self.block.is_relaxed() {
@@ -335,6 +476,7 @@ impl<'a> CircuitInputBuilder {
                 geth_trace,
                 check_last_tx && tx_index + 1 == eth_block.transactions.len(),
             )?;
+            block_gas_used += self.tx_gas_used(self.block.txs.last().unwrap());
             log::debug!(
                 "after handle {}th tx: rwc {:?}, total gas {:?}",
                 chunk_tx_idx,
@@ -550,7 +692,34 @@ impl<'a> CircuitInputBuilder {
         geth_trace: &GethExecTrace,
         is_last_tx: bool,
     ) -> Result<(), Error> {
+        if let Some(max_steps) = self.max_steps {
+            if geth_trace.struct_logs.len() > max_steps {
+                log::error!(
+                    "tx {:?} exceeds the step limit {}, total steps {}",
+                    eth_tx.hash,
+                    max_steps,
+                    geth_trace.struct_logs.len()
+                );
+                return Err(Error::StepLimitExceeded(max_steps));
+            }
+        }
+
         let mut tx = self.new_tx(eth_tx, !geth_trace.failed)?;
+        let intrinsic_gas = tx.intrinsic_gas();
+        if tx.gas < intrinsic_gas {
+            log::error!(
+                "tx {:?} gas limit {} is below its intrinsic gas {}",
+                eth_tx.hash,
+                tx.gas,
+                intrinsic_gas
+            );
+            return Err(Error::IntrinsicGasExceedsGasLimit(tx.gas, intrinsic_gas));
+        }
+        tx.return_value = hex::decode(geth_trace.return_value.trim_start_matches("0x"))
+            .unwrap_or_else(|err| {
+                log::error!("invalid return value of tx {:?}: {err}", eth_tx.hash);
+                Vec::new()
+            });
 
         // Sanity check for transaction L1 fee.
         let tx_l1_fee = if tx.tx_type.is_l1_msg() {
@@ -599,6 +768,7 @@ impl<'a> CircuitInputBuilder {
             }
         }
 
+        self.observe_steps(&begin_tx_steps);
         tx.steps_mut().extend(begin_tx_steps);
 
         for (index, geth_step) in geth_trace.struct_logs.iter().enumerate() {
@@ -689,6 +859,7 @@ impl<'a> CircuitInputBuilder {
                 &mut state_ref,
                 &geth_trace.struct_logs[index..],
             )?;
+            self.observe_steps(&exec_steps);
             tx.steps_mut().extend(exec_steps);
         }
 
@@ -697,6 +868,7 @@ impl<'a> CircuitInputBuilder {
         let end_tx_steps =
             gen_associated_steps(&mut self.state_ref(&mut tx, &mut tx_ctx), ExecState::EndTx)?;
         self.sdb.clear_transient_storage();
+        self.observe_steps(&end_tx_steps);
         tx.steps_mut().extend(end_tx_steps);
 
         debug_assert_eq!(
@@ -704,12 +876,230 @@ impl<'a> CircuitInputBuilder {
             tx_ctx.call_is_success_offset + tx_ctx.call_is_success.len()
         );
 
+        (tx.warm_addresses, tx.warm_storage_keys) = self.sdb.access_list_snapshot();
         self.sdb.commit_tx();
         self.block.txs.push(tx);
         log::trace!("handle_tx finished");
 
         Ok(())
     }
+
+    /// Return all the logs emitted by the handled transactions, in order.
+    /// Logs from reverted calls are not included.
+    pub fn logs(&self) -> Vec<LogEntry> {
+        let mut logs: BTreeMap<(usize, usize), LogEntry> = BTreeMap::new();
+        for op in self.block.container.tx_log.iter().map(|op| op.op()) {
+            let entry = logs
+                .entry((op.tx_id, op.log_id))
+                .or_insert_with(|| LogEntry {
+                    tx_id: op.tx_id,
+                    log_id: op.log_id,
+                    address: Address::zero(),
+                    topics: vec![],
+                    data: vec![],
+                });
+            match op.field {
+                TxLogField::Address => entry.address = op.value.to_address(),
+                TxLogField::Topic => {
+                    if entry.topics.len() <= op.index {
+                        entry.topics.resize(op.index + 1, H256::zero());
+                    }
+                    entry.topics[op.index] = H256::from(op.value.to_be_bytes());
+                }
+                TxLogField::Data => {
+                    if entry.data.len() < op.index + 32 {
+                        entry.data.resize(op.index + 32, 0);
+                    }
+                    entry.data[op.index..op.index + 32].copy_from_slice(&op.value.to_be_bytes());
+                }
+            }
+        }
+
+        // The data is written in words, so cut it to the length that was
+        // actually copied into the log.
+        for event in self
+            .block
+            .copy_events
+            .iter()
+            .filter(|event| event.dst_type == CopyDataType::TxLog)
+        {
+            if let (NumberOrHash::Number(tx_id), Some(log_id)) = (&event.dst_id, event.log_id) {
+                if let Some(entry) = logs.get_mut(&(*tx_id, log_id as usize)) {
+                    let length = (event.src_addr_end - event.src_addr) as usize;
+                    entry.data.resize(length, 0);
+                }
+            }
+        }
+
+        logs.into_values().collect()
+    }
+
+    /// Return the address and code hash of every contract deployed in the
+    /// handled transactions, by a creation transaction, CREATE or CREATE2, in
+    /// deployment order. Deployments that were reverted are not included.
+    pub fn created_contracts(&self) -> Vec<(Address, H256)> {
+        let empty_code_hash = CodeDB::empty_code_hash().to_word();
+        // (address, code hash before the first write, code hash after the last write)
+        let mut code_hashes: Vec<(Address, Word, Word)> = vec![];
+        for op in self
+            .block
+            .container
+            .account
+            .iter()
+            .filter(|op| op.rw() == RW::WRITE)
+            .map(|op| op.op())
+            .filter(|op| op.field == operation::AccountField::CodeHash)
+        {
+            match code_hashes
+                .iter_mut()
+                .find(|(address, _, _)| *address == op.address)
+            {
+                Some((_, _, value)) => *value = op.value,
+                None => code_hashes.push((op.address, op.value_prev, op.value)),
+            }
+        }
+
+        code_hashes
+            .into_iter()
+            .filter(|(_, value_prev, value)| {
+                (value_prev.is_zero() || *value_prev == empty_code_hash)
+                    && !value.is_zero()
+                    && *value != empty_code_hash
+            })
+            .map(|(address, _, value)| (address, H256::from(value.to_be_bytes())))
+            .collect()
+    }
+
+    /// Check that `expected`, the gas used of the block header, is the sum of
+    /// the gas used by the handled transactions, after their refunds.
+    pub fn verify_block_gas_used(&self, expected: u64) -> Result<(), Error> {
+        let gas_used = self.block.txs().iter().map(|tx| self.tx_gas_used(tx)).sum();
+        if gas_used != expected {
+            log::error!("block gas used {expected} != sum of txs gas used {gas_used}");
+            return Err(Error::BlockGasUsedMismatch(expected, gas_used));
+        }
+        Ok(())
+    }
+
+    /// Compare the gas cost the builder models for each handled opcode step
+    /// with the gas consumed until the next step of the same call in
+    /// `geth_traces`, grouping the mismatches by opcode, most frequent first.
+    /// Only opcodes whose cost is fully modeled by the builder are checked,
+    /// see `modeled_gas_cost`. Steps followed by a call entry or exit are
+    /// skipped since their consumption can't be read from the trace.
+    pub fn gas_mismatch_report(&self, geth_traces: &[GethExecTrace]) -> Vec<OpcodeGasMismatch> {
+        const MAX_EXAMPLE_PCS: usize = 5;
+
+        let mut mismatches: BTreeMap<OpcodeId, OpcodeGasMismatch> = BTreeMap::new();
+        for (tx, geth_trace) in self.block.txs().iter().zip(geth_traces) {
+            let op_steps = tx
+                .steps()
+                .iter()
+                .filter(|step| matches!(step.exec_state, ExecState::Op(_)));
+            for (step, logs) in op_steps.zip(geth_trace.struct_logs.windows(2)) {
+                let (log, next_log) = (&logs[0], &logs[1]);
+                if next_log.depth != log.depth {
+                    continue;
+                }
+                let Some(gas_cost) = modeled_gas_cost(step) else {
+                    continue;
+                };
+                if gas_cost != log.gas.0 - next_log.gas.0 {
+                    let mismatch = mismatches.entry(log.op).or_insert(OpcodeGasMismatch {
+                        opcode: log.op,
+                        count: 0,
+                        example_pcs: vec![],
+                    });
+                    mismatch.count += 1;
+                    if mismatch.example_pcs.len() < MAX_EXAMPLE_PCS {
+                        mismatch.example_pcs.push(step.pc.0);
+                    }
+                }
+            }
+        }
+
+        let mut report = mismatches.into_values().collect::<Vec<_>>();
+        report.sort_by(|a, b| b.count.cmp(&a.count));
+        report
+    }
+
+    /// Gas used by a handled transaction, after the refund.
+    fn tx_gas_used(&self, tx: &Transaction) -> u64 {
+        let end_tx = tx.last_step();
+        debug_assert_eq!(end_tx.exec_state, ExecState::EndTx);
+        let refund = end_tx
+            .bus_mapping_instance
+            .iter()
+            .find(|op_ref| op_ref.target() == operation::Target::TxRefund)
+            .map_or(0, |op_ref| {
+                self.block.container.tx_refund[op_ref.as_usize()].op().value
+            });
+        let tx_gas_used = tx.gas - end_tx.gas_left.0;
+        tx_gas_used - refund.min(tx_gas_used / MAX_REFUND_QUOTIENT_OF_GAS_USED as u64)
+    }
+
+    /// Root of the transactions trie of the handled transactions.
+    /// All transactions handled by this builder are included, so this only
+    /// matches the header of a builder fed a single block.
+    pub fn transactions_root(&self) -> H256 {
+        let txs = self.block.txs().iter().map(|tx| tx.rlp_bytes.clone());
+        ordered_trie_root(&txs.collect::<Vec<_>>())
+    }
+
+    /// Root of the receipts trie of the handled transactions.
+    /// Like [`Self::transactions_root`], this spans every transaction handled
+    /// by this builder.
+    pub fn receipts_root(&self) -> H256 {
+        let logs = self.logs();
+        let mut cumulative_gas_used = 0;
+        let receipts = self
+            .block
+            .txs()
+            .iter()
+            .enumerate()
+            .map(|(idx, tx)| {
+                cumulative_gas_used += self.tx_gas_used(tx);
+                let tx_logs = logs
+                    .iter()
+                    .filter(|log| log.tx_id == idx + 1)
+                    .collect::<Vec<_>>();
+
+                let mut bloom = [0u8; 256];
+                for input in tx_logs.iter().flat_map(|log| {
+                    iter::once(log.address.as_bytes())
+                        .chain(log.topics.iter().map(|t| t.as_bytes()))
+                }) {
+                    let hash = keccak256(input);
+                    for i in [0, 2, 4] {
+                        let bit = (usize::from(hash[i]) << 8 | usize::from(hash[i + 1])) & 2047;
+                        bloom[255 - bit / 8] |= 1 << (bit % 8);
+                    }
+                }
+
+                let mut stream = RlpStream::new_list(4);
+                stream.append(&u8::from(tx.calls()[0].is_success));
+                stream.append(&cumulative_gas_used);
+                stream.append(&bloom[..]);
+                stream.begin_list(tx_logs.len());
+                for log in tx_logs {
+                    stream.begin_list(3);
+                    stream.append(&log.address);
+                    stream.append_list(&log.topics);
+                    stream.append(&log.data);
+                }
+
+                let mut receipt = match tx.tx_type {
+                    TxType::Eip2930 => vec![0x01],
+                    TxType::Eip1559 => vec![0x02],
+                    TxType::L1Msg => vec![0x7e],
+                    _ => vec![],
+                };
+                receipt.extend_from_slice(&stream.out());
+                receipt
+            })
+            .collect::<Vec<_>>();
+        ordered_trie_root(&receipts)
+    }
 }
 
 #[cfg(feature = "test")]
@@ -731,6 +1121,9 @@ impl CircuitInputBuilder {
 
 /// Return all the keccak inputs used during the processing of the current
 /// block.
+/// This covers the tx, signature and PI circuit inputs as well as every
+/// preimage hashed while executing the block: SHA3 operands, contract address
+/// preimages and init code of deployments, and BLOCKHASH lookups.
 pub fn keccak_inputs(block: &Block, code_db: &CodeDB) -> Result<Vec<Vec<u8>>, Error> {
     let mut keccak_inputs = Vec::new();
     // Tx Circuit
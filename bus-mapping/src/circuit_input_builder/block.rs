@@ -6,11 +6,14 @@ use super::{
     CircuitsParams, CopyEvent, ExecStep, ExpEvent,
 };
 use crate::{
-    operation::{OperationContainer, RWCounter},
+    operation::{OperationContainer, RWCounter, RW},
     Error,
 };
-use eth_types::{Address, Hash, ToWord, Word};
-use std::collections::{BTreeMap, HashMap};
+use eth_types::{Address, Hash, ToBigEndian, ToWord, Word};
+use std::{
+    cmp::min,
+    collections::{BTreeMap, HashMap},
+};
 
 /// Context of a [`Block`] which can mutate in a [`Transaction`].
 #[derive(Debug)]
@@ -24,6 +27,8 @@ pub struct BlockContext {
     pub(crate) call_map: HashMap<usize, (usize, usize)>,
     /// Total gas used by previous transactions in this block.
     pub(crate) cumulative_gas_used: u64,
+    /// Mismatches against the geth trace found when not checking strictly.
+    pub(crate) geth_mismatches: Vec<String>,
 }
 
 impl Default for BlockContext {
@@ -39,6 +44,7 @@ impl BlockContext {
             rwc: RWCounter::new(),
             call_map: HashMap::new(),
             cumulative_gas_used: 0,
+            geth_mismatches: Vec::new(),
         }
     }
 }
@@ -327,6 +333,31 @@ impl Block {
             .unwrap_or(self.prev_state_root)
     }
 
+    /// Reconstruct the memory of the call executing `step` (which belongs to
+    /// `tx`) as it was at the start of that step, by replaying the memory
+    /// writes recorded in the operation container before the step.
+    pub fn memory_snapshot(&self, tx: &Transaction, step: &ExecStep) -> Vec<u8> {
+        let call_id = tx.calls()[step.call_index].call_id;
+        let mut memory = vec![0u8; step.memory_size];
+        let mut writes = self
+            .container
+            .memory
+            .iter()
+            .filter(|op| op.rw() == RW::WRITE && op.rwc() < step.rwc && op.op().call_id == call_id)
+            .collect::<Vec<_>>();
+        writes.sort_by_key(|op| op.rwc());
+        for op in writes {
+            let address = op.op().address.0;
+            if address >= memory.len() {
+                continue;
+            }
+            let bytes = op.op().value.to_be_bytes();
+            let end = min(address + bytes.len(), memory.len());
+            memory[address..end].copy_from_slice(&bytes[..end - address]);
+        }
+        memory
+    }
+
     #[cfg(test)]
     pub fn txs_mut(&mut self) -> &mut Vec<Transaction> {
         &mut self.txs
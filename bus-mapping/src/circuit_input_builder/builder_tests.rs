@@ -0,0 +1,854 @@
+use super::*;
+use crate::{
+    mock::BlockData,
+    operation::{AccountField, Target},
+};
+use eth_types::{
+    address, bytecode, geth_types::GethData, state_db::Account, AccessList, AccessListItem,
+    Bytecode, Bytes,
+};
+use mock::{
+    eth,
+    test_ctx::helpers::{account_0_code_account_1_no_code, tx_from_1_to_0},
+    MockTransaction, TestContext, MOCK_ACCOUNTS, MOCK_BASEFEE, MOCK_COINBASE, MOCK_WALLETS,
+};
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+};
+
+/// A block with number 0xcafe and `base_fee`, with `NTX` txs from
+/// `MOCK_WALLETS[0]`, which has `sender_balance`, to `MOCK_ACCOUNTS[0]`, which
+/// has 1 ether. The rest of the txs is set up by `func_tx`.
+fn transfer_ctx<const NTX: usize>(
+    sender_balance: Word,
+    base_fee: Word,
+    func_tx: impl FnOnce(Vec<&mut MockTransaction>),
+) -> Result<TestContext<2, NTX>, eth_types::Error> {
+    TestContext::new(
+        None,
+        |accs| {
+            accs[0]
+                .address(MOCK_WALLETS[0].address())
+                .balance(sender_balance);
+            accs[1].address(MOCK_ACCOUNTS[0]).balance(eth(1));
+        },
+        |mut txs, accs| {
+            for tx in txs.iter_mut() {
+                tx.from(MOCK_WALLETS[0].clone()).to(accs[1].address);
+            }
+            func_tx(txs);
+        },
+        |block, _tx| block.number(0xcafeu64).base_fee_per_gas(base_fee),
+    )
+}
+
+/// Handle all the txs of `block` with a new builder.
+fn build_block(block: &GethData) -> CircuitInputBuilder {
+    let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+    builder
+        .handle_block(&block.eth_block, &block.geth_traces)
+        .unwrap();
+    builder
+}
+
+#[test]
+fn intrinsic_gas() {
+    let access_list = AccessList(vec![
+        AccessListItem {
+            address: address!("0x0000000000000000000000000000000000001111"),
+            storage_keys: [10, 11].map(H256::from_low_u64_be).to_vec(),
+        },
+        AccessListItem {
+            address: address!("0x0000000000000000000000000000000000002222"),
+            storage_keys: [20].map(H256::from_low_u64_be).to_vec(),
+        },
+    ]);
+    let block: GethData = transfer_ctx::<4>(eth(20), *MOCK_BASEFEE, |mut txs| {
+        // transfer
+        txs[0].value(eth(1));
+        // call with 2 zero and 2 non-zero bytes of call data
+        txs[1].input(Bytes::from(vec![0x00, 0x00, 0x01, 0x02]));
+        // creation with init code PUSH1 0 PUSH1 0 RETURN
+        txs[2].to = None;
+        txs[2].input(Bytes::from(vec![0x60, 0x00, 0x60, 0x00, 0xf3]));
+        // EIP-2930 transfer with 2 addresses and 3 storage keys
+        txs[3]
+            .gas(Word::from(0x10000))
+            .transaction_type(1)
+            .access_list(access_list);
+    })
+    .unwrap()
+    .into();
+
+    let builder = build_block(&block);
+
+    let intrinsic_gas = builder
+        .block
+        .txs()
+        .iter()
+        .map(|tx| tx.intrinsic_gas())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        intrinsic_gas,
+        vec![
+            21000,
+            21000 + 2 * 4 + 2 * 16,
+            // 2 zero and 3 non-zero bytes, 1 word of init code
+            53000 + 2 * 4 + 3 * 16 + 2,
+            21000 + 2 * 2400 + 3 * 1900,
+        ]
+    );
+    // the intrinsic gas is what BeginTx charges
+    for tx in builder.block.txs() {
+        assert_eq!(tx.steps()[0].gas_cost.as_u64(), tx.intrinsic_gas());
+    }
+}
+
+#[test]
+fn opcode_histogram_of_loop() {
+    // Count down from 3 to 0, so the loop body runs 3 times.
+    let code = bytecode! {
+        PUSH1(3)
+        JUMPDEST
+        PUSH1(1)
+        SWAP1
+        SUB
+        DUP1
+        PUSH1(2)
+        JUMPI
+        STOP
+    };
+    let block: GethData = TestContext::<2, 1>::simple_ctx_with_bytecode(code)
+        .unwrap()
+        .into();
+
+    let builder = build_block(&block);
+
+    let histogram = builder.block.txs()[0].opcode_histogram();
+    for (op, count) in [
+        (OpcodeId::PUSH1, 7),
+        (OpcodeId::JUMPDEST, 3),
+        (OpcodeId::SWAP1, 3),
+        (OpcodeId::SUB, 3),
+        (OpcodeId::DUP1, 3),
+        (OpcodeId::JUMPI, 3),
+        (OpcodeId::STOP, 1),
+    ] {
+        assert_eq!(histogram[&op], count, "{op:?}");
+    }
+    assert_eq!(histogram.len(), 7);
+}
+
+#[test]
+#[cfg(feature = "enable-stack")]
+fn non_strict_geth_check_collects_mismatches() {
+    let code = bytecode! {
+        // touch the memory so that the memory check has something to compare
+        PUSH1(0)
+        MLOAD
+        POP
+        PUSH1(1)
+        PUSH1(2)
+        ADD
+        STOP
+    };
+    let mut block: GethData = TestContext::<2, 1>::simple_ctx_with_bytecode(code)
+        .unwrap()
+        .into();
+    // Corrupt the top of the stack seen by ADD, which both the second PUSH1
+    // and the stack check before ADD compare against. The stack of STOP is
+    // made consistent with it, since the builder continues from the geth
+    // stack after a mismatch.
+    let struct_logs = &mut block.geth_traces[0].struct_logs;
+    assert_eq!(struct_logs[5].op, OpcodeId::ADD);
+    struct_logs[5].stack.0[1] = Word::from(7);
+    assert_eq!(struct_logs[6].op, OpcodeId::STOP);
+    struct_logs[6].stack.0[0] = Word::from(8);
+
+    let mut builder = BlockData::new_from_geth_data(block.clone())
+        .new_circuit_input_builder()
+        .with_strict_geth_check(false);
+    builder
+        .handle_block(&block.eth_block, &block.geth_traces)
+        .unwrap();
+
+    // Steps are counted from BeginTx, so PUSH1(2) is step 5 and ADD step 6.
+    let mismatches = builder.geth_mismatches();
+    assert_eq!(mismatches.len(), 2);
+    assert!(mismatches[0].contains("step 5") && mismatches[0].ends_with("2 != 7"));
+    assert!(mismatches[1].contains("step 6") && mismatches[1].ends_with("stack before ADD"));
+}
+
+#[test]
+fn step_observer_counts_opcodes() {
+    let code = bytecode! {
+        PUSH1(3)
+        JUMPDEST
+        PUSH1(1)
+        SWAP1
+        SUB
+        DUP1
+        PUSH1(2)
+        JUMPI
+        STOP
+    };
+    let block: GethData = TestContext::<2, 1>::simple_ctx_with_bytecode(code)
+        .unwrap()
+        .into();
+
+    let counts = Arc::new(Mutex::new(HashMap::new()));
+    let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+    builder.set_step_observer({
+        let counts = counts.clone();
+        move |step| {
+            if let ExecState::Op(op) = step.exec_state {
+                *counts.lock().unwrap().entry(op).or_insert(0) += 1;
+            }
+        }
+    });
+    builder
+        .handle_block(&block.eth_block, &block.geth_traces)
+        .unwrap();
+
+    assert_eq!(
+        *counts.lock().unwrap(),
+        builder.block.txs()[0].opcode_histogram()
+    );
+}
+
+#[test]
+fn revert_reason() {
+    let reason = "insufficient balance";
+    // Error(string) selector, offset, length and the right padded string
+    let mut revert_data = vec![0x08, 0xc3, 0x79, 0xa0];
+    revert_data.extend(Word::from(0x20).to_be_bytes());
+    revert_data.extend(Word::from(reason.len()).to_be_bytes());
+    revert_data.extend(reason.as_bytes());
+    revert_data.resize(4 + 3 * 32, 0);
+
+    let mut code = Bytecode::default();
+    for (index, word) in revert_data.chunks(32).enumerate() {
+        code.op_mstore(index * 32, Word::from_big_endian(word));
+    }
+    code.append(&bytecode! {
+        PUSH1(revert_data.len())
+        PUSH1(0)
+        REVERT
+    });
+
+    let block: GethData = TestContext::<2, 2>::new(
+        None,
+        account_0_code_account_1_no_code(code),
+        |mut txs, accs| {
+            txs[0].from(accs[1].address).to(accs[0].address);
+            // a plain transfer succeeds without any revert reason
+            txs[1].from(accs[1].address).to(accs[1].address);
+        },
+        |block, _tx| block,
+    )
+    .unwrap()
+    .into();
+
+    let builder = build_block(&block);
+
+    let txs = builder.block.txs();
+    assert_eq!(txs[0].return_value, revert_data);
+    assert_eq!(txs[0].revert_reason().as_deref(), Some(reason));
+    assert_eq!(txs[1].revert_reason(), None);
+}
+
+#[test]
+fn tx_below_intrinsic_gas_is_rejected() {
+    // A transfer needs 21000 gas, so 20000 fails the intrinsic gas check
+    // of the tracer.
+    let transfer = |gas: u64| {
+        transfer_ctx::<1>(eth(20), *MOCK_BASEFEE, |mut txs| {
+            txs[0].value(eth(1)).gas(Word::from(gas));
+        })
+    };
+    let result = transfer(20000);
+    assert!(matches!(result, Err(eth_types::Error::TracingError(_))));
+
+    // The builder rejects it as well before any BeginTx step is built,
+    // when it's given such a tx along with the trace of a valid one.
+    let mut block: GethData = transfer(21000).unwrap().into();
+    block.eth_block.transactions[0].gas = Word::from(20000);
+
+    let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+    let err = builder
+        .handle_block(&block.eth_block, &block.geth_traces)
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        Error::IntrinsicGasExceedsGasLimit(20000, 21000)
+    ));
+    assert!(builder.block.txs().is_empty());
+}
+
+#[cfg(not(feature = "scroll"))]
+fn transfer_with_sender_balance(balance: Word) -> Result<TestContext<2, 1>, eth_types::Error> {
+    transfer_ctx(balance, *MOCK_BASEFEE, |mut txs| {
+        txs[0]
+            .value(eth(1))
+            .gas(Word::from(21000))
+            .gas_price(Word::from(10));
+    })
+}
+
+#[test]
+#[cfg(not(feature = "scroll"))]
+fn sender_balance_covers_value_and_max_gas_cost() {
+    // value + gas * gas_price, with no L1 fee outside of scroll.
+    let upfront_cost = eth(1) + Word::from(21000 * 10);
+
+    assert!(matches!(
+        transfer_with_sender_balance(upfront_cost - 1),
+        Err(eth_types::Error::TracingError(_))
+    ));
+
+    let block: GethData = transfer_with_sender_balance(upfront_cost).unwrap().into();
+    let builder = build_block(&block);
+
+    // BeginTx deducts the max gas cost before transferring the value.
+    let sender = MOCK_WALLETS[0].address();
+    let balance_writes: Vec<_> = builder.block.txs()[0].steps()[0]
+        .bus_mapping_instance
+        .iter()
+        .filter(|op_ref| op_ref.target() == Target::Account)
+        .map(|op_ref| builder.block.container.account[op_ref.as_usize()].op())
+        .filter(|op| op.address == sender && op.field == AccountField::Balance)
+        .map(|op| (op.value_prev, op.value))
+        .collect();
+    assert_eq!(
+        balance_writes,
+        vec![(upfront_cost, eth(1)), (eth(1), Word::zero())]
+    );
+    // All gas is used, so nothing is refunded.
+    assert_eq!(builder.sdb.get_account(&sender).1.balance, Word::zero());
+}
+
+#[test]
+#[cfg(not(feature = "scroll"))]
+fn balance_deltas_of_transfer() {
+    let block: GethData = transfer_with_sender_balance(eth(20)).unwrap().into();
+    let builder = build_block(&block);
+
+    let deltas = builder.block.txs()[0].balance_deltas(&builder.block.container);
+    let fees = Word::from(21000 * 10);
+    assert_eq!(
+        deltas[&MOCK_WALLETS[0].address()],
+        (eth(20), eth(20) - eth(1) - fees)
+    );
+    assert_eq!(deltas[&MOCK_ACCOUNTS[0]], (eth(1), eth(2)));
+}
+
+#[test]
+fn access_list_snapshot_of_each_tx() {
+    let code = bytecode! {
+        PUSH1(0x03)
+        SLOAD
+        POP
+        PUSH1(0x01)
+        PUSH1(0x05)
+        SSTORE
+        PUSH20(MOCK_ACCOUNTS[2].to_word())
+        BALANCE
+        POP
+        STOP
+    };
+    let block: GethData = TestContext::<3, 2>::new(
+        None,
+        |accs| {
+            accs[0].address(MOCK_WALLETS[0].address()).balance(eth(20));
+            accs[1].address(MOCK_ACCOUNTS[0]).code(code);
+            accs[2].address(MOCK_ACCOUNTS[1]).balance(eth(1));
+        },
+        |mut txs, accs| {
+            txs[0]
+                .from(MOCK_WALLETS[0].clone())
+                .to(accs[1].address)
+                .gas(Word::from(100_000));
+            txs[1].from(MOCK_WALLETS[0].clone()).to(accs[2].address);
+        },
+        |block, _tx| block.number(0xcafeu64),
+    )
+    .unwrap()
+    .into();
+
+    let builder = build_block(&block);
+
+    let txs = builder.block.txs();
+    let (addresses, slots) = txs[0].access_list_snapshot();
+    for addr in [
+        MOCK_WALLETS[0].address(),
+        MOCK_ACCOUNTS[0],
+        MOCK_ACCOUNTS[2],
+    ] {
+        assert!(addresses.contains(&addr));
+    }
+    assert!(!addresses.contains(&MOCK_ACCOUNTS[1]));
+    assert_eq!(
+        slots,
+        &HashMap::from([(
+            MOCK_ACCOUNTS[0],
+            HashSet::from([Word::from(3), Word::from(5)])
+        )])
+    );
+
+    // The second tx starts from fresh access lists.
+    let (addresses, slots) = txs[1].access_list_snapshot();
+    assert!(addresses.contains(&MOCK_ACCOUNTS[1]));
+    assert!(!addresses.contains(&MOCK_ACCOUNTS[0]));
+    assert!(!addresses.contains(&MOCK_ACCOUNTS[2]));
+    assert!(slots.is_empty());
+}
+
+#[test]
+fn block_index_of_each_tx() {
+    let block: GethData = transfer_ctx::<3>(eth(20), *MOCK_BASEFEE, |_txs| {})
+        .unwrap()
+        .into();
+
+    let mut builder = build_block(&block);
+
+    let indices: Vec<usize> = builder
+        .block
+        .txs()
+        .iter()
+        .map(|tx| tx.block_index())
+        .collect();
+    assert_eq!(indices, vec![0, 1, 2]);
+
+    // The index is the position in the builder's tx list, not the one
+    // reported by the node.
+    let mut eth_tx = block.eth_block.transactions[2].clone();
+    eth_tx.transaction_index = None;
+    let tx = builder.new_tx(&eth_tx, true).unwrap();
+    assert_eq!(tx.block_index(), 3);
+}
+
+#[test]
+fn mixed_legacy_and_eip1559_txs() {
+    let base_fee = Word::from(10);
+    let block: GethData = transfer_ctx::<2>(eth(20), base_fee, |mut txs| {
+        txs[0].gas_price(Word::from(20));
+        // The effective gas price is base_fee + min(5, 30 - base_fee) = 15.
+        txs[1]
+            .max_fee_per_gas(Word::from(30))
+            .max_priority_fee_per_gas(Word::from(5))
+            .transaction_type(2);
+    })
+    .unwrap()
+    .into();
+    let coinbase = block.eth_block.author.unwrap();
+
+    let builder = build_block(&block);
+
+    let txs = builder.block.txs();
+    assert_eq!(txs[0].tx_type, TxType::Eip155);
+    assert_eq!(txs[1].tx_type, TxType::Eip1559);
+    // The typed tx is signed over its EIP-2718 envelope, with a y-parity v.
+    assert!(txs[0].rlp_unsigned_bytes[0] >= 0xc0);
+    assert_eq!(txs[1].rlp_unsigned_bytes[0], 0x02);
+    assert!(txs[1].signature.v <= 1);
+
+    for (tx, gas_price) in txs.iter().zip([20, 15].map(Word::from)) {
+        assert_eq!(tx.gas_price, gas_price);
+
+        let end_tx = tx.last_step();
+        assert_eq!(end_tx.exec_state, ExecState::EndTx);
+        let gas_used = tx.gas - end_tx.gas_left.0;
+        let effective_tip = if cfg!(feature = "scroll") {
+            gas_price
+        } else {
+            gas_price - base_fee
+        };
+        let coinbase_reward = end_tx
+            .bus_mapping_instance
+            .iter()
+            .filter(|op_ref| op_ref.target() == Target::Account)
+            .map(|op_ref| builder.block.container.account[op_ref.as_usize()].op())
+            .find(|op| op.address == coinbase && op.field == AccountField::Balance)
+            .map(|op| op.value - op.value_prev)
+            .unwrap();
+        assert_eq!(
+            coinbase_reward,
+            effective_tip * gas_used + Word::from(tx.l1_fee())
+        );
+    }
+}
+
+#[test]
+fn fees_go_to_block_coinbase() {
+    // A fee vault that isn't the block's coinbase doesn't collect the fees.
+    let vault = address!("0x000000000000000000000000000000000000fee0");
+    let block: GethData = TestContext::<4, 1>::new(
+        None,
+        |accs| {
+            accs[0].address(MOCK_WALLETS[0].address()).balance(eth(20));
+            accs[1].address(MOCK_ACCOUNTS[0]).balance(eth(1));
+            accs[2].address(vault).balance(eth(1));
+            accs[3].address(*MOCK_COINBASE).balance(eth(1));
+        },
+        |mut txs, accs| {
+            txs[0]
+                .from(MOCK_WALLETS[0].clone())
+                .to(accs[1].address)
+                .gas_price(Word::from(20));
+        },
+        |block, _tx| block.number(0xcafeu64).author(*MOCK_COINBASE),
+    )
+    .unwrap()
+    .into();
+
+    let builder = build_block(&block);
+
+    // The mock base fee is zero, so the whole gas price is the tip.
+    let tx = &builder.block.txs()[0];
+    let fee = Word::from(20 * GasCost::TX.as_u64() + tx.l1_fee());
+    assert_eq!(builder.sdb.get_balance(&MOCK_COINBASE), eth(1) + fee);
+    assert_eq!(builder.sdb.get_balance(&vault), eth(1));
+}
+
+#[test]
+fn verify_block_gas_used() {
+    let block: GethData = transfer_ctx::<2>(eth(20), *MOCK_BASEFEE, |mut txs| {
+        txs[0].value(eth(1));
+        // 2 non-zero bytes of call data cost 2 * 16 more
+        txs[1].input(Bytes::from(vec![0x01, 0x02]));
+    })
+    .unwrap()
+    .into();
+
+    let builder = build_block(&block);
+
+    let gas_used = 21000 + 21000 + 2 * 16;
+    builder.verify_block_gas_used(gas_used).unwrap();
+    assert!(matches!(
+        builder.verify_block_gas_used(gas_used + 1),
+        Err(Error::BlockGasUsedMismatch(expected, actual))
+            if expected == gas_used + 1 && actual == gas_used
+    ));
+}
+
+#[test]
+fn end_tx_refund_is_capped_at_fifth_of_gas_used() {
+    // Clearing 3 slots accumulates a refund larger than gas used / 5.
+    let code = bytecode! {
+        PUSH1(0x00)
+        PUSH1(0x00)
+        SSTORE
+        PUSH1(0x00)
+        PUSH1(0x01)
+        SSTORE
+        PUSH1(0x00)
+        PUSH1(0x02)
+        SSTORE
+        STOP
+    };
+    let block: GethData = TestContext::<2, 1>::new(
+        None,
+        |accs| {
+            accs[0]
+                .address(MOCK_ACCOUNTS[0])
+                .code(code)
+                .storage((0..3u64).map(|slot| (Word::from(slot), Word::one())));
+            accs[1].address(MOCK_ACCOUNTS[1]).balance(eth(1));
+        },
+        tx_from_1_to_0,
+        |block, _tx| block.number(0xcafeu64),
+    )
+    .unwrap()
+    .into();
+
+    let builder = build_block(&block);
+
+    let tx = &builder.block.txs()[0];
+    let end_tx = tx.last_step();
+    let refund = end_tx
+        .bus_mapping_instance
+        .iter()
+        .find(|op_ref| op_ref.target() == Target::TxRefund)
+        .map(|op_ref| {
+            builder.block.container.tx_refund[op_ref.as_usize()]
+                .op()
+                .value
+        })
+        .unwrap();
+    assert_eq!(refund, 3 * GasCost::SSTORE_CLEARS_SCHEDULE.as_u64());
+
+    let gas_used = tx.gas - end_tx.gas_left.0;
+    let max_refund = gas_used / MAX_REFUND_QUOTIENT_OF_GAS_USED as u64;
+    assert!(refund > max_refund);
+
+    // Only the capped refund is paid back to the sender.
+    let sender_balance = end_tx
+        .bus_mapping_instance
+        .iter()
+        .filter(|op_ref| op_ref.target() == Target::Account)
+        .map(|op_ref| builder.block.container.account[op_ref.as_usize()].op())
+        .find(|op| op.address == MOCK_ACCOUNTS[1] && op.field == AccountField::Balance)
+        .unwrap();
+    assert_eq!(
+        sender_balance.value - sender_balance.value_prev,
+        tx.gas_price * (end_tx.gas_left.0 + max_refund)
+    );
+    builder
+        .verify_block_gas_used(gas_used - max_refund)
+        .unwrap();
+}
+
+#[test]
+fn gas_mismatch_report_groups_by_opcode() {
+    let code = bytecode! {
+        // Memory expansion
+        PUSH1(0x01)
+        PUSH1(0x40)
+        MSTORE
+        // Cold, then warm account access
+        PUSH20(MOCK_ACCOUNTS[2].to_word())
+        BALANCE
+        PUSH20(MOCK_ACCOUNTS[2].to_word())
+        BALANCE
+        PUSH20(MOCK_ACCOUNTS[3].to_word())
+        EXTCODESIZE
+        // Cold, then warm slot access
+        PUSH1(0x00)
+        SLOAD
+        PUSH1(0x00)
+        SLOAD
+        STOP
+    };
+    let block: GethData = TestContext::<2, 1>::simple_ctx_with_bytecode(code)
+        .unwrap()
+        .into();
+    let builder = |warm: &[Address]| {
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        for address in warm {
+            builder.sdb.add_account_to_access_list(*address);
+        }
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+        builder
+    };
+    assert_eq!(builder(&[]).gas_mismatch_report(&block.geth_traces), vec![]);
+
+    // The builder wrongly believes both accounts are already warm, so it
+    // models the cold BALANCE and EXTCODESIZE as warm ones.
+    let pc_of = |op| {
+        block.geth_traces[0]
+            .struct_logs
+            .iter()
+            .find(|log| log.op == op)
+            .unwrap()
+            .pc
+            .0
+    };
+    let mut report =
+        builder(&[MOCK_ACCOUNTS[2], MOCK_ACCOUNTS[3]]).gas_mismatch_report(&block.geth_traces);
+    // Both have the same count.
+    report.sort_by_key(|mismatch| mismatch.example_pcs[0]);
+    assert_eq!(
+        report,
+        vec![
+            OpcodeGasMismatch {
+                opcode: OpcodeId::BALANCE,
+                count: 1,
+                example_pcs: vec![pc_of(OpcodeId::BALANCE)],
+            },
+            OpcodeGasMismatch {
+                opcode: OpcodeId::EXTCODESIZE,
+                count: 1,
+                example_pcs: vec![pc_of(OpcodeId::EXTCODESIZE)],
+            },
+        ]
+    );
+}
+
+#[cfg(not(feature = "scroll"))]
+#[test]
+fn coinbase_sends_tx() {
+    let coinbase = MOCK_WALLETS[0].address();
+    let block: GethData = TestContext::<2, 1>::new(
+        None,
+        |accs| {
+            accs[0].address(coinbase).balance(eth(10));
+            accs[1].address(MOCK_ACCOUNTS[0]);
+        },
+        |mut txs, accs| {
+            txs[0]
+                .from(MOCK_WALLETS[0].clone())
+                .to(accs[1].address)
+                .value(eth(1));
+        },
+        |block, _tx| block.number(0xcafeu64).author(coinbase),
+    )
+    .unwrap()
+    .into();
+
+    let builder = build_block(&block);
+
+    // EndTx refunds the sender and then rewards the coinbase on top of the
+    // refunded balance.
+    let tx = &builder.block.txs()[0];
+    let end_tx = tx.last_step();
+    let balance_writes = end_tx
+        .bus_mapping_instance
+        .iter()
+        .filter(|op_ref| op_ref.target() == Target::Account)
+        .map(|op_ref| builder.block.container.account[op_ref.as_usize()].op())
+        .filter(|op| op.address == coinbase && op.field == AccountField::Balance)
+        .collect::<Vec<_>>();
+    assert_eq!(balance_writes.len(), 2);
+    assert_eq!(balance_writes[1].value_prev, balance_writes[0].value);
+
+    // The coinbase gets its own tip back, so only the value and the burnt
+    // base fee leave its balance.
+    let gas_used = tx.gas - end_tx.gas_left.0;
+    let base_fee = block.eth_block.base_fee_per_gas.unwrap();
+    let tip = (tx.gas_price - base_fee) * gas_used;
+    assert!(!tip.is_zero());
+    assert_eq!(
+        builder.sdb.get_account(&coinbase).1.balance,
+        eth(10) - eth(1) - tx.gas_price * gas_used + tip
+    );
+}
+
+#[test]
+fn blob_and_set_code_txs_are_rejected() {
+    let sender = MOCK_ACCOUNTS[0];
+    let mut sdb = StateDB::new();
+    sdb.set_account(&sender, Account::default());
+    // EIP-4844 blob and EIP-7702 set-code transactions
+    for tx_type in [3u64, 4] {
+        let eth_tx = eth_types::Transaction {
+            from: sender,
+            to: Some(MOCK_ACCOUNTS[1]),
+            transaction_type: Some(tx_type.into()),
+            ..Default::default()
+        };
+
+        let err = Transaction::new(1, &sdb, &mut CodeDB::new(), &eth_tx, true).unwrap_err();
+        assert!(matches!(err, Error::UnsupportedTxType(t) if t == tx_type));
+    }
+}
+
+#[test]
+fn transactions_root_of_two_txs() {
+    let block: GethData = transfer_ctx::<2>(eth(20), *MOCK_BASEFEE, |mut txs| {
+        txs[0].value(eth(1));
+        txs[1].value(eth(2));
+    })
+    .unwrap()
+    .into();
+
+    let builder = build_block(&block);
+
+    // The trie itself is checked against geth fixtures in `eth_types::trie`,
+    // so here only the items the builder puts in the tries are checked:
+    // the signed txs as sent, and a successful receipt with no logs for
+    // each of the two 21000 gas transfers.
+    let txs = block
+        .eth_block
+        .transactions
+        .iter()
+        .map(|tx| tx.rlp().to_vec())
+        .collect::<Vec<_>>();
+    assert_eq!(builder.transactions_root(), ordered_trie_root(&txs));
+
+    let receipts = [21000u64, 42000]
+        .into_iter()
+        .map(|cumulative_gas_used| {
+            let mut stream = RlpStream::new_list(4);
+            stream.append(&1u8);
+            stream.append(&cumulative_gas_used);
+            stream.append(&[0u8; 256][..]);
+            stream.begin_list(0);
+            stream.out().to_vec()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(builder.receipts_root(), ordered_trie_root(&receipts));
+}
+
+#[test]
+fn effective_gas_price_of_legacy_and_eip1559_txs() {
+    let base_fee = Word::from(10);
+    let block: GethData = transfer_ctx::<3>(eth(20), base_fee, |mut txs| {
+        txs[0].gas_price(Word::from(20));
+        // Bound by the tip: min(30, 10 + 5)
+        txs[1]
+            .max_fee_per_gas(Word::from(30))
+            .max_priority_fee_per_gas(Word::from(5))
+            .transaction_type(2);
+        // Bound by the fee cap: min(30, 10 + 25)
+        txs[2]
+            .max_fee_per_gas(Word::from(30))
+            .max_priority_fee_per_gas(Word::from(25))
+            .transaction_type(2);
+    })
+    .unwrap()
+    .into();
+
+    let builder = build_block(&block);
+
+    let txs = builder.block.txs();
+    assert_eq!(
+        txs.iter()
+            .map(|tx| tx.effective_gas_price(base_fee))
+            .collect::<Vec<_>>(),
+        [20, 15, 30].map(Word::from)
+    );
+    // The price the block was built with.
+    for tx in txs {
+        assert_eq!(tx.effective_gas_price(base_fee), tx.gas_price);
+    }
+
+    // A tip cap so large that adding the base fee overflows.
+    let mut tx = txs[1].clone();
+    tx.gas_tip_cap = Word::MAX;
+    assert_eq!(tx.effective_gas_price(base_fee), tx.gas_fee_cap);
+}
+
+#[test]
+fn step_limit_exceeded() {
+    // Loops until running out of gas.
+    let code = bytecode! {
+        JUMPDEST
+        PUSH1(0x00)
+        JUMP
+    };
+    let block: GethData = TestContext::<2, 1>::new(
+        None,
+        account_0_code_account_1_no_code(code),
+        |mut txs, accs| {
+            txs[0]
+                .from(accs[1].address)
+                .to(accs[0].address)
+                .gas(Word::from(30_000));
+        },
+        |block, _tx| block,
+    )
+    .unwrap()
+    .into();
+    let num_steps = block.geth_traces[0].struct_logs.len();
+    assert!(num_steps > 100);
+
+    let mut builder = BlockData::new_from_geth_data(block.clone())
+        .new_circuit_input_builder()
+        .with_max_steps(100);
+    assert!(matches!(
+        builder.handle_block(&block.eth_block, &block.geth_traces),
+        Err(Error::StepLimitExceeded(100))
+    ));
+    // Rejected up front, even the BeginTx step isn't built.
+    assert!(builder.block.container.call_context.is_empty());
+
+    let mut builder = BlockData::new_from_geth_data(block.clone())
+        .new_circuit_input_builder()
+        .with_max_steps(num_steps);
+    builder
+        .handle_block(&block.eth_block, &block.geth_traces)
+        .unwrap();
+}
@@ -170,12 +170,20 @@ pub struct ReversionGroup {
     /// List of `step_index` and [`OperationRef`] that have been done in this
     /// group.
     pub(crate) op_refs: Vec<(usize, OperationRef)>,
+    /// [`StateDB`](eth_types::state_db::StateDB) checkpoint taken when the
+    /// call that initiated this group was entered. Not set for the root call,
+    /// since part of `BeginTx` (e.g. nonce increase) must not be reverted.
+    pub(crate) sdb_checkpoint: Option<usize>,
 }
 
 impl ReversionGroup {
     /// Creates a new `ReversionGroup` instance from the calls and operation
     /// references lists.
     pub fn new(calls: Vec<(usize, usize)>, op_refs: Vec<(usize, OperationRef)>) -> Self {
-        Self { calls, op_refs }
+        Self {
+            calls,
+            op_refs,
+            sdb_checkpoint: None,
+        }
     }
 }
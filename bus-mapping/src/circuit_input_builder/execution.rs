@@ -6,10 +6,10 @@ use std::{
 };
 
 use crate::{
-    circuit_input_builder::CallContext,
+    circuit_input_builder::{CallContext, Transaction},
     error::{ExecError, OogError},
     exec_trace::OperationRef,
-    operation::RWCounter,
+    operation::{OperationContainer, RWCounter},
     precompile::{PrecompileAuxData, PrecompileCalls},
 };
 use eth_types::{
@@ -28,6 +28,67 @@ use halo2_proofs::{
     plonk::Expression,
 };
 
+/// Breakdown of the gas cost of an [`ExecStep`], where `base`,
+/// `memory_expansion`, `dynamic` and `access_list` add up to the step's
+/// `gas_cost`. By default the cost beyond the constant gas of the opcode is
+/// all `dynamic`, opcode handlers that know better split it further.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GasBreakdown {
+    /// Constant gas cost of the opcode
+    pub base: u64,
+    /// Cost of the memory expansion
+    pub memory_expansion: u64,
+    /// Opcode specific cost, depending on its inputs
+    pub dynamic: u64,
+    /// Extra cost of accessing a cold account or storage slot (EIP-2929)
+    pub access_list: u64,
+    /// Change of the accumulated gas refund done by the step
+    pub refund_delta: i64,
+}
+
+impl GasBreakdown {
+    /// Breakdown of `gas_cost` into the constant gas of `op` and the dynamic
+    /// rest.
+    pub fn new(op: OpcodeId, gas_cost: GasCost) -> Self {
+        let base = op.constant_gas_cost().as_u64().min(gas_cost.as_u64());
+        Self {
+            base,
+            dynamic: gas_cost.as_u64() - base,
+            ..Default::default()
+        }
+    }
+
+    /// Set the memory expansion cost computed by the builder, taking it out of
+    /// `dynamic`.
+    pub fn set_memory_expansion(&mut self, cost: u64) {
+        self.memory_expansion = cost;
+        self.dynamic = self.dynamic.saturating_sub(cost);
+    }
+
+    /// Set the extra cost of a cold access computed by the builder, taking it
+    /// out of `dynamic`.
+    pub fn set_access_list(&mut self, cost: u64) {
+        self.access_list = cost;
+        self.dynamic = self.dynamic.saturating_sub(cost);
+    }
+
+    /// Set the extra cost of accessing an account, which is
+    /// `COLD_ACCOUNT_ACCESS - WARM_ACCESS` when it is cold.
+    pub fn set_account_access(&mut self, is_warm: bool) {
+        let cost = if is_warm {
+            0
+        } else {
+            GasCost::COLD_ACCOUNT_ACCESS.as_u64() - GasCost::WARM_ACCESS.as_u64()
+        };
+        self.set_access_list(cost);
+    }
+
+    /// Total gas cost, without the refund.
+    pub fn total(&self) -> u64 {
+        self.base + self.memory_expansion + self.dynamic + self.access_list
+    }
+}
+
 /// An execution step of the EVM.
 #[derive(Clone, Debug)]
 pub struct ExecStep {
@@ -47,8 +108,12 @@ pub struct ExecStep {
     pub gas_cost: GasCost,
     /// Accumulated gas refund
     pub gas_refund: Gas,
+    /// Breakdown of `gas_cost`
+    pub gas_breakdown: GasBreakdown,
     /// Call index within the Transaction.
     pub call_index: usize,
+    /// Depth of the call frame the step executed in, 0 for the root call.
+    pub(crate) call_depth: usize,
     /// The global counter when this step was executed.
     pub rwc: RWCounter,
     /// Reversible Write Counter.  Counter of write operations in the call that
@@ -86,7 +151,9 @@ impl ExecStep {
             gas_left: step.gas,
             gas_cost: step.gas_cost,
             gas_refund: step.refund,
+            gas_breakdown: GasBreakdown::new(step.op, step.gas_cost),
             call_index: call_ctx.index,
+            call_depth: (step.depth as usize).saturating_sub(1),
             rwc,
             reversible_write_counter,
             reversible_write_counter_delta: 0,
@@ -98,6 +165,12 @@ impl ExecStep {
         }
     }
 
+    /// Returns the depth of the call frame this step executed in, where the
+    /// root call of the transaction is at depth 0.
+    pub fn call_depth(&self) -> usize {
+        self.call_depth
+    }
+
     /// Returns `true` if `error` is oog and stack related..
     pub fn oog_or_stack_error(&self) -> bool {
         matches!(
@@ -115,6 +188,27 @@ impl ExecStep {
     pub fn is_precompile_oog_err(&self) -> bool {
         matches!(self.error, Some(ExecError::OutOfGas(OogError::Precompile)))
     }
+
+    /// Returns the stack of the call at the start of this step, from bottom to
+    /// top, reconstructed from the stack operations of `tx` recorded in
+    /// `container` before this step.
+    pub fn stack_snapshot(&self, tx: &Transaction, container: &OperationContainer) -> Vec<Word> {
+        let call_id = tx.calls()[self.call_index].call_id;
+        let mut stack = vec![Word::zero(); self.stack_size];
+        for op in container
+            .stack
+            .iter()
+            .filter(|op| op.rwc() < self.rwc && op.op().call_id == call_id)
+        {
+            // The bottom of the stack is at address 1023.
+            if let Some(index) = 1023usize.checked_sub(op.op().address.0) {
+                if index < self.stack_size {
+                    stack[index] = op.op().value;
+                }
+            }
+        }
+        stack
+    }
 }
 
 impl Default for ExecStep {
@@ -127,7 +221,9 @@ impl Default for ExecStep {
             gas_left: Gas(0),
             gas_cost: GasCost(0),
             gas_refund: Gas(0),
+            gas_breakdown: GasBreakdown::default(),
             call_index: 0,
+            call_depth: 0,
             rwc: RWCounter(0),
             reversible_write_counter: 0,
             reversible_write_counter_delta: 0,
@@ -20,6 +20,7 @@ use crate::{
         TxReceiptField, TxReceiptOp, RW,
     },
     precompile::PrecompileCalls,
+    util::GethTraceSanityCheckLevel,
     Error,
 };
 use eth_types::{
@@ -52,9 +53,33 @@ pub struct CircuitInputStateRef<'a> {
     pub tx: &'a mut Transaction,
     /// Transaction Context
     pub tx_ctx: &'a mut TransactionContext,
+    /// Geth trace sanity check level
+    pub geth_trace_check_level: GethTraceSanityCheckLevel,
 }
 
 impl<'a> CircuitInputStateRef<'a> {
+    /// Report a value that differs from the geth trace. It's ignored with the
+    /// [`GethTraceSanityCheckLevel::None`] level, panics with the
+    /// [`GethTraceSanityCheckLevel::Strict`] level, and otherwise is logged and
+    /// recorded so that all of them can be inspected after the block.
+    pub(crate) fn geth_mismatch(&mut self, location: &str, mismatch: String) {
+        if !self.geth_trace_check_level.should_check() {
+            return;
+        }
+        let mismatch = format!(
+            "tx {} step {} ({}): {}",
+            self.tx_ctx.id(),
+            self.tx.steps().len(),
+            location,
+            mismatch
+        );
+        if self.geth_trace_check_level.should_panic() {
+            panic!("geth trace mismatch in {mismatch}");
+        }
+        log::warn!("geth trace mismatch in {mismatch}");
+        self.block_ctx.geth_mismatches.push(mismatch);
+    }
+
     /// Create a new step from a `GethExecStep`
     pub fn new_step(&self, geth_step: &GethExecStep) -> Result<ExecStep, Error> {
         let call_ctx = self.tx_ctx.call_ctx()?;
@@ -104,6 +129,7 @@ impl<'a> CircuitInputStateRef<'a> {
 
             memory_size: call_ctx.memory.len(),
             call_index: call_ctx.index,
+            call_depth: ref_step.call_depth,
             reversible_write_counter: call_ctx.reversible_write_counter,
             rwc: self.block_ctx.rwc,
             log_id: self.tx_ctx.log_id,
@@ -970,6 +996,15 @@ impl<'a> CircuitInputStateRef<'a> {
 
         self.tx_ctx
             .push_call_ctx(call_idx, call_data, call.is_success);
+        if !call.is_success {
+            // The call starts a new reversion group, remember the state to
+            // restore once it reverts.
+            self.tx_ctx
+                .reversion_groups
+                .last_mut()
+                .expect("reversion_groups should not be empty for non-persistent call")
+                .sdb_checkpoint = Some(self.sdb.snapshot());
+        }
         self.tx.push_call(call);
 
         self.block_ctx
@@ -997,10 +1032,10 @@ impl<'a> CircuitInputStateRef<'a> {
 
     /// Return the contract address of a CREATE2 step.  This is calculated
     /// deterministically from the arguments in the stack.
-    pub(crate) fn create2_address(&self, _step: &GethExecStep) -> Result<Address, Error> {
-        let call_ctx = self.call_ctx()?;
+    pub(crate) fn create2_address(&mut self, _step: &GethExecStep) -> Result<Address, Error> {
         #[cfg(feature = "enable-stack")]
-        assert_eq!(call_ctx.stack, _step.stack);
+        crate::util::check_geth_eq!(self, self.call_ctx()?.stack, _step.stack);
+        let call_ctx = self.call_ctx()?;
         let salt = call_ctx.stack.nth_last(3)?;
         let init_code = get_create_init_code(call_ctx)?.to_vec();
         let address = get_create2_address(self.call()?.address, salt.to_be_bytes(), init_code);
@@ -1057,11 +1092,11 @@ impl<'a> CircuitInputStateRef<'a> {
     /// Parse [`Call`] from a *CALL*/CREATE* step without information about success and persistent.
     pub fn parse_call_partial(&mut self, step: &GethExecStep) -> Result<Call, Error> {
         let kind = CallKind::try_from(step.op)?;
-        let caller = self.call()?;
+        let caller = self.call()?.clone();
         let caller_ctx = self.call_ctx()?.clone();
         let stack = &caller_ctx.stack;
         #[cfg(feature = "enable-stack")]
-        assert_eq!(stack, &step.stack);
+        crate::util::check_geth_eq!(self, stack, &step.stack);
 
         let (caller_address, address, value) = match kind {
             CallKind::Call => (
@@ -1125,6 +1160,7 @@ impl<'a> CircuitInputStateRef<'a> {
             caller_id: caller.call_id,
             last_callee_id: 0,
             kind,
+            // Staticness is inherited, so nested calls can't escape it.
             is_static: kind == CallKind::StaticCall || caller.is_static,
             is_root: false,
             is_persistent: caller.is_persistent,
@@ -1293,6 +1329,12 @@ impl<'a> CircuitInputStateRef<'a> {
             }
         }
 
+        // The reversion ops above already undo all the reversible writes, restoring
+        // the checkpoint also undoes the side effects which aren't tracked by ops.
+        if let Some(checkpoint) = reversion_group.sdb_checkpoint {
+            self.sdb.revert_to(checkpoint);
+        }
+
         // Set calls' `rw_counter_end_of_reversion`
         let rwc = self.block_ctx.rwc.0 - 1;
         for (call_idx, reversible_write_counter_offset) in reversion_group.calls {
@@ -1355,8 +1397,8 @@ impl<'a> CircuitInputStateRef<'a> {
             let length = length.expect("length not set");
             #[cfg(feature = "enable-stack")]
             {
-                assert_eq!(offset, step.stack.nth_last(0)?);
-                assert_eq!(length, step.stack.nth_last(1)?);
+                crate::util::check_geth_eq!(self, offset, step.stack.nth_last(0)?);
+                crate::util::check_geth_eq!(self, length, step.stack.nth_last(1)?);
             }
             let code = callee_memory.read_chunk(MemoryRange::new_with_length(
                 offset.low_u64(),
@@ -1392,15 +1434,18 @@ impl<'a> CircuitInputStateRef<'a> {
             .caller_ctx()
             .map(|c| c.return_data.len() as u64)
             .unwrap_or_default();
+        let is_return_data = matches!(step.op, OpcodeId::RETURN | OpcodeId::REVERT)
+            && step.error.is_none()
+            && !call_success_create;
+        #[cfg(feature = "enable-stack")]
+        if is_return_data && self.caller().is_ok() {
+            let offset = offset.expect("offset not set");
+            crate::util::check_geth_eq!(self, offset, step.stack.nth_last(0)?);
+        }
         // If current call has caller.
         if let Ok(caller) = self.caller_mut() {
-            let return_data_offset = if matches!(step.op, OpcodeId::RETURN | OpcodeId::REVERT)
-                && step.error.is_none()
-                && !call_success_create
-            {
+            let return_data_offset = if is_return_data {
                 let offset = offset.expect("offset not set");
-                #[cfg(feature = "enable-stack")]
-                assert_eq!(offset, step.stack.nth_last(0)?);
                 offset.low_u64()
             } else {
                 // common err, call empty, call precompile
@@ -1450,11 +1495,6 @@ impl<'a> CircuitInputStateRef<'a> {
                 OpcodeId::REVERT | OpcodeId::RETURN => {
                     let offset = offset.expect("offset not set");
                     let length = length.expect("length not set");
-                    #[cfg(feature = "enable-stack")]
-                    {
-                        assert_eq!(offset, geth_step.stack.nth_last(0)?);
-                        assert_eq!(length, geth_step.stack.nth_last(1)?);
-                    }
                     // This is the convention we are using for memory addresses so that there is no
                     // memory expansion cost when the length is 0.
                     // https://github.com/privacy-scaling-explorations/zkevm-circuits/pull/279/files#r787806678
@@ -1524,7 +1564,15 @@ impl<'a> CircuitInputStateRef<'a> {
             .get(1)
             .ok_or(Error::InternalError("invalid index 1"))?;
         #[cfg(feature = "enable-stack")]
-        assert_eq!(self.caller_ctx()?.stack, geth_step_next.stack);
+        {
+            crate::util::check_geth_eq!(self, self.caller_ctx()?.stack, geth_step_next.stack);
+            if let (false, OpcodeId::RETURN | OpcodeId::REVERT, Some(offset), Some(length)) =
+                (is_err, geth_step.op, offset, length)
+            {
+                crate::util::check_geth_eq!(self, offset, geth_step.stack.nth_last(0)?);
+                crate::util::check_geth_eq!(self, length, geth_step.stack.nth_last(1)?);
+            }
+        }
         self.call_context_read(
             exec_step,
             call.call_id,
@@ -1645,7 +1693,7 @@ impl<'a> CircuitInputStateRef<'a> {
     }
 
     pub(crate) fn get_step_err(
-        &self,
+        &mut self,
         step: &GethExecStep,
         next_step: Option<&GethExecStep>,
     ) -> Result<Option<ExecError>, Error> {
@@ -1657,7 +1705,7 @@ impl<'a> CircuitInputStateRef<'a> {
             return Ok(Some(get_step_reported_error(&step.op, error)));
         }
 
-        let call = self.call()?;
+        let call = self.call()?.clone();
         //trace!("get_step_err:
         // step:\n\tstep:{step:?}\n\tnext_step:{next_step:?}\n\tcall:{call:?}");
 
@@ -1688,9 +1736,9 @@ impl<'a> CircuitInputStateRef<'a> {
         //     .unwrap_or_else(Word::zero);
         let next_success = self.next_call_is_success().unwrap_or(true);
 
-        let call_ctx = self.call_ctx()?;
         #[cfg(feature = "enable-stack")]
-        assert_eq!(call_ctx.stack, step.stack);
+        crate::util::check_geth_eq!(self, self.call_ctx()?.stack, step.stack);
+        let call_ctx = self.call_ctx()?;
         // get value first if call/create
         let value = match step.op {
             OpcodeId::CALL | OpcodeId::CALLCODE => call_ctx.stack.nth_last(2)?,
@@ -1504,6 +1504,58 @@ fn tracer_err_gas_uint_overflow() {
     );
 }
 
+#[test]
+fn tracer_err_memory_offset_beyond_u32() {
+    for offset in [1u64 << 32, u64::MAX] {
+        for (code, opcode, error) in [
+            (
+                bytecode! {
+                    PUSH32(offset)
+                    MLOAD
+                },
+                OpcodeId::MLOAD,
+                OogError::StaticMemoryExpansion,
+            ),
+            (
+                bytecode! {
+                    PUSH1(0x42)
+                    PUSH32(offset)
+                    MSTORE
+                },
+                OpcodeId::MSTORE,
+                OogError::StaticMemoryExpansion,
+            ),
+            (
+                bytecode! {
+                    PUSH1(0x20) // size
+                    PUSH1(0) // offset
+                    PUSH32(offset) // dest_offset
+                    CALLDATACOPY
+                },
+                OpcodeId::CALLDATACOPY,
+                OogError::MemoryCopy,
+            ),
+        ] {
+            let block: GethData = TestContext::<2, 1>::simple_ctx_with_bytecode(code)
+                .unwrap()
+                .into();
+            let mut builder = crate::mock::BlockData::new_from_geth_data(block.clone())
+                .new_circuit_input_builder();
+            builder
+                .handle_block(&block.eth_block, &block.geth_traces)
+                .unwrap();
+
+            let step = builder.block.txs()[0]
+                .steps()
+                .iter()
+                .find(|step| step.exec_state == ExecState::Op(opcode))
+                .unwrap();
+            assert_eq!(step.error, Some(ExecError::OutOfGas(error)));
+            assert!(!builder.block.txs()[0].calls()[0].is_success);
+        }
+    }
+}
+
 #[test]
 fn tracer_err_invalid_opcode() {
     // The second opcode is invalid (0x0f)
@@ -1,15 +1,23 @@
 //! Transaction & TransactionContext utility module.
 
-use super::{call::ReversionGroup, Call, CallContext, CallKind, CodeSource, ExecStep};
-use crate::{l2_predeployed::l1_gas_price_oracle, Error};
+use super::{call::ReversionGroup, Call, CallContext, CallKind, CodeSource, ExecState, ExecStep};
+use crate::{
+    l2_predeployed::l1_gas_price_oracle,
+    operation::{AccountField, OperationContainer, Target},
+    Error,
+};
 use eth_types::{
-    evm_types::{gas_utils::tx_data_gas_cost, OpcodeId},
+    evm_types::{
+        gas_utils::{tx_access_list_gas_cost, tx_data_gas_cost},
+        GasCost, OpcodeId, INIT_CODE_WORD_GAS,
+    },
     geth_types,
     geth_types::{get_rlp_unsigned, TxType},
     state_db::{CodeDB, StateDB},
     AccessList, Address, GethExecTrace, Signature, Word, H256,
 };
 use ethers_core::utils::get_contract_address;
+use std::collections::{HashMap, HashSet};
 
 /// Precision of transaction L1 fee
 pub const TX_L1_FEE_PRECISION: u64 = 1_000_000_000;
@@ -174,6 +182,9 @@ impl TransactionContext {
 pub struct Transaction {
     /// ..
     pub block_num: u64,
+    /// Index in [`Block::txs`](super::Block::txs), i.e. in the chunk being
+    /// built
+    pub(crate) block_index: usize,
     /// Type
     pub tx_type: TxType,
     /// Nonce
@@ -210,6 +221,12 @@ pub struct Transaction {
     pub l1_fee_committed: TxL1Fee,
     /// EIP2930
     pub access_list: Option<AccessList>,
+    /// Return data of the top-level call, or its revert data if it reverted
+    pub return_value: Vec<u8>,
+    /// Addresses warm (EIP-2929) at the end of the transaction
+    pub(crate) warm_addresses: HashSet<Address>,
+    /// Storage slots warm (EIP-2929) at the end of the transaction, by address
+    pub(crate) warm_storage_keys: HashMap<Address, HashSet<Word>>,
     /// Calls made in the transaction
     pub(crate) calls: Vec<Call>,
     /// Execution steps
@@ -261,9 +278,13 @@ impl Transaction {
             },
             rlp_bytes: vec![],
             rlp_unsigned_bytes: vec![],
+            return_value: Vec::new(),
+            warm_addresses: HashSet::new(),
+            warm_storage_keys: HashMap::new(),
             calls: Vec::new(),
             steps: Vec::new(),
             block_num: Default::default(),
+            block_index: 0,
             hash: Default::default(),
             tx_type: Default::default(),
             l1_fee: Default::default(),
@@ -280,6 +301,16 @@ impl Transaction {
         eth_tx: &eth_types::Transaction,
         is_success: bool,
     ) -> Result<Self, Error> {
+        // Only legacy, EIP-2930, EIP-1559 and L1 message transactions are
+        // supported.  Other types would otherwise be silently treated as
+        // legacy ones, e.g. the authorization list of an EIP-7702 tx would
+        // be ignored, and so would the blob gas fee and blob hashes of an
+        // EIP-4844 tx.
+        if let Some(tx_type) = eth_tx.transaction_type {
+            if ![0, 1, 2, 0x7e].contains(&tx_type.as_u64()) {
+                return Err(Error::UnsupportedTxType(tx_type.as_u64()));
+            }
+        }
         let (found, _) = sdb.get_account(&eth_tx.from);
         if !found {
             return Err(Error::AccountNotFound(eth_tx.from));
@@ -358,6 +389,7 @@ impl Transaction {
 
         Ok(Self {
             block_num: eth_tx.block_number.unwrap().as_u64(),
+            block_index: 0,
             hash: eth_tx.hash,
             tx_type,
             rlp_bytes: eth_tx.rlp().to_vec(),
@@ -372,6 +404,9 @@ impl Transaction {
             value: eth_tx.value,
             input: eth_tx.input.to_vec(),
             chain_id: eth_tx.chain_id.unwrap_or_default().as_u64(), // FIXME
+            return_value: Vec::new(),
+            warm_addresses: HashSet::new(),
+            warm_storage_keys: HashMap::new(),
             calls: vec![call],
             steps: Vec::new(),
             signature: Signature {
@@ -385,11 +420,83 @@ impl Transaction {
         })
     }
 
+    /// Index of this [`Transaction`] in [`Block::txs`](super::Block::txs),
+    /// i.e. in the chunk being built, which is the order the PI circuit
+    /// commits to its data in.  It's set by
+    /// [`CircuitInputBuilder::new_tx`](super::CircuitInputBuilder::new_tx).
+    pub fn block_index(&self) -> usize {
+        self.block_index
+    }
+
+    /// Addresses and storage slots of each address warm (EIP-2929) at the
+    /// end of this [`Transaction`].
+    pub fn access_list_snapshot(&self) -> (&HashSet<Address>, &HashMap<Address, HashSet<Word>>) {
+        (&self.warm_addresses, &self.warm_storage_keys)
+    }
+
     /// Whether this [`Transaction`] is a create one
     pub fn is_create(&self) -> bool {
         self.calls[0].is_create()
     }
 
+    /// Intrinsic gas of this [`Transaction`], charged before any execution:
+    /// the base cost (creation or not), the call data cost (4 per zero byte and
+    /// 16 per non-zero byte), the EIP-2930 access list cost and the EIP-3860
+    /// init code cost for creation. A transaction whose gas limit is below
+    /// this is invalid: it can't be included in a block, the tracer rejects
+    /// it, and the builder fails with [`Error::IntrinsicGasExceedsGasLimit`].
+    pub fn intrinsic_gas(&self) -> u64 {
+        let base_gas_cost = if self.is_create() {
+            GasCost::CREATION_TX.as_u64()
+        } else {
+            GasCost::TX.as_u64()
+        };
+        let call_data_gas_cost = tx_data_gas_cost(&self.input);
+        let access_list_gas_cost = tx_access_list_gas_cost(&self.access_list);
+        let init_code_gas_cost = if self.is_create() {
+            (self.input.len() as u64 + 31) / 32 * INIT_CODE_WORD_GAS
+        } else {
+            0
+        };
+
+        base_gas_cost + call_data_gas_cost + access_list_gas_cost + init_code_gas_cost
+    }
+
+    /// Gas price actually paid by this [`Transaction`] in a block with
+    /// `base_fee`: the gas price for legacy and EIP-2930 transactions, and
+    /// min(gas_fee_cap, base_fee + gas_tip_cap) for EIP-1559 ones.  The sum
+    /// saturates, as the tip cap isn't bounded by the fee cap in the tx.
+    pub fn effective_gas_price(&self, base_fee: Word) -> Word {
+        match self.tx_type {
+            TxType::Eip1559 => self
+                .gas_fee_cap
+                .min(base_fee.saturating_add(self.gas_tip_cap)),
+            _ => self.gas_price,
+        }
+    }
+
+    /// Decode the reason string of a reverted transaction, if its revert data
+    /// is a standard ABI encoded `Error(string)`.
+    pub fn revert_reason(&self) -> Option<String> {
+        // bytes4(keccak256("Error(string)"))
+        const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+        if self.calls.first()?.is_success {
+            return None;
+        }
+        let data = self.return_value.strip_prefix(&ERROR_SELECTOR[..])?;
+        // Read a word at `at` which is used as an offset or length into `data`.
+        let read_usize = |at: usize| {
+            let word = Word::from_big_endian(data.get(at..at.checked_add(32)?)?);
+            (word <= Word::from(data.len())).then(|| word.as_usize())
+        };
+        let offset = read_usize(0)?;
+        let len = read_usize(offset)?;
+        let start = offset + 32;
+        let reason = data.get(start..start.checked_add(len)?)?;
+        String::from_utf8(reason.to_vec()).ok()
+    }
+
     /// Return the list of execution steps of this transaction.
     pub fn steps(&self) -> &[ExecStep] {
         &self.steps
@@ -430,6 +537,44 @@ impl Transaction {
         self.steps.is_empty()
     }
 
+    /// Count how many times each opcode was executed in this transaction,
+    /// including steps that ended in an error. Virtual steps and precompile
+    /// steps are not counted.
+    pub fn opcode_histogram(&self) -> HashMap<OpcodeId, usize> {
+        let mut histogram = HashMap::new();
+        for step in self.steps.iter() {
+            if let ExecState::Op(op) = step.exec_state {
+                *histogram.entry(op).or_insert(0) += 1;
+            }
+        }
+        histogram
+    }
+
+    /// Balance of each account touched by this transaction as `(before,
+    /// after)`, aggregated from the balance account ops in `container`,
+    /// including the fee payment, refund and coinbase reward.
+    pub fn balance_deltas(&self, container: &OperationContainer) -> HashMap<Address, (Word, Word)> {
+        let mut deltas = HashMap::new();
+        for op_ref in self
+            .steps
+            .iter()
+            .flat_map(|step| step.bus_mapping_instance.iter())
+        {
+            if op_ref.target() != Target::Account {
+                continue;
+            }
+            let op = container.account[op_ref.as_usize()].op();
+            if op.field != AccountField::Balance {
+                continue;
+            }
+            deltas
+                .entry(op.address)
+                .and_modify(|(_, after)| *after = op.value)
+                .or_insert((op.value_prev, op.value));
+        }
+        deltas
+    }
+
     /// Calculate L1 fee of this transaction.
     pub fn l1_fee(&self) -> u64 {
         let tx_data_gas_cost = tx_data_gas_cost(&self.rlp_bytes);
@@ -442,10 +587,7 @@ impl Transaction {
 impl Transaction {
     /// test if the transaction has different evm behaviour opcodes or precompiles
     pub fn has_l2_different_evm_behaviour_step(&self) -> bool {
-        use crate::{
-            circuit_input_builder::execution::ExecState, error::ExecError,
-            precompile::PrecompileCalls,
-        };
+        use crate::{error::ExecError, precompile::PrecompileCalls};
         let different_opcodes = self.steps.iter().any(|step| {
             matches!(
                 step.exec_state,
@@ -39,6 +39,21 @@ pub enum Error {
     ExecutionError(ExecError),
     /// Internal Code error
     InternalError(&'static str),
+    /// A transaction has more execution steps than the configured limit.
+    StepLimitExceeded(usize),
+    /// The gas used of the block header doesn't match the sum of the gas used
+    /// by its transactions: (header gas used, sum of transactions gas used).
+    BlockGasUsedMismatch(u64, u64),
+    /// The gas limit of a transaction is above the gas left in the block,
+    /// i.e. the block gas limit minus the gas used by the previous
+    /// transactions: (tx gas limit, block gas left).
+    TxGasLimitExceedsBlockGasLeft(Word, Word),
+    /// The transaction has an EIP-2718 type the builder doesn't know how to
+    /// trace, e.g. EIP-4844 blob or EIP-7702 set-code transactions.
+    UnsupportedTxType(u64),
+    /// The gas limit of a transaction is below its intrinsic gas: (tx gas
+    /// limit, intrinsic gas).
+    IntrinsicGasExceedsGasLimit(u64, u64),
 }
 
 impl From<eth_types::Error> for Error {
@@ -10,10 +10,10 @@ use crate::{
     Error,
 };
 use core::fmt::Debug;
-use eth_types::{evm_unimplemented, GethExecStep, ToAddress, ToWord, Word};
-
-#[cfg(any(feature = "enable-memory", feature = "enable-stack"))]
-use crate::util::GETH_TRACE_CHECK_LEVEL;
+use eth_types::{
+    evm_types::gas_utils::memory_expansion_gas_cost, evm_unimplemented, GethExecStep, ToAddress,
+    ToWord, Word,
+};
 
 #[cfg(any(feature = "test", test))]
 pub use self::sha3::sha3_tests::{gen_sha3_code, MemoryKind};
@@ -399,7 +399,7 @@ pub fn gen_associated_ops(
     geth_steps: &[GethExecStep],
 ) -> Result<Vec<ExecStep>, Error> {
     #[cfg(feature = "enable-memory")]
-    if GETH_TRACE_CHECK_LEVEL.should_check() {
+    if state.geth_trace_check_level.should_check() {
         let memory_enabled = !geth_steps.iter().all(|s| s.memory.is_empty());
         assert!(memory_enabled);
         if memory_enabled {
@@ -429,15 +429,16 @@ pub fn gen_associated_ops(
                         );
                     }
                 }
-                if GETH_TRACE_CHECK_LEVEL.should_panic() {
-                    panic!("mem wrong");
-                }
+                state.geth_mismatch(
+                    concat!(file!(), ":", line!()),
+                    format!("memory before {opcode_id:?}"),
+                );
                 state.call_ctx_mut()?.memory = geth_steps[0].memory.clone();
             }
         }
     }
     #[cfg(feature = "enable-stack")]
-    if GETH_TRACE_CHECK_LEVEL.should_check() {
+    if state.geth_trace_check_level.should_check() {
         if state.call_ctx()?.stack != geth_steps[0].stack {
             log::error!(
                 "wrong stack before {:?}. len in state {}, len in step {}",
@@ -461,9 +462,10 @@ pub fn gen_associated_ops(
                     );
                 }
             }
-            if GETH_TRACE_CHECK_LEVEL.should_panic() {
-                panic!("stack wrong");
-            }
+            state.geth_mismatch(
+                concat!(file!(), ":", line!()),
+                format!("stack before {opcode_id:?}"),
+            );
             state.call_ctx_mut()?.stack = geth_steps[0].stack.clone();
         } else {
             log::debug!("stack sanity check passed");
@@ -522,8 +524,23 @@ pub fn gen_associated_ops(
         }
     }
     // if no errors, continue as normal
+    let call_id = state.call()?.call_id;
+    let memory_word_size = state.call_ctx()?.memory_word_size();
     let fn_gen_associated_ops = fn_gen_associated_ops(opcode_id);
-    fn_gen_associated_ops(state, geth_steps)
+    let mut steps = fn_gen_associated_ops(state, geth_steps)?;
+    // Calls and creates compute their memory expansion themselves, the other
+    // opcodes expand the memory of the call they run in.
+    if !opcode_id.is_call_or_create() && matches!(state.call(), Ok(call) if call.call_id == call_id)
+    {
+        let next_memory_word_size = state.call_ctx()?.memory_word_size();
+        steps[0]
+            .gas_breakdown
+            .set_memory_expansion(memory_expansion_gas_cost(
+                memory_word_size,
+                next_memory_word_size,
+            ));
+    }
+    Ok(steps)
 }
 
 pub fn gen_associated_steps(
@@ -566,7 +583,7 @@ fn dummy_gen_selfdestruct_ops(
     let sender = state.call()?.address;
     let receiver = state.call_ctx_mut()?.stack.pop()?.to_address();
     #[cfg(feature = "enable-stack")]
-    assert_eq!(receiver, geth_step.stack.last()?.to_address());
+    crate::util::check_geth_eq!(state, receiver, geth_step.stack.last()?.to_address());
 
     let is_warm = state.sdb.check_account_in_access_list(&receiver);
     state.push_op_reversible(
@@ -597,35 +614,44 @@ fn dummy_gen_selfdestruct_ops(
         value
     );
     // NOTE: In this dummy implementation we assume that the receiver already
-    // exists.
-
-    state.push_op_reversible(
-        &mut exec_step,
-        AccountOp {
-            address: sender,
-            field: AccountField::Balance,
-            value: Word::zero(),
-            value_prev: value,
-        },
-    )?;
-    state.push_op_reversible(
-        &mut exec_step,
-        AccountOp {
-            address: sender,
-            field: AccountField::Nonce,
-            value: Word::zero(),
-            value_prev: sender_account.nonce,
-        },
-    )?;
-    state.push_op_reversible(
-        &mut exec_step,
-        AccountOp {
-            address: sender,
-            field: AccountField::CodeHash,
-            value: Word::zero(),
-            value_prev: sender_account.code_hash.to_word(),
-        },
-    )?;
+    // exists. An account created in this transaction is also cleared right
+    // away, while geth keeps its code until the end of the transaction, so
+    // reading it again later in the same transaction isn't supported.
+
+    // EIP-6780: the account is only deleted if it was created in the same
+    // transaction, otherwise SELFDESTRUCT just sends all its balance away.
+    let is_deleted = state.sdb.is_created(&sender);
+    if is_deleted || receiver != sender {
+        state.push_op_reversible(
+            &mut exec_step,
+            AccountOp {
+                address: sender,
+                field: AccountField::Balance,
+                value: Word::zero(),
+                value_prev: value,
+            },
+        )?;
+    }
+    if is_deleted {
+        state.push_op_reversible(
+            &mut exec_step,
+            AccountOp {
+                address: sender,
+                field: AccountField::Nonce,
+                value: Word::zero(),
+                value_prev: sender_account.nonce,
+            },
+        )?;
+        state.push_op_reversible(
+            &mut exec_step,
+            AccountOp {
+                address: sender,
+                field: AccountField::CodeHash,
+                value: Word::zero(),
+                value_prev: sender_account.code_hash.to_word(),
+            },
+        )?;
+    }
     if receiver != sender {
         state.transfer_to(
             &mut exec_step,
@@ -637,7 +663,7 @@ fn dummy_gen_selfdestruct_ops(
         )?;
     }
 
-    if state.call()?.is_persistent {
+    if is_deleted && state.call()?.is_persistent {
         state.sdb.destruct_account(sender);
     }
 
@@ -19,7 +19,7 @@ impl Opcode for Address {
 
         let address = state.call()?.address.to_word();
         #[cfg(feature = "enable-stack")]
-        assert_eq!(address, geth_steps[1].stack.last()?);
+        crate::util::check_geth_eq!(state, address, geth_steps[1].stack.last()?);
 
         // Read the callee address in call context.
         state.call_context_read(
@@ -343,13 +343,14 @@ where
 
         #[cfg(feature = "enable-stack")]
         for (i, input) in stack_inputs.iter().enumerate() {
-            assert_eq!(*input, geth_step.stack.nth_last(i)?);
+            crate::util::check_geth_eq!(state, *input, geth_step.stack.nth_last(i)?);
         }
         let output = Self::handle(stack_inputs);
         state.stack_push(&mut exec_step, output)?;
 
         #[cfg(feature = "enable-stack")]
-        assert_eq!(
+        crate::util::check_geth_eq!(
+            state,
             output,
             geth_steps[1].stack.nth_last(0)?,
             "stack mismatch, opcode: {}, inputs: {}, actual: {:x}, expected: {:x}",
@@ -456,6 +457,13 @@ mod tests {
         test_both::<2, { OpcodeId::SDIV }>([0x60u64.into(), 0x80u64.into()], 0u64.into());
     }
 
+    #[test]
+    fn test_iszero() {
+        test_both::<1, { OpcodeId::ISZERO }>([Word::zero()], Word::one());
+        test_both::<1, { OpcodeId::ISZERO }>([Word::one()], Word::zero());
+        test_both::<1, { OpcodeId::ISZERO }>([Word::one() << 255], Word::zero());
+    }
+
     #[test]
     fn test_mod() {
         test_both::<2, { OpcodeId::MOD }>([0x60u64.into(), 0x80u64.into()], 0x60u64.into());
@@ -466,6 +474,20 @@ mod tests {
         test_both::<2, { OpcodeId::SMOD }>([0x60u64.into(), 0x80u64.into()], 0x60u64.into());
     }
 
+    #[test]
+    fn test_smod_sign_follows_dividend() {
+        let neg = |x: u64| SignedWord(x.into()).neg().0;
+        // (-8) % 3 == -2
+        test_both::<2, { OpcodeId::SMOD }>([neg(8), 3u64.into()], neg(2));
+        // 8 % (-3) == 2
+        test_both::<2, { OpcodeId::SMOD }>([8u64.into(), neg(3)], 2u64.into());
+        // (-8) % (-3) == -2
+        test_both::<2, { OpcodeId::SMOD }>([neg(8), neg(3)], neg(2));
+        // x % 0 == 0
+        test_both::<2, { OpcodeId::SMOD }>([neg(8), Word::zero()], Word::zero());
+        test_both::<2, { OpcodeId::SMOD }>([8u64.into(), Word::zero()], Word::zero());
+    }
+
     #[test]
     fn test_addmod() {
         // testool: randomStatetest382_d0_g0_v0, randomStatetest242_d0_g0_v0
@@ -516,6 +538,26 @@ mod tests {
         test_both::<2, { OpcodeId::SGT }>([0x02u64.into(), 0x01u64.into()], 0x01u64.into());
     }
 
+    #[test]
+    fn test_signed_vs_unsigned_comparison() {
+        let minus_1 = Word::MAX;
+        let min_neg = Word::one() << 255;
+        test_both::<2, { OpcodeId::SLT }>([minus_1, Word::zero()], Word::one());
+        test_both::<2, { OpcodeId::SGT }>([minus_1, Word::zero()], Word::zero());
+        test_both::<2, { OpcodeId::LT }>([min_neg, Word::one()], Word::zero());
+        test_both::<2, { OpcodeId::SLT }>([min_neg, Word::one()], Word::one());
+    }
+
+    #[test]
+    fn test_byte() {
+        let value =
+            Word::from_big_endian(&[[0xaa, 0xbb].as_slice(), &[0x11; 29], &[0xcc]].concat());
+        test_both::<2, { OpcodeId::BYTE }>([0.into(), value], 0xaa.into());
+        test_both::<2, { OpcodeId::BYTE }>([1.into(), value], 0xbb.into());
+        test_both::<2, { OpcodeId::BYTE }>([31.into(), value], 0xcc.into());
+        test_both::<2, { OpcodeId::BYTE }>([32.into(), value], Word::zero());
+    }
+
     #[test]
     fn test_and() {
         test_both::<2, { OpcodeId::AND }>([0x01u64.into(), 0x02u64.into()], 0x00u64.into());
@@ -21,7 +21,7 @@ impl Opcode for Balance {
         let address_word = state.stack_pop(&mut exec_step)?;
         let address = address_word.to_address();
         #[cfg(feature = "enable-stack")]
-        assert_eq!(address_word, geth_step.stack.last()?);
+        crate::util::check_geth_eq!(state, address_word, geth_step.stack.last()?);
 
         // Read transaction ID, rw_counter_end_of_reversion, and is_persistent
         // from call context.
@@ -46,6 +46,7 @@ impl Opcode for Balance {
 
         // Update transaction access list for account address.
         let is_warm = state.sdb.check_account_in_access_list(&address);
+        exec_step.gas_breakdown.set_account_access(is_warm);
         state.push_op_reversible(
             &mut exec_step,
             TxAccessListAccountOp {
@@ -77,7 +78,7 @@ impl Opcode for Balance {
 
         // Write the BALANCE result to stack.
         #[cfg(feature = "enable-stack")]
-        assert_eq!(geth_steps[1].stack.last()?, balance);
+        crate::util::check_geth_eq!(state, geth_steps[1].stack.last()?, balance);
         state.stack_push(&mut exec_step, balance)?;
 
         Ok(vec![exec_step])
@@ -90,7 +91,7 @@ mod balance_tests {
     use crate::{
         circuit_input_builder::ExecState,
         mock::BlockData,
-        operation::{AccountOp, CallContextOp, StackOp, RW},
+        operation::{AccountOp, CallContextOp, OpEnum, StackOp, Target, RW},
     };
     use eth_types::{
         address, bytecode,
@@ -99,7 +100,7 @@ mod balance_tests {
         state_db::CodeDB,
         Bytecode, ToWord, Word, U256,
     };
-    use mock::TestContext;
+    use mock::{eth, TestContext, MOCK_ACCOUNTS};
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -119,6 +120,63 @@ mod balance_tests {
         test_ok(true, true, Some(vec![2, 3, 4]))
     }
 
+    #[test]
+    fn test_balance_gas_breakdown() {
+        use crate::circuit_input_builder::GasBreakdown;
+
+        let code = bytecode! {
+            // cold, then warm
+            PUSH20(MOCK_ACCOUNTS[2].to_word())
+            BALANCE
+            POP
+            PUSH20(MOCK_ACCOUNTS[2].to_word())
+            BALANCE
+            STOP
+        };
+        let block: GethData = TestContext::<3, 1>::new(
+            None,
+            |accs| {
+                accs[0].address(MOCK_ACCOUNTS[0]).code(code);
+                accs[1].address(MOCK_ACCOUNTS[1]).balance(eth(1));
+                accs[2].address(MOCK_ACCOUNTS[2]).balance(eth(1));
+            },
+            |mut txs, accs| {
+                txs[0].to(accs[0].address).from(accs[1].address);
+            },
+            |block, _tx| block,
+        )
+        .unwrap()
+        .into();
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let breakdowns = builder.block.txs()[0]
+            .steps()
+            .iter()
+            .filter(|step| step.exec_state == ExecState::Op(OpcodeId::BALANCE))
+            .map(|step| {
+                assert_eq!(step.gas_breakdown.total(), step.gas_cost.as_u64());
+                step.gas_breakdown
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            breakdowns,
+            vec![
+                GasBreakdown {
+                    base: 100,
+                    access_list: 2500,
+                    ..Default::default()
+                },
+                GasBreakdown {
+                    base: 100,
+                    ..Default::default()
+                },
+            ]
+        );
+    }
+
     // account_code = None should be the same as exists = false, so we can remove
     // it.
     fn test_ok(exists: bool, is_warm: bool, account_code: Option<Vec<u8>>) {
@@ -197,15 +255,29 @@ mod balance_tests {
 
         let container = builder.block.container;
 
-        let operation = &container.stack[indices[0].as_usize()];
-        assert_eq!(operation.rw(), RW::READ);
-        assert_eq!(
-            operation.op(),
-            &StackOp {
-                call_id,
-                address: StackAddress::from(1023u32),
-                value: address.to_word()
+        // The top of the stack holds the address pushed for each BALANCE, which
+        // BALANCE pops and overwrites with the balance. In the warm case the
+        // first balance is popped again by POP.
+        let mut stack_ops = vec![];
+        for i in 0..if is_warm { 2 } else { 1 } {
+            if i > 0 {
+                stack_ops.push((RW::READ, balance));
             }
+            stack_ops.extend([
+                (RW::WRITE, address.to_word()),
+                (RW::READ, address.to_word()),
+                (RW::WRITE, balance),
+            ]);
+        }
+        assert_eq!(
+            container.find_op(Target::Stack, call_id, 1023u64.into()),
+            stack_ops
+                .into_iter()
+                .map(|(rw, value)| (
+                    rw,
+                    OpEnum::Stack(StackOp::new(call_id, StackAddress(1023), value))
+                ))
+                .collect::<Vec<_>>()
         );
 
         let operation = &container.call_context[indices[1].as_usize()];
@@ -241,16 +313,25 @@ mod balance_tests {
             }
         );
 
-        let operation = &container.tx_access_list_account[indices[4].as_usize()];
-        assert_eq!(operation.rw(), RW::WRITE);
+        let is_warm_prevs = if is_warm {
+            vec![false, true]
+        } else {
+            vec![false]
+        };
         assert_eq!(
-            operation.op(),
-            &TxAccessListAccountOp {
-                tx_id,
-                address,
-                is_warm: true,
-                is_warm_prev: is_warm
-            }
+            container.find_op(Target::TxAccessListAccount, tx_id, address.to_word()),
+            is_warm_prevs
+                .iter()
+                .map(|&is_warm_prev| (
+                    RW::WRITE,
+                    OpEnum::TxAccessListAccount(TxAccessListAccountOp {
+                        tx_id,
+                        address,
+                        is_warm: true,
+                        is_warm_prev,
+                    })
+                ))
+                .collect::<Vec<_>>()
         );
 
         let code_hash = if let Some(code) = account_code {
@@ -260,40 +341,26 @@ mod balance_tests {
         } else {
             U256::zero()
         };
-        let operation = &container.account[indices[5].as_usize()];
-        assert_eq!(operation.rw(), RW::READ);
-        assert_eq!(
-            operation.op(),
-            &AccountOp {
-                address,
-                field: AccountField::CodeHash,
-                value: if exists { code_hash } else { U256::zero() },
-                value_prev: if exists { code_hash } else { U256::zero() },
-            }
-        );
+        let mut account_reads = vec![(AccountField::CodeHash, code_hash)];
         if exists {
-            let operation = &container.account[indices[6].as_usize()];
-            assert_eq!(operation.rw(), RW::READ);
-            assert_eq!(
-                operation.op(),
-                &AccountOp {
-                    address,
-                    field: AccountField::Balance,
-                    value: balance,
-                    value_prev: balance,
-                }
-            );
+            account_reads.push((AccountField::Balance, balance));
         }
-
-        let operation = &container.stack[indices[6 + if exists { 1 } else { 0 }].as_usize()];
-        assert_eq!(operation.rw(), RW::WRITE);
+        // Account operations aren't scoped to a tx, so `tx_id` is ignored here.
         assert_eq!(
-            operation.op(),
-            &StackOp {
-                call_id,
-                address: 1023u32.into(),
-                value: balance,
-            }
+            container.find_op(Target::Account, tx_id, address.to_word()),
+            is_warm_prevs
+                .iter()
+                .flat_map(|_| account_reads.iter())
+                .map(|&(field, value)| (
+                    RW::READ,
+                    OpEnum::Account(AccountOp {
+                        address,
+                        field,
+                        value,
+                        value_prev: value,
+                    })
+                ))
+                .collect::<Vec<_>>()
         );
     }
 }
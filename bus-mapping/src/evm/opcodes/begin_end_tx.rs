@@ -15,10 +15,7 @@ use crate::{
     Error,
 };
 use eth_types::{
-    evm_types::{
-        gas_utils::{tx_access_list_gas_cost, tx_data_gas_cost},
-        GasCost, MAX_REFUND_QUOTIENT_OF_GAS_USED,
-    },
+    evm_types::{GasCost, MAX_REFUND_QUOTIENT_OF_GAS_USED},
     state_db::CodeDB,
     utils::is_precompiled,
     Bytecode, ToWord, Word,
@@ -182,24 +179,12 @@ pub fn gen_begin_tx_steps(state: &mut CircuitInputStateRef) -> Result<Vec<ExecSt
         )?;
     }
 
-    // Calculate gas cost of init code only for EIP-3860 of Shanghai.
-    let init_code_gas_cost = if state.tx.is_create() {
-        (state.tx.input.len() as u64 + 31) / 32 * eth_types::evm_types::INIT_CODE_WORD_GAS
-    } else {
-        0
-    };
-
     // Calculate intrinsic gas cost
-    let call_data_gas_cost = tx_data_gas_cost(&state.tx.input);
-    let access_list_gas_cost = tx_access_list_gas_cost(&state.tx.access_list);
-    let intrinsic_gas_cost = if state.tx.is_create() {
-        GasCost::CREATION_TX.as_u64()
-    } else {
-        GasCost::TX.as_u64()
-    } + call_data_gas_cost
-        + access_list_gas_cost
-        + init_code_gas_cost;
-    log::trace!("intrinsic_gas_cost {intrinsic_gas_cost}, call_data_gas_cost {call_data_gas_cost}, access_list_gas_cost {access_list_gas_cost}, init_code_gas_cost {init_code_gas_cost}, &mut exec_step.gas_cost {:?}", &mut exec_step.gas_cost);
+    let intrinsic_gas_cost = state.tx.intrinsic_gas();
+    log::trace!(
+        "intrinsic_gas_cost {intrinsic_gas_cost}, &mut exec_step.gas_cost {:?}",
+        &mut exec_step.gas_cost
+    );
     exec_step.gas_cost = GasCost(intrinsic_gas_cost);
 
     // Get code_hash of callee account
@@ -282,6 +267,7 @@ pub fn gen_begin_tx_steps(state: &mut CircuitInputStateRef) -> Result<Vec<ExecSt
     // to the Keccak circuit, so that the BeginTxGadget can do a lookup to the
     // Keccak table and verify the contract address.
     if state.tx.is_create() {
+        state.sdb.set_created(&call.address);
         // 1. add RLP-bytes for contract address to keccak circuit.
         state.block.sha3_inputs.push({
             let mut stream = ethers_core::utils::rlp::RlpStream::new();
@@ -540,6 +526,13 @@ pub fn gen_begin_tx_steps(state: &mut CircuitInputStateRef) -> Result<Vec<ExecSt
     Ok(std::iter::once(exec_step).chain(precompile_step).collect())
 }
 
+/// Generate the EndTx step: refund the caller and reward the coinbase.
+///
+/// The fee recipient is always the block's coinbase, since that is what the
+/// traced state and the EndTx gadget agree on. An L2 that collects fees in a
+/// vault sets the vault as coinbase in its block trace, which overrides the
+/// header's coinbase when the trace is applied, rather than plugging in a
+/// different fee distribution here.
 pub fn gen_end_tx_steps(state: &mut CircuitInputStateRef) -> Result<ExecStep, Error> {
     let mut exec_step = state.new_end_tx_step();
     let call = state.tx.calls()[0].clone();
@@ -22,7 +22,7 @@ impl Opcode for Blockhash {
 
         let block_number = state.stack_pop(&mut exec_step)?;
         #[cfg(feature = "enable-stack")]
-        assert_eq!(block_number, geth_step.stack.last()?);
+        crate::util::check_geth_eq!(state, block_number, geth_step.stack.last()?);
 
         let current_block_number = state.tx.block_num;
         let block_hash = if is_valid_block_number(block_number, current_block_number.into()) {
@@ -41,7 +41,7 @@ impl Opcode for Blockhash {
             0.into()
         };
         #[cfg(feature = "enable-stack")]
-        assert_eq!(block_hash, geth_steps[1].stack.last()?);
+        crate::util::check_geth_eq!(state, block_hash, geth_steps[1].stack.last()?);
         state.stack_push(&mut exec_step, block_hash)?;
 
         Ok(vec![exec_step])
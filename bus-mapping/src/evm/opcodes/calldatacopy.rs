@@ -24,9 +24,9 @@ impl Opcode for Calldatacopy {
         let length = state.stack_pop(&mut exec_step)?;
         #[cfg(feature = "enable-stack")]
         {
-            assert_eq!(memory_offset, geth_step.stack.nth_last(0)?);
-            assert_eq!(data_offset, geth_step.stack.nth_last(1)?);
-            assert_eq!(length, geth_step.stack.nth_last(2)?);
+            crate::util::check_geth_eq!(state, memory_offset, geth_step.stack.nth_last(0)?);
+            crate::util::check_geth_eq!(state, data_offset, geth_step.stack.nth_last(1)?);
+            crate::util::check_geth_eq!(state, length, geth_step.stack.nth_last(2)?);
         }
         if state.call()?.is_root {
             state.call_context_read(
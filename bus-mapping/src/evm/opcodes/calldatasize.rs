@@ -26,7 +26,7 @@ impl Opcode for Calldatasize {
             call_data_length,
         )?;
         #[cfg(feature = "enable-stack")]
-        assert_eq!(call_data_length, geth_steps[1].stack.last()?);
+        crate::util::check_geth_eq!(state, call_data_length, geth_steps[1].stack.last()?);
         state.stack_push(&mut exec_step, call_data_length)?;
         Ok(vec![exec_step])
     }
@@ -43,9 +43,14 @@ mod calldatasize_tests {
         bytecode,
         evm_types::{OpcodeId, StackAddress},
         geth_types::GethData,
+        Word,
     };
 
-    use mock::test_ctx::{helpers::*, TestContext};
+    use mock::{
+        eth,
+        test_ctx::{helpers::*, TestContext},
+        MOCK_ACCOUNTS,
+    };
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -105,4 +110,51 @@ mod calldatasize_tests {
             )
         );
     }
+
+    #[test]
+    fn calldatasize_in_creation_tx_is_zero() {
+        // The tx data is the init code, so the creation frame has no calldata.
+        let init_code = bytecode! {
+            CALLDATASIZE
+            STOP
+        };
+
+        let block: GethData = TestContext::<1, 1>::new(
+            None,
+            |accs| {
+                accs[0].address(MOCK_ACCOUNTS[0]).balance(eth(1));
+            },
+            |mut txs, accs| {
+                txs[0].from(accs[0].address).input(init_code.code().into());
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let tx = &builder.block.txs()[0];
+        assert!(tx.is_create());
+        let step = tx
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::CALLDATASIZE))
+            .unwrap();
+        let call_context_op =
+            &builder.block.container.call_context[step.bus_mapping_instance[0].as_usize()];
+        assert_eq!(call_context_op.op().field, CallContextField::CallDataLength);
+        assert_eq!(call_context_op.op().value, Word::zero());
+        let stack_op = &builder.block.container.stack[step.bus_mapping_instance[1].as_usize()];
+        assert_eq!(
+            (stack_op.rw(), stack_op.op()),
+            (
+                RW::WRITE,
+                &StackOp::new(1, StackAddress::from(1023), Word::zero())
+            )
+        );
+    }
 }
@@ -30,7 +30,7 @@ impl Opcode for Caller {
 
         // Stack write of the caller_address
         #[cfg(feature = "enable-stack")]
-        assert_eq!(caller_address, geth_steps[1].stack.last()?);
+        crate::util::check_geth_eq!(state, caller_address, geth_steps[1].stack.last()?);
         state.stack_push(&mut exec_step, caller_address)?;
 
         Ok(vec![exec_step])
@@ -112,4 +112,59 @@ mod caller_tests {
             )
         );
     }
+
+    #[test]
+    fn caller_opcode_in_delegatecall() {
+        use mock::MOCK_ACCOUNTS;
+
+        let callee_code = bytecode! {
+            CALLER
+            STOP
+        };
+        let code = bytecode! {
+            PUSH1(0) // retLength
+            PUSH1(0) // retOffset
+            PUSH1(0) // argsLength
+            PUSH1(0) // argsOffset
+            PUSH20(MOCK_ACCOUNTS[1].to_word())
+            GAS
+            DELEGATECALL
+            STOP
+        };
+
+        let block: GethData = TestContext::<3, 1>::new(
+            None,
+            |accs| {
+                accs[0].address(MOCK_ACCOUNTS[0]).code(code);
+                accs[1].address(MOCK_ACCOUNTS[1]).code(callee_code);
+                accs[2].address(MOCK_ACCOUNTS[2]).balance(mock::eth(10));
+            },
+            |mut txs, accs| {
+                txs[0].from(accs[2].address).to(accs[0].address);
+            },
+            |block, _tx| block,
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let tx = &builder.block.txs()[0];
+        let step = tx
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::CALLER))
+            .unwrap();
+        // CALLER runs in the delegated call, which keeps the caller of the
+        // delegating contract.
+        assert_eq!(step.call_index, 1);
+        assert_eq!(tx.calls()[1].address, MOCK_ACCOUNTS[0]);
+
+        let operation = &builder.block.container.stack[step.bus_mapping_instance[1].as_usize()];
+        assert_eq!(operation.rw(), RW::WRITE);
+        assert_eq!(operation.op().value, MOCK_ACCOUNTS[2].to_word());
+    }
 }
@@ -28,6 +28,15 @@ use std::cmp::min;
 /// `OpcodeId::DELEGATECALL` and `OpcodeId::STATICCALL`.
 /// - CALL and CALLCODE: N_ARGS = 7
 /// - DELEGATECALL and STATICCALL: N_ARGS = 6
+///
+/// The gas of a call to an account with code always follows the standard
+/// rules: the gas cost and the gas forwarded to the callee come from the
+/// trace, and the CallOp gadget constrains them with the EIP-150/2929
+/// formulas. There is no per-address gas override for predeployed contracts
+/// with custom gas: such a cost would have to be charged by the tracer and
+/// constrained by the gadget, so overriding it here alone would just make the
+/// steps disagree with the trace. Only the precompiles at 0x01..=0x09 have
+/// their own gas, see `execute_precompiled`.
 #[derive(Debug, Copy, Clone)]
 pub(crate) struct CallOpcode<const N_ARGS: usize>;
 
@@ -133,7 +142,7 @@ impl<const N_ARGS: usize> Opcode for CallOpcode<N_ARGS> {
         let stack_inputs: Vec<Word> = state.stack_pops(&mut exec_step, N_ARGS)?;
         #[cfg(feature = "enable-stack")]
         for (i, input) in stack_inputs.iter().enumerate() {
-            assert_eq!(*input, geth_step.stack.nth_last(i)?);
+            crate::util::check_geth_eq!(state, *input, geth_step.stack.nth_last(i)?);
         }
         state.stack_push(&mut exec_step, (callee_call.is_success as u64).into())?;
 
@@ -156,6 +165,7 @@ impl<const N_ARGS: usize> Opcode for CallOpcode<N_ARGS> {
         )?;
 
         let is_warm = state.sdb.check_account_in_access_list(&callee_address);
+        exec_step.gas_breakdown.set_account_access(is_warm);
         state.push_op_reversible(
             &mut exec_step,
             TxAccessListAccountOp {
@@ -232,6 +242,7 @@ impl<const N_ARGS: usize> Opcode for CallOpcode<N_ARGS> {
             GasCost::COLD_ACCOUNT_ACCESS.as_u64()
         } + if has_value {
             GasCost::CALL_WITH_VALUE.as_u64()
+                // Since EIP-161 only a value transfer creates an empty account.
                 + if callee_call.kind == CallKind::Call && !callee_exists {
                     GasCost::NEW_ACCOUNT.as_u64()
                 } else {
@@ -240,6 +251,9 @@ impl<const N_ARGS: usize> Opcode for CallOpcode<N_ARGS> {
         } else {
             0
         } + memory_expansion_gas_cost;
+        exec_step
+            .gas_breakdown
+            .set_memory_expansion(memory_expansion_gas_cost);
         let gas_specified = stack_inputs[0];
         debug_assert!(
             geth_step.gas.0 >= gas_cost,
@@ -348,7 +362,8 @@ impl<const N_ARGS: usize> Opcode for CallOpcode<N_ARGS> {
                 }
 
                 #[cfg(feature = "enable-stack")]
-                assert_eq!(
+                crate::util::check_geth_eq!(
+                    state,
                     state.caller_ctx()?.stack.stack_pointer().0,
                     geth_step.stack.stack_pointer().0 + N_ARGS - 1
                 );
@@ -564,7 +579,8 @@ impl<const N_ARGS: usize> Opcode for CallOpcode<N_ARGS> {
             // 3. Call to account with non-empty code.
             (false, _, false) => {
                 #[cfg(feature = "enable-stack")]
-                assert_eq!(
+                crate::util::check_geth_eq!(
+                    state,
                     state.caller_ctx()?.stack.stack_pointer().0,
                     geth_step.stack.stack_pointer().0 + N_ARGS - 1
                 );
@@ -1076,4 +1092,566 @@ pub mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_call_value_stipend_sstore_oog() {
+        use crate::{
+            circuit_input_builder::ExecState,
+            error::{ExecError, OogError},
+            mock::BlockData,
+        };
+        use eth_types::{
+            bytecode,
+            evm_types::{OpcodeId, GAS_STIPEND_CALL_WITH_VALUE},
+            geth_types::GethData,
+            ToWord, Word,
+        };
+        use mock::{TestContext, MOCK_ACCOUNTS};
+
+        // The callee only gets the stipend, which is not enough for SSTORE.
+        let callee_code = bytecode! {
+            PUSH1(0x01)
+            PUSH1(0x00)
+            SSTORE
+            STOP
+        };
+        let caller_code = bytecode! {
+            PUSH1(0x00) // retLength
+            PUSH1(0x00) // retOffset
+            PUSH1(0x00) // argsLength
+            PUSH1(0x00) // argsOffset
+            PUSH1(0x01) // value
+            PUSH20(MOCK_ACCOUNTS[1].to_word())
+            PUSH1(0x00) // gas
+            CALL
+            POP
+            PUSH1(0x2a)
+            PUSH1(0x00)
+            SSTORE
+            STOP
+        };
+
+        let block: GethData = TestContext::<3, 1>::new(
+            None,
+            |accs| {
+                accs[0]
+                    .address(MOCK_ACCOUNTS[0])
+                    .code(caller_code)
+                    .balance(Word::from(1u64 << 20));
+                accs[1].address(MOCK_ACCOUNTS[1]).code(callee_code);
+                accs[2]
+                    .address(MOCK_ACCOUNTS[2])
+                    .balance(Word::from(1u64 << 30));
+            },
+            |mut txs, accs| {
+                txs[0].to(accs[0].address).from(accs[2].address);
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let tx = &builder.block.txs()[0];
+        let sstore_steps = tx
+            .steps()
+            .iter()
+            .filter(|step| step.exec_state == ExecState::Op(OpcodeId::SSTORE))
+            .collect::<Vec<_>>();
+        assert_eq!(sstore_steps.len(), 2);
+
+        // The callee starts with exactly the stipend and runs out of gas.
+        let (callee_sstore, caller_sstore) = (sstore_steps[0], sstore_steps[1]);
+        let callee_first_step = tx
+            .steps()
+            .iter()
+            .find(|step| step.call_index == callee_sstore.call_index)
+            .unwrap();
+        assert_eq!(callee_first_step.gas_left.0, GAS_STIPEND_CALL_WITH_VALUE);
+        assert_eq!(
+            callee_sstore.error,
+            Some(ExecError::OutOfGas(OogError::SloadSstore))
+        );
+        assert!(!tx.calls()[callee_sstore.call_index].is_success);
+
+        // The caller carries on after the failed call.
+        assert_eq!(caller_sstore.error, None);
+        assert!(tx.calls()[caller_sstore.call_index].is_success);
+        assert_eq!(
+            builder.sdb.get_storage(&MOCK_ACCOUNTS[0], &Word::zero()).1,
+            &Word::from(0x2a)
+        );
+    }
+
+    #[test]
+    fn test_call_self_with_value_reentrant_sstore() {
+        use crate::{
+            circuit_input_builder::ExecState,
+            mock::BlockData,
+            operation::{AccountField, Target, RW},
+        };
+        use eth_types::{bytecode, evm_types::OpcodeId, geth_types::GethData, Word};
+        use mock::{
+            test_ctx::helpers::{account_0_code_account_1_no_code, tx_from_1_to_0},
+            TestContext, MOCK_ACCOUNTS,
+        };
+
+        // Without calldata, call itself with value and some calldata, then
+        // read the slot written by the reentrant frame.
+        let code = bytecode! {
+            CALLDATASIZE
+            PUSH1(0x16) // reentrant frame
+            JUMPI
+            PUSH1(0x00) // retLength
+            PUSH1(0x00) // retOffset
+            PUSH1(0x01) // argsLength
+            PUSH1(0x00) // argsOffset
+            PUSH1(0x01) // value
+            ADDRESS
+            GAS
+            CALL
+            POP
+            PUSH1(0x00)
+            SLOAD
+            STOP
+            JUMPDEST
+            PUSH1(0x2a)
+            PUSH1(0x00)
+            SSTORE
+            STOP
+        };
+
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            account_0_code_account_1_no_code(code),
+            tx_from_1_to_0,
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+        let balance_prev = block.accounts[0].balance;
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let tx = &builder.block.txs()[0];
+        let inner_call = &tx.calls()[1];
+        assert_eq!(inner_call.address, MOCK_ACCOUNTS[0]);
+        assert_eq!(inner_call.caller_address, MOCK_ACCOUNTS[0]);
+        assert_eq!(inner_call.value, Word::one());
+        assert!(inner_call.is_success);
+
+        // The self transfer leaves the balance as it was.
+        let call_step = tx
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::CALL))
+            .unwrap();
+        let balance_writes = call_step
+            .bus_mapping_instance
+            .iter()
+            .filter(|op_ref| op_ref.target() == Target::Account)
+            .map(|op_ref| &builder.block.container.account[op_ref.as_usize()])
+            .filter(|op| op.rw() == RW::WRITE && op.op().field == AccountField::Balance)
+            .map(|op| op.op().value)
+            .collect::<Vec<_>>();
+        assert!(!balance_writes.is_empty());
+        assert_eq!(*balance_writes.last().unwrap(), balance_prev);
+        assert_eq!(builder.sdb.get_balance(&MOCK_ACCOUNTS[0]), balance_prev);
+
+        // The outer frame reads the value written by the reentrant one.
+        let sload_step = tx
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::SLOAD))
+            .unwrap();
+        assert_eq!(sload_step.call_index, 0);
+        let storage_op =
+            &builder.block.container.storage[sload_step.bus_mapping_instance[5].as_usize()];
+        assert_eq!(storage_op.rw(), RW::READ);
+        assert_eq!(storage_op.op().address, MOCK_ACCOUNTS[0]);
+        assert_eq!(storage_op.op().value, Word::from(0x2a));
+        assert_eq!(storage_op.op().committed_value, Word::zero());
+        assert_eq!(
+            builder.sdb.get_storage(&MOCK_ACCOUNTS[0], &Word::zero()).1,
+            &Word::from(0x2a)
+        );
+    }
+
+    #[test]
+    fn test_ecrecover_gas() {
+        use crate::{
+            circuit_input_builder::ExecState,
+            error::{ExecError, OogError},
+            mock::BlockData,
+            precompile::PrecompileCalls,
+        };
+        use eth_types::{bytecode, geth_types::GethData, word, ToWord};
+        use mock::{test_ctx::helpers::*, TestContext};
+
+        // Returns the gas left after the call and the return data size.
+        let call_ecrecover = |gas: u64| {
+            let code = PrecompileCallArgs {
+                name: "ecrecover",
+                setup_code: bytecode! {
+                    PUSH32(word!("0x456e9aea5e197a1f1af7a3e85a3212fa4049a3ba34c2289b4c860fc0b0c64ef3")) // hash
+                    PUSH1(0x0)
+                    MSTORE
+                    PUSH1(28) // v
+                    PUSH1(0x20)
+                    MSTORE
+                    PUSH32(word!("0x9242685bf161793cc25603c231bc2f568eb630ea16aa137d2664ac8038825608")) // r
+                    PUSH1(0x40)
+                    MSTORE
+                    PUSH32(word!("0x4f8ae3bd7535248d0bd448298cc2e2071e56992d0774dc340c368ae950852ada")) // s
+                    PUSH1(0x60)
+                    MSTORE
+                },
+                ret_size: Word::from(0x20),
+                ret_offset: Word::from(0x80),
+                call_data_length: Word::from(0x80),
+                address: PrecompileCalls::Ecrecover.address().to_word(),
+                gas: Word::from(gas),
+                ..Default::default()
+            }
+            .with_call_op(OpcodeId::STATICCALL);
+
+            let block: GethData = TestContext::<2, 1>::new(
+                None,
+                account_0_code_account_1_no_code(code),
+                tx_from_1_to_0,
+                |block, _tx| block,
+            )
+            .unwrap()
+            .into();
+            let mut builder =
+                BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+            builder
+                .handle_block(&block.eth_block, &block.geth_traces)
+                .unwrap();
+
+            let tx = &builder.block.txs()[0];
+            let precompile_step = tx.steps().iter().find(|step| step.call_index == 1).unwrap();
+            if tx.calls()[1].is_success {
+                assert_eq!(
+                    precompile_step.exec_state,
+                    ExecState::Precompile(PrecompileCalls::Ecrecover)
+                );
+                assert_eq!(precompile_step.gas_cost.as_u64(), 3000);
+            } else {
+                assert_eq!(
+                    precompile_step.error,
+                    Some(ExecError::OutOfGas(OogError::Precompile))
+                );
+            }
+
+            let gas_left = tx
+                .steps()
+                .iter()
+                .find(|step| step.exec_state == ExecState::Op(OpcodeId::POP))
+                .unwrap()
+                .gas_left
+                .0;
+            let returndatasize = tx
+                .steps()
+                .iter()
+                .find(|step| step.exec_state == ExecState::Op(OpcodeId::RETURNDATASIZE))
+                .unwrap();
+            let returndatasize = builder.block.container.stack
+                [returndatasize.bus_mapping_instance[0].as_usize()]
+            .op()
+            .value;
+            (tx.calls()[1].is_success, gas_left, returndatasize)
+        };
+
+        let (success, gas_left, returndatasize) = call_ecrecover(3000);
+        assert!(success);
+        assert_eq!(returndatasize, Word::from(0x20));
+
+        // All the forwarded gas is consumed, and nothing is returned.
+        let (oog_success, oog_gas_left, oog_returndatasize) = call_ecrecover(2999);
+        assert!(!oog_success);
+        assert_eq!(oog_returndatasize, Word::zero());
+        assert_eq!(oog_gas_left, gas_left + 1);
+
+        // The unused gas goes back to the caller.
+        let (extra_success, extra_gas_left, extra_returndatasize) = call_ecrecover(3500);
+        assert!(extra_success);
+        assert_eq!(extra_returndatasize, Word::from(0x20));
+        assert_eq!(extra_gas_left, gas_left);
+    }
+
+    #[test]
+    fn test_call_new_account_gas() {
+        use crate::{circuit_input_builder::ExecState, mock::BlockData};
+        use eth_types::{address, bytecode, geth_types::GethData, Address, ToWord};
+        use mock::{eth, TestContext, MOCK_ACCOUNTS};
+
+        let existing = MOCK_ACCOUNTS[2];
+        let nonexistent = address!("0x000000000000000000000000000000000000dead");
+        // Returns the gas spent by the caller on a CALL with 0 gas, which
+        // forwards only the stipend and gets it back unused.
+        let call_gas = |to: Address, value: u64| {
+            let code = bytecode! {
+                PUSH1(0) // retSize
+                PUSH1(0) // retOffset
+                PUSH1(0) // argsSize
+                PUSH1(0) // argsOffset
+                PUSH1(value)
+                PUSH20(to.to_word())
+                PUSH1(0) // gas
+                CALL
+                POP
+                STOP
+            };
+            let block: GethData = TestContext::<3, 1>::new(
+                None,
+                |accs| {
+                    accs[0].address(MOCK_ACCOUNTS[0]).balance(eth(1)).code(code);
+                    accs[1].address(MOCK_ACCOUNTS[1]).balance(eth(10));
+                    accs[2].address(existing).balance(eth(1));
+                },
+                |mut txs, accs| {
+                    txs[0].from(accs[1].address).to(accs[0].address);
+                },
+                |block, _tx| block,
+            )
+            .unwrap()
+            .into();
+            let mut builder =
+                BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+            builder
+                .handle_block(&block.eth_block, &block.geth_traces)
+                .unwrap();
+
+            let steps = builder.block.txs()[0].steps();
+            let gas_left_at = |op| {
+                steps
+                    .iter()
+                    .find(|step| step.exec_state == ExecState::Op(op))
+                    .unwrap()
+                    .gas_left
+                    .0
+            };
+            gas_left_at(OpcodeId::CALL) - gas_left_at(OpcodeId::POP)
+        };
+
+        // cold access + value transfer + new account - returned stipend
+        assert_eq!(call_gas(nonexistent, 1), 2600 + 9000 + 25000 - 2300);
+        // The account exists, so no new account gas.
+        assert_eq!(call_gas(existing, 1), 2600 + 9000 - 2300);
+        // Without value no account is created (EIP-161).
+        assert_eq!(call_gas(nonexistent, 0), 2600);
+    }
+
+    #[test]
+    fn test_call_depth_of_steps() {
+        use crate::{circuit_input_builder::ExecState, mock::BlockData};
+        use eth_types::{bytecode, geth_types::GethData, ToWord};
+        use mock::{eth, TestContext, MOCK_ACCOUNTS};
+
+        let callee_code = bytecode! {
+            PUSH1(1)
+            PUSH1(2)
+            ADD
+            STOP
+        };
+        let caller_code = bytecode! {
+            PUSH1(0) // retSize
+            PUSH1(0) // retOffset
+            PUSH1(0) // argsSize
+            PUSH1(0) // argsOffset
+            PUSH1(0) // value
+            PUSH20(MOCK_ACCOUNTS[2].to_word())
+            PUSH2(0xffff) // gas
+            CALL
+            POP
+            STOP
+        };
+        let block: GethData = TestContext::<3, 1>::new(
+            None,
+            |accs| {
+                accs[0]
+                    .address(MOCK_ACCOUNTS[0])
+                    .balance(eth(1))
+                    .code(caller_code);
+                accs[1].address(MOCK_ACCOUNTS[1]).balance(eth(10));
+                accs[2].address(MOCK_ACCOUNTS[2]).code(callee_code);
+            },
+            |mut txs, accs| {
+                txs[0].from(accs[1].address).to(accs[0].address);
+            },
+            |block, _tx| block,
+        )
+        .unwrap()
+        .into();
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let tx = &builder.block.txs()[0];
+        let depth_of = |op| {
+            tx.steps()
+                .iter()
+                .find(|step| step.exec_state == ExecState::Op(op))
+                .unwrap()
+                .call_depth()
+        };
+        assert_eq!(depth_of(OpcodeId::CALL), 0);
+        assert_eq!(depth_of(OpcodeId::ADD), 1);
+        assert_eq!(depth_of(OpcodeId::POP), 0);
+        assert_eq!(tx.steps().first().unwrap().call_depth(), 0);
+        assert_eq!(tx.last_step().call_depth(), 0);
+        for step in tx.steps() {
+            let call = &tx.calls()[step.call_index];
+            assert_eq!(step.call_depth(), call.depth - 1);
+        }
+    }
+
+    #[test]
+    fn test_ecrecover_output_is_left_padded_address() {
+        use crate::{
+            circuit_input_builder::ExecState, mock::BlockData, precompile::PrecompileCalls,
+        };
+        use eth_types::{address, bytecode, geth_types::GethData, word, ToBigEndian, ToWord};
+        use mock::{test_ctx::helpers::*, TestContext};
+
+        let code = PrecompileCallArgs {
+            name: "ecrecover",
+            setup_code: bytecode! {
+                PUSH32(word!("0x456e9aea5e197a1f1af7a3e85a3212fa4049a3ba34c2289b4c860fc0b0c64ef3")) // hash
+                PUSH1(0x0)
+                MSTORE
+                PUSH1(28) // v
+                PUSH1(0x20)
+                MSTORE
+                PUSH32(word!("0x9242685bf161793cc25603c231bc2f568eb630ea16aa137d2664ac8038825608")) // r
+                PUSH1(0x40)
+                MSTORE
+                PUSH32(word!("0x4f8ae3bd7535248d0bd448298cc2e2071e56992d0774dc340c368ae950852ada")) // s
+                PUSH1(0x60)
+                MSTORE
+                // dirty the output word, so the zero high bytes must be written
+                PUSH32(Word::MAX)
+                PUSH1(0x80)
+                MSTORE
+            },
+            ret_size: Word::from(0x20),
+            ret_offset: Word::from(0x80),
+            call_data_length: Word::from(0x80),
+            address: PrecompileCalls::Ecrecover.address().to_word(),
+            stack_value: vec![(Word::from(0x80), Word::zero())],
+            ..Default::default()
+        }
+        .with_call_op(OpcodeId::STATICCALL);
+
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            account_0_code_account_1_no_code(code),
+            tx_from_1_to_0,
+            |block, _tx| block,
+        )
+        .unwrap()
+        .into();
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let mload = builder.block.txs()[0]
+            .steps()
+            .iter()
+            .filter(|step| step.exec_state == ExecState::Op(OpcodeId::MLOAD))
+            .last()
+            .unwrap();
+        let output = builder.block.container.stack[mload.bus_mapping_instance[1].as_usize()]
+            .op()
+            .value;
+        let output = output.to_be_bytes();
+        assert_eq!(output[..12], [0u8; 12]);
+        assert_eq!(
+            output[12..],
+            address!("0x7156526fbd7a3c72969b54f64e42c10fbb768c8a").0
+        );
+    }
+
+    #[test]
+    fn test_ecrecover_accepts_high_s() {
+        use crate::{
+            circuit_input_builder::ExecState, mock::BlockData, precompile::PrecompileCalls,
+        };
+        use eth_types::{address, bytecode, geth_types::GethData, word, ToWord};
+        use mock::{test_ctx::helpers::*, TestContext};
+
+        // The s range check of EIP-2 only applies to transaction signatures,
+        // ecrecover accepts a high s and recovers the same address as the
+        // low-s signature with the flipped v.
+        let recover = |v: u64, s: Word| {
+            let code = PrecompileCallArgs {
+                name: "ecrecover",
+                setup_code: bytecode! {
+                    PUSH32(word!("0x456e9aea5e197a1f1af7a3e85a3212fa4049a3ba34c2289b4c860fc0b0c64ef3")) // hash
+                    PUSH1(0x0)
+                    MSTORE
+                    PUSH1(v)
+                    PUSH1(0x20)
+                    MSTORE
+                    PUSH32(word!("0x9242685bf161793cc25603c231bc2f568eb630ea16aa137d2664ac8038825608")) // r
+                    PUSH1(0x40)
+                    MSTORE
+                    PUSH32(s)
+                    PUSH1(0x60)
+                    MSTORE
+                },
+                ret_size: Word::from(0x20),
+                ret_offset: Word::from(0x80),
+                call_data_length: Word::from(0x80),
+                address: PrecompileCalls::Ecrecover.address().to_word(),
+                stack_value: vec![(Word::from(0x80), Word::zero())],
+                ..Default::default()
+            }
+            .with_call_op(OpcodeId::STATICCALL);
+
+            let block: GethData = TestContext::<2, 1>::new(
+                None,
+                account_0_code_account_1_no_code(code),
+                tx_from_1_to_0,
+                |block, _tx| block,
+            )
+            .unwrap()
+            .into();
+            let mut builder =
+                BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+            builder
+                .handle_block(&block.eth_block, &block.geth_traces)
+                .unwrap();
+
+            let mload = builder.block.txs()[0]
+                .steps()
+                .iter()
+                .filter(|step| step.exec_state == ExecState::Op(OpcodeId::MLOAD))
+                .last()
+                .unwrap();
+            builder.block.container.stack[mload.bus_mapping_instance[1].as_usize()]
+                .op()
+                .value
+        };
+
+        let secp256k1_n =
+            word!("0xfffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141");
+        let low_s = word!("0x4f8ae3bd7535248d0bd448298cc2e2071e56992d0774dc340c368ae950852ada");
+        let high_s = secp256k1_n - low_s;
+        assert!(high_s > secp256k1_n / 2);
+
+        let expected = address!("0x7156526fbd7a3c72969b54f64e42c10fbb768c8a").to_word();
+        assert_eq!(recover(28, low_s), expected);
+        assert_eq!(recover(27, high_s), expected);
+    }
 }
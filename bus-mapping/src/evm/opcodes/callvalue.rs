@@ -30,7 +30,7 @@ impl Opcode for Callvalue {
 
         // Stack write of the call_value
         #[cfg(feature = "enable-stack")]
-        assert_eq!(value, geth_steps[1].stack.last()?);
+        crate::util::check_geth_eq!(state, value, geth_steps[1].stack.last()?);
         state.stack_push(&mut exec_step, value)?;
 
         Ok(vec![exec_step])
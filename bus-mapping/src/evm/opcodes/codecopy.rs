@@ -25,9 +25,9 @@ impl Opcode for Codecopy {
 
         #[cfg(feature = "enable-stack")]
         {
-            assert_eq!(dest_offset, geth_step.stack.nth_last(0)?);
-            assert_eq!(code_offset, geth_step.stack.nth_last(1)?);
-            assert_eq!(length, geth_step.stack.nth_last(2)?);
+            crate::util::check_geth_eq!(state, dest_offset, geth_step.stack.nth_last(0)?);
+            crate::util::check_geth_eq!(state, code_offset, geth_step.stack.nth_last(1)?);
+            crate::util::check_geth_eq!(state, length, geth_step.stack.nth_last(2)?);
         }
 
         let copy_event = gen_copy_event(
@@ -91,7 +91,7 @@ mod codecopy_tests {
         evm_types::{MemoryAddress, OpcodeId, StackAddress},
         geth_types::GethData,
         state_db::CodeDB,
-        Word,
+        Bytecode, Word,
     };
     use mock::{
         test_ctx::{
@@ -102,7 +102,7 @@ mod codecopy_tests {
     };
 
     use crate::{
-        circuit_input_builder::{CopyDataType, ExecState, NumberOrHash},
+        circuit_input_builder::{CopyDataType, CopyEvent, ExecState, NumberOrHash},
         mock::BlockData,
         operation::{MemoryOp, StackOp, RW},
     };
@@ -113,6 +113,109 @@ mod codecopy_tests {
         test_ok(0x20, 0x40, 0xA0);
     }
 
+    #[test]
+    fn codecopy_past_code_end_zero_fills() {
+        let code = bytecode! {
+            PUSH1(0x40)
+            PUSH1(0)
+            PUSH1(0)
+            CODECOPY
+            STOP
+        };
+        let copy_event = codecopy_event(code.clone(), false);
+
+        let mut expected = code.to_vec();
+        expected.resize(0x40, 0);
+        assert_eq!(
+            copy_event.src_id,
+            NumberOrHash::Hash(CodeDB::hash(&code.to_vec()))
+        );
+        assert_eq!(copy_event.src_addr_end as usize, code.to_vec().len());
+        assert_eq!(copied_bytes(&copy_event), expected);
+    }
+
+    #[test]
+    fn codecopy_in_create_frame_copies_init_code() {
+        let init_code = bytecode! {
+            PUSH1(0x20)
+            PUSH1(0)
+            PUSH1(0)
+            CODECOPY
+            STOP
+        };
+        // Store the 8 bytes of init code at memory[24..32] and run it.
+        let code = bytecode! {
+            PUSH8(Word::from_big_endian(&init_code.to_vec()))
+            PUSH1(0)
+            MSTORE
+
+            PUSH1(8)
+            PUSH1(24)
+            PUSH1(0)
+            CREATE
+            STOP
+        };
+        let copy_event = codecopy_event(code, true);
+
+        let mut expected = init_code.to_vec();
+        expected.resize(0x20, 0);
+        assert_eq!(
+            copy_event.src_id,
+            NumberOrHash::Hash(CodeDB::hash(&init_code.to_vec()))
+        );
+        assert_eq!(copy_event.src_type, CopyDataType::Bytecode);
+        assert_eq!(copy_event.src_addr_end as usize, init_code.to_vec().len());
+        assert_eq!(copied_bytes(&copy_event), expected);
+    }
+
+    /// Run `code` and return the copy event of its only CODECOPY, checking
+    /// that it writes to the memory of the (creation) call executing it.
+    fn codecopy_event(code: Bytecode, in_create: bool) -> CopyEvent {
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            account_0_code_account_1_no_code(code),
+            tx_from_1_to_0,
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let tx = &builder.block.txs()[0];
+        let step = tx
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::CODECOPY))
+            .unwrap();
+        let call = &tx.calls()[step.call_index];
+        assert_eq!(call.is_create(), in_create);
+
+        let copy_event = builder
+            .block
+            .copy_events
+            .iter()
+            .find(|event| event.src_type == CopyDataType::Bytecode)
+            .unwrap()
+            .clone();
+        assert_eq!(copy_event.dst_type, CopyDataType::Memory);
+        assert_eq!(copy_event.dst_id, NumberOrHash::Number(call.call_id));
+        copy_event
+    }
+
+    fn copied_bytes(copy_event: &CopyEvent) -> Vec<u8> {
+        copy_event
+            .copy_bytes
+            .bytes
+            .iter()
+            .filter(|(_, _, is_mask)| !is_mask)
+            .map(|(value, _, _)| *value)
+            .collect()
+    }
+
     fn test_ok(memory_offset: usize, code_offset: usize, copy_size: usize) {
         let code = bytecode! {
             PUSH32(copy_size)
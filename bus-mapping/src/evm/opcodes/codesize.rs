@@ -23,7 +23,7 @@ impl Opcode for Codesize {
         let codesize = code.len();
 
         #[cfg(feature = "enable-stack")]
-        assert_eq!(codesize, geth_steps[1].stack.last()?.as_usize());
+        crate::util::check_geth_eq!(state, codesize, geth_steps[1].stack.last()?.as_usize());
         state.stack_push(&mut exec_step, codesize.into())?;
 
         Ok(vec![exec_step])
@@ -100,4 +100,60 @@ mod codesize_tests {
         test_ok(false);
         test_ok(true);
     }
+
+    #[test]
+    fn codesize_opcode_in_create_frame() {
+        // The init code is CODESIZE STOP, stored at memory[30..32].
+        let init_code = bytecode! {
+            CODESIZE
+            STOP
+        };
+        let code = bytecode! {
+            PUSH2(0x3800)
+            PUSH1(0)
+            MSTORE
+
+            PUSH1(2)
+            PUSH1(30)
+            PUSH1(0)
+            CREATE
+            CODESIZE
+            STOP
+        };
+        let codesize = code.to_vec().len();
+
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            account_0_code_account_1_no_code(code),
+            tx_from_1_to_0,
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let tx = &builder.block.txs()[0];
+        let steps = tx
+            .steps()
+            .iter()
+            .filter(|step| step.exec_state == ExecState::Op(OpcodeId::CODESIZE))
+            .collect::<Vec<_>>();
+        assert_eq!(steps.len(), 2);
+
+        // The first CODESIZE runs in the CREATE frame and sees the init code,
+        // the second one runs back in the deployed contract.
+        let expected = [(true, init_code.to_vec().len()), (false, codesize)];
+        for (step, (is_create, expected)) in steps.into_iter().zip(expected) {
+            let call = &tx.calls()[step.call_index];
+            assert_eq!(call.is_create(), is_create);
+            let op = &builder.block.container.stack[step.bus_mapping_instance[0].as_usize()];
+            assert_eq!(op.rw(), RW::WRITE);
+            assert_eq!(op.op().call_id, call.call_id);
+            assert_eq!(op.op().value, Word::from(expected));
+        }
+    }
 }
@@ -108,7 +108,7 @@ impl<const IS_CREATE2: bool> Opcode for Create<IS_CREATE2> {
         let stack_inputs = state.stack_pops(&mut exec_step, n_pop)?;
         #[cfg(feature = "enable-stack")]
         for (i, value) in stack_inputs.iter().enumerate() {
-            assert_eq!(*value, geth_step.stack.nth_last(i)?);
+            crate::util::check_geth_eq!(state, *value, geth_step.stack.nth_last(i)?);
         }
 
         state.stack_push(
@@ -289,6 +289,7 @@ impl<const IS_CREATE2: bool> Opcode for Create<IS_CREATE2> {
                     value_prev: 0.into(),
                 },
             )?;
+            state.sdb.set_created(&callee.address);
 
             if length > 0 {
                 for (field, value) in [
@@ -409,10 +410,16 @@ fn handle_copy(
 mod tests {
     use super::*;
     use crate::{circuit_input_builder::ExecState, mock::BlockData, operation::RW};
-    use eth_types::{bytecode, evm_types::OpcodeId, geth_types::GethData, word};
+    use eth_types::{
+        bytecode,
+        evm_types::{GasCost, OpcodeId, INIT_CODE_WORD_GAS},
+        geth_types::GethData,
+        word,
+    };
     use mock::{
+        eth,
         test_ctx::{helpers::account_0_code_account_1_no_code, LoggerConfig},
-        TestContext,
+        TestContext, MOCK_ACCOUNTS,
     };
 
     #[test]
@@ -483,4 +490,370 @@ mod tests {
         let operation = &container.stack[step.bus_mapping_instance[5].as_usize()];
         assert_eq!(operation.rw(), RW::READ);
     }
+
+    #[test]
+    fn test_create2_init_code_and_keccak_gas() {
+        // 33 bytes of (zero) init code span 2 words.
+        let init_code_size = 0x21_u64;
+        let init_code_words = 2_u64;
+        let code = bytecode! {
+            PUSH1 (0xef) // salt
+            PUSH1 (init_code_size) // size
+            PUSH1 (0)   // offset
+            PUSH1 (0)   // value
+            CREATE2
+            STOP
+        };
+
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            account_0_code_account_1_no_code(code),
+            |mut txs, accs| {
+                txs[0].from(accs[1].address).to(accs[0].address);
+            },
+            |block, _tx| block,
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let step = builder.block.txs()[0]
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::CREATE2))
+            .unwrap();
+
+        // Expanding memory from 0 to 2 words costs 2 * 3 + 2 * 2 / 512.
+        let memory_expansion_gas = 6;
+        let init_code_gas = init_code_words * INIT_CODE_WORD_GAS;
+        let keccak_gas = init_code_words * GasCost::COPY_SHA3.as_u64();
+        assert_eq!(init_code_gas, 4);
+        assert_eq!(keccak_gas, 12);
+        assert_eq!(
+            step.gas_cost.as_u64(),
+            GasCost::CREATE.as_u64() + memory_expansion_gas + init_code_gas + keccak_gas
+        );
+    }
+
+    #[test]
+    fn test_selfdestruct_destructed_accounts() {
+        let beneficiary = MOCK_ACCOUNTS[1];
+        let init_code = bytecode! {
+            PUSH20 (beneficiary.to_word())
+            SELFDESTRUCT
+        };
+        let init_code_len = init_code.code().len() as u64;
+        // Store the init code in the low bytes of the first memory word, and
+        // deploy it with CREATE2 so that it self destructs right away.
+        let factory_code = bytecode! {
+            PUSH22 (Word::from_big_endian(&init_code.code()))
+            PUSH1 (0)
+            MSTORE
+            PUSH1 (0) // salt
+            PUSH1 (init_code_len) // size
+            PUSH1 (32 - init_code_len) // offset
+            PUSH1 (0) // value
+            CREATE2
+            STOP
+        };
+        // A contract deployed before the block, which self destructs when
+        // called.
+        let destructor_code = bytecode! {
+            PUSH20 (beneficiary.to_word())
+            SELFDESTRUCT
+        };
+
+        let block: GethData = TestContext::<3, 2>::new(
+            None,
+            |accs| {
+                accs[0].address(MOCK_ACCOUNTS[0]).code(factory_code);
+                accs[1].address(beneficiary).balance(eth(10));
+                accs[2]
+                    .address(MOCK_ACCOUNTS[2])
+                    .balance(eth(1))
+                    .code(destructor_code);
+            },
+            |mut txs, accs| {
+                txs[0].from(accs[1].address).to(accs[0].address);
+                txs[1].from(accs[1].address).to(accs[2].address);
+            },
+            |block, _tx| block,
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let created = get_create2_address(MOCK_ACCOUNTS[0], [0u8; 32], init_code.code());
+        // Created and self destructed in the same tx, so it's deleted.
+        assert_eq!(builder.sdb.destructed_accounts(), vec![created]);
+
+        // Since EIP-6780 the pre-existing contract only loses its balance.
+        let (_, destructor) = builder.sdb.get_account(&MOCK_ACCOUNTS[2]);
+        assert_eq!(destructor.balance, Word::zero());
+        assert_ne!(destructor.code_hash, CodeDB::empty_code_hash());
+        assert!(!destructor.code_hash.is_zero());
+    }
+
+    #[test]
+    fn test_created_contracts() {
+        // Init code deploying `PUSH1(1) STOP`.
+        let init_code = bytecode! {
+            .op_mstore(0, Word::from(0x600100))
+            .op_return(29, 3)
+        };
+        let init_code = init_code.code();
+        let salt = Word::from(0xef);
+        let factory = bytecode! {
+            PUSH32(Word::from_big_endian(&init_code) << (8 * (32 - init_code.len())))
+            PUSH1(0)
+            MSTORE
+            PUSH32(salt)
+            PUSH1(init_code.len()) // size
+            PUSH1(0) // offset
+            PUSH1(0) // value
+            CREATE2
+            STOP
+        };
+
+        let block: GethData = TestContext::<2, 2>::new(
+            None,
+            account_0_code_account_1_no_code(factory),
+            |mut txs, accs| {
+                txs[0].from(accs[1].address).input(init_code.clone().into());
+                txs[1].from(accs[1].address).to(accs[0].address);
+            },
+            |block, _tx| block,
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let code_hash = CodeDB::hash(&[0x60, 0x01, 0x00]);
+        assert_eq!(
+            builder.created_contracts(),
+            vec![
+                (
+                    ethers_core::utils::get_contract_address(MOCK_ACCOUNTS[1], 0),
+                    code_hash
+                ),
+                (
+                    get_create2_address(MOCK_ACCOUNTS[0], salt.to_be_bytes(), init_code),
+                    code_hash
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_create2_address_follows_eip1014() {
+        // Init code deploying `PUSH1(1) STOP`.
+        let init_code = bytecode! {
+            .op_mstore(0, Word::from(0x600100))
+            .op_return(29, 3)
+        };
+        let init_code = init_code.code();
+        let salt = word!("0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef");
+        let factory = bytecode! {
+            PUSH32(Word::from_big_endian(&init_code) << (8 * (32 - init_code.len())))
+            PUSH1(0)
+            MSTORE
+            PUSH32(salt)
+            PUSH1(init_code.len()) // size
+            PUSH1(0) // offset
+            PUSH1(0) // value
+            CREATE2
+            STOP
+        };
+
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            account_0_code_account_1_no_code(factory),
+            |mut txs, accs| {
+                txs[0].from(accs[1].address).to(accs[0].address);
+            },
+            |block, _tx| block,
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        // keccak256(0xff ++ sender ++ salt ++ keccak256(init_code))[12..]
+        let mut preimage = vec![0xff];
+        preimage.extend_from_slice(MOCK_ACCOUNTS[0].as_bytes());
+        preimage.extend_from_slice(&salt.to_be_bytes());
+        preimage.extend_from_slice(&keccak256(&init_code));
+        let expected = H160::from_slice(&keccak256(&preimage)[12..]);
+
+        assert_eq!(builder.created_contracts()[0].0, expected);
+        assert!(builder.block.sha3_inputs.contains(&preimage));
+        assert!(builder.block.sha3_inputs.contains(&init_code));
+    }
+
+    #[test]
+    fn test_keccak_inputs_include_sha3_and_deployment() {
+        let init_code = bytecode! {
+            .op_mstore(0, Word::from(0x600100))
+            .op_return(29, 3)
+        };
+        let init_code = init_code.code();
+        let hashed = bytecode! {
+            PUSH32(Word::from(0xdeadbeefu64))
+            PUSH1(0)
+            MSTORE
+            PUSH1(0x20) // size
+            PUSH1(0) // offset
+            SHA3
+            STOP
+        };
+
+        let block: GethData = TestContext::<2, 2>::new(
+            None,
+            account_0_code_account_1_no_code(hashed),
+            |mut txs, accs| {
+                txs[0].from(accs[1].address).input(init_code.clone().into());
+                txs[1].from(accs[1].address).to(accs[0].address);
+            },
+            |block, _tx| block,
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let inputs =
+            crate::circuit_input_builder::keccak_inputs(&builder.block, &builder.code_db).unwrap();
+
+        // SHA3 preimage.
+        assert!(inputs.contains(&Word::from(0xdeadbeefu64).to_be_bytes().to_vec()));
+        // Deployment: the contract address preimage RLP([sender, nonce]) and the init code.
+        let mut stream = ethers_core::utils::rlp::RlpStream::new();
+        stream.begin_list(2);
+        stream.append(&MOCK_ACCOUNTS[1]);
+        stream.append(&Word::zero());
+        assert!(inputs.contains(&stream.out().to_vec()));
+        assert!(inputs.contains(&init_code));
+    }
+
+    #[test]
+    fn test_create_init_code_runs_in_child_frame() {
+        use crate::{
+            circuit_input_builder::CallKind,
+            operation::{StorageOp, Target},
+        };
+
+        // Init code writing to the new contract's storage and deploying
+        // `PUSH1(1) STOP`.
+        let init_code = bytecode! {
+            PUSH1(0x2a)
+            PUSH1(0)
+            SSTORE
+            .op_mstore(0, Word::from(0x600100))
+            .op_return(29, 3)
+        };
+        let init_code = init_code.code();
+        let code = bytecode! {
+            PUSH32(Word::from_big_endian(&init_code) << (8 * (32 - init_code.len())))
+            PUSH1(0)
+            MSTORE
+            PUSH1(init_code.len()) // size
+            PUSH1(0) // offset
+            PUSH1(0) // value
+            CREATE
+            STOP
+        };
+
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            account_0_code_account_1_no_code(code),
+            |mut txs, accs| {
+                txs[0].from(accs[1].address).to(accs[0].address);
+            },
+            |block, _tx| block,
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let code_hash = CodeDB::hash(&[0x60, 0x01, 0x00]);
+        let created_contracts = builder.created_contracts();
+        assert_eq!(created_contracts.len(), 1);
+        let (address, deployed_code_hash) = created_contracts[0];
+        assert_eq!(deployed_code_hash, code_hash);
+
+        // The init code runs as a child call of the creator.
+        let tx = &builder.block.txs()[0];
+        let child = &tx.calls()[1];
+        assert_eq!(child.kind, CallKind::Create);
+        assert_eq!(child.caller_id, tx.calls()[0].call_id);
+        assert_eq!(child.address, address);
+        assert_eq!(child.code_hash, CodeDB::hash(&init_code));
+        assert!(child.is_success);
+
+        // Its SSTORE writes to the storage of the new contract.
+        let sstore = tx
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::SSTORE))
+            .unwrap();
+        assert_eq!(sstore.call_index, 1);
+        assert!(builder
+            .block
+            .container
+            .storage
+            .iter()
+            .any(|op| op.rw() == RW::WRITE
+                && *op.op()
+                    == StorageOp::new(
+                        address,
+                        Word::zero(),
+                        Word::from(0x2a),
+                        Word::zero(),
+                        1,
+                        Word::zero(),
+                    )));
+        assert_eq!(
+            builder.sdb.get_storage(&address, &Word::zero()).1,
+            &Word::from(0x2a)
+        );
+
+        // CREATE pushes the address of the deployed contract to the parent's
+        // stack.
+        let create = tx
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::CREATE))
+            .unwrap();
+        assert_eq!(create.call_index, 0);
+        let pushed = create
+            .bus_mapping_instance
+            .iter()
+            .filter(|op_ref| op_ref.target() == Target::Stack)
+            .map(|op_ref| &builder.block.container.stack[op_ref.as_usize()])
+            .find(|op| op.rw() == RW::WRITE)
+            .unwrap();
+        assert_eq!(pushed.op().value, address.to_word());
+    }
 }
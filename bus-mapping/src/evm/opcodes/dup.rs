@@ -22,8 +22,12 @@ impl<const N: usize> Opcode for Dup<N> {
         let stack_position = state.call_ctx()?.stack.nth_last_filled(N - 1);
         #[cfg(feature = "enable-stack")]
         {
-            assert_eq!(stack_value_read, geth_step.stack.nth_last(N - 1)?);
-            assert_eq!(stack_position, geth_step.stack.nth_last_filled(N - 1));
+            crate::util::check_geth_eq!(state, stack_value_read, geth_step.stack.nth_last(N - 1)?);
+            crate::util::check_geth_eq!(
+                state,
+                stack_position,
+                geth_step.stack.nth_last_filled(N - 1)
+            );
         }
         state.stack_read(&mut exec_step, stack_position, stack_value_read)?;
         state.stack_push(&mut exec_step, stack_value_read)?;
@@ -30,6 +30,8 @@ impl BlockHeaderToField for GetBlockHeaderField<{ OpcodeId::NUMBER }> {
     }
 }
 
+// `BlockHead::difficulty` is the difficulty of a pre-Merge block, or the
+// prevRandao (the header's mix hash) of a post-Merge one whose difficulty is 0.
 impl BlockHeaderToField for GetBlockHeaderField<{ OpcodeId::DIFFICULTY }> {
     fn handle(block_head: &BlockHead) -> Word {
         block_head.difficulty
@@ -68,7 +70,7 @@ where
         let output = Self::handle(block_head);
 
         #[cfg(feature = "enable-stack")]
-        assert_eq!(output, geth_steps[1].stack.last()?);
+        crate::util::check_geth_eq!(state, output, geth_steps[1].stack.last()?);
         state.stack_push(&mut exec_step, output)?;
 
         Ok(vec![exec_step])
@@ -87,7 +89,7 @@ impl Opcode for Pc {
         let output: U256 = geth_steps[0].pc.0.into();
 
         #[cfg(feature = "enable-stack")]
-        assert_eq!(output, geth_steps[1].stack.last()?);
+        crate::util::check_geth_eq!(state, output, geth_steps[1].stack.last()?);
         state.stack_push(&mut exec_step, output)?;
 
         Ok(vec![exec_step])
@@ -106,7 +108,7 @@ impl Opcode for Msize {
         let output: U256 = state.call_ctx()?.memory.len().into();
 
         #[cfg(feature = "enable-stack")]
-        assert_eq!(output, geth_steps[1].stack.last()?);
+        crate::util::check_geth_eq!(state, output, geth_steps[1].stack.last()?);
         state.stack_push(&mut exec_step, output)?;
 
         Ok(vec![exec_step])
@@ -125,7 +127,7 @@ impl Opcode for Gas {
         let output: U256 = geth_steps[1].gas.0.into();
 
         #[cfg(feature = "enable-stack")]
-        assert_eq!(output, geth_steps[1].stack.last()?);
+        crate::util::check_geth_eq!(state, output, geth_steps[1].stack.last()?);
         state.stack_push(&mut exec_step, output)?;
 
         Ok(vec![exec_step])
@@ -134,9 +136,18 @@ impl Opcode for Gas {
 
 #[cfg(test)]
 mod tests {
-    use crate::mock::BlockData;
-    use eth_types::{bytecode, geth_types::GethData, Bytecode};
-    use mock::TestContext;
+    use super::{BlockHeaderToField, GetBlockHeaderField};
+    use crate::{
+        circuit_input_builder::{BlockHead, ExecState},
+        mock::BlockData,
+        operation::RW,
+        Error,
+    };
+    use eth_types::{bytecode, evm_types::OpcodeId, geth_types::GethData, Bytecode, Word, H256};
+    use mock::{
+        test_ctx::helpers::{account_0_code_account_1_no_code, tx_from_1_to_0},
+        TestContext,
+    };
 
     fn test_trace(code: Bytecode) {
         // Get the execution steps from the external tracer
@@ -158,6 +169,36 @@ mod tests {
         });
     }
 
+    #[test]
+    fn difficulty_before_and_after_the_merge() {
+        let block: GethData = TestContext::<2, 1>::simple_ctx_with_bytecode(bytecode! {
+            DIFFICULTY
+            STOP
+        })
+        .unwrap()
+        .into();
+        let prev_randao = H256::from_low_u64_be(0xabcd);
+
+        // London: the header carries the actual difficulty.
+        let mut london = block.eth_block.clone();
+        london.difficulty = Word::from(0x20000);
+        london.mix_hash = Some(prev_randao);
+        // Paris: the difficulty is 0 and the mix hash is prevRandao (EIP-4399).
+        let mut paris = london.clone();
+        paris.difficulty = Word::zero();
+
+        for (eth_block, expected) in [
+            (london, Word::from(0x20000)),
+            (paris, Word::from_big_endian(prev_randao.as_bytes())),
+        ] {
+            let head = BlockHead::new(block.chain_id, Vec::new(), &eth_block).unwrap();
+            assert_eq!(
+                GetBlockHeaderField::<{ OpcodeId::DIFFICULTY }>::handle(&head),
+                expected
+            );
+        }
+    }
+
     #[test]
     fn gas_limit_opcode_impl() {
         test_trace(bytecode! {
@@ -166,6 +207,125 @@ mod tests {
         });
     }
 
+    #[test]
+    fn gas_limit_opcode_pushes_header_gas_limit() {
+        let code = bytecode! {
+            GASLIMIT
+            STOP
+        };
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            account_0_code_account_1_no_code(code),
+            tx_from_1_to_0,
+            |block, _tx| block.gas_limit(Word::from(0x1c9c380u64)),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let step = builder.block.txs()[0]
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::GASLIMIT))
+            .unwrap();
+        let operation = &builder.block.container.stack[step.bus_mapping_instance[0].as_usize()];
+        assert_eq!(operation.rw(), RW::WRITE);
+        assert_eq!(operation.op().value, block.eth_block.gas_limit);
+        assert_eq!(operation.op().value, Word::from(0x1c9c380u64));
+    }
+
+    #[test]
+    fn msize_opcode_pushes_word_aligned_memory_size() {
+        let code = bytecode! {
+            MSIZE
+            PUSH1(0x2a)
+            PUSH1(0)
+            MSTORE
+            MSIZE
+            PUSH1(0x2a)
+            PUSH1(40)
+            MSTORE
+            MSIZE
+            STOP
+        };
+        let block: GethData = TestContext::<2, 1>::simple_ctx_with_bytecode(code)
+            .unwrap()
+            .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let msizes = builder.block.txs()[0]
+            .steps()
+            .iter()
+            .filter(|step| step.exec_state == ExecState::Op(OpcodeId::MSIZE))
+            .map(|step| {
+                assert_eq!(step.gas_cost.as_u64(), 2);
+                let operation =
+                    &builder.block.container.stack[step.bus_mapping_instance[0].as_usize()];
+                assert_eq!(operation.rw(), RW::WRITE);
+                operation.op().value
+            })
+            .collect::<Vec<_>>();
+        // Nothing accessed yet, one word, then [40, 72) rounded up to 3 words.
+        assert_eq!(msizes, vec![Word::zero(), Word::from(32), Word::from(96)]);
+    }
+
+    #[test]
+    fn txs_gas_exceeding_block_gas_limit() {
+        let code = bytecode! {
+            STOP
+        };
+        let block: GethData = TestContext::<2, 2>::new(
+            None,
+            account_0_code_account_1_no_code(code),
+            |mut txs, accs| {
+                txs[0]
+                    .from(accs[1].address)
+                    .to(accs[0].address)
+                    .gas(Word::from(30_000));
+                txs[1]
+                    .from(accs[1].address)
+                    .to(accs[0].address)
+                    .gas(Word::from(30_000));
+            },
+            |block, _tx| block,
+        )
+        .unwrap()
+        .into();
+        // The external tracer rejects such blocks itself, so only lower the
+        // gas limit once the traces have been produced.
+        let handle_block = |gas_limit: u64| {
+            let mut block = block.clone();
+            block.eth_block.gas_limit = Word::from(gas_limit);
+            let mut builder =
+                BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+            builder.handle_block(&block.eth_block, &block.geth_traces)
+        };
+
+        // The gas limits add up to more than the block gas limit, but the
+        // second tx fits in what the first one left: 55000 - 21000 >= 30000.
+        handle_block(55_000).unwrap();
+        // 50000 - 21000 < 30000
+        assert!(matches!(
+            handle_block(50_000),
+            Err(Error::TxGasLimitExceedsBlockGasLeft(tx_gas, gas_left))
+                if tx_gas == Word::from(30_000) && gas_left == Word::from(29_000)
+        ));
+        // Already the first tx doesn't fit.
+        assert!(matches!(
+            handle_block(20_000),
+            Err(Error::TxGasLimitExceedsBlockGasLeft(_, gas_left))
+                if gas_left == Word::from(20_000)
+        ));
+    }
+
     #[test]
     fn basefee_opcode_impl() {
         test_trace(bytecode! {
@@ -23,8 +23,8 @@ impl Opcode for ErrorCreationCode {
         let length = state.stack_pop(&mut exec_step)?;
         #[cfg(feature = "enable-stack")]
         {
-            assert_eq!(offset, geth_step.stack.nth_last(0)?);
-            assert_eq!(length, geth_step.stack.nth_last(1)?);
+            crate::util::check_geth_eq!(state, offset, geth_step.stack.nth_last(0)?);
+            crate::util::check_geth_eq!(state, length, geth_step.stack.nth_last(1)?);
         }
 
         // in create context
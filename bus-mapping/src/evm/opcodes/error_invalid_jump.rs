@@ -32,9 +32,9 @@ impl Opcode for InvalidJump {
         };
         #[cfg(feature = "enable-stack")]
         {
-            assert_eq!(_counter, geth_step.stack.last()?);
+            crate::util::check_geth_eq!(state, _counter, geth_step.stack.last()?);
             if is_jumpi {
-                assert_eq!(_condition, geth_step.stack.nth_last(1)?);
+                crate::util::check_geth_eq!(state, _condition, geth_step.stack.nth_last(1)?);
             }
         }
 
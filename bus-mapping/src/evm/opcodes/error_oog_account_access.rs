@@ -30,7 +30,7 @@ impl Opcode for ErrorOOGAccountAccess {
         let address_word = state.stack_pop(&mut exec_step)?;
         let address = address_word.to_address();
         #[cfg(feature = "enable-stack")]
-        assert_eq!(address_word, geth_step.stack.last()?);
+        crate::util::check_geth_eq!(state, address_word, geth_step.stack.last()?);
 
         // Read transaction ID from call context.
         state.call_context_read(
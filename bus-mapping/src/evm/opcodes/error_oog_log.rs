@@ -35,8 +35,8 @@ impl Opcode for ErrorOOGLog {
         let _msize = state.stack_pop(&mut exec_step)?;
         #[cfg(feature = "enable-stack")]
         {
-            assert_eq!(_mstart, geth_step.stack.nth_last(0)?);
-            assert_eq!(_msize, geth_step.stack.nth_last(1)?);
+            crate::util::check_geth_eq!(state, _mstart, geth_step.stack.nth_last(0)?);
+            crate::util::check_geth_eq!(state, _msize, geth_step.stack.nth_last(1)?);
         }
 
         // read static call property
@@ -62,7 +62,7 @@ impl Opcode for OOGMemoryCopy {
         let _stack_inputs = state.stack_pops(&mut exec_step, stack_read_num)?;
         #[cfg(feature = "enable-stack")]
         for (i, v) in _stack_inputs.iter().enumerate() {
-            assert_eq!(*v, geth_step.stack.nth_last(i)?);
+            crate::util::check_geth_eq!(state, *v, geth_step.stack.nth_last(i)?);
         }
 
         state.handle_return((None, None), &mut [&mut exec_step], geth_steps, true)?;
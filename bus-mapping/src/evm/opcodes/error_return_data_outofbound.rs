@@ -30,9 +30,9 @@ impl Opcode for ErrorReturnDataOutOfBound {
         let length = state.stack_pop(&mut exec_step)?;
         #[cfg(feature = "enable-stack")]
         {
-            assert_eq!(_memory_offset, geth_step.stack.nth_last(0)?);
-            assert_eq!(data_offset, geth_step.stack.nth_last(1)?);
-            assert_eq!(length, geth_step.stack.nth_last(2)?);
+            crate::util::check_geth_eq!(state, _memory_offset, geth_step.stack.nth_last(0)?);
+            crate::util::check_geth_eq!(state, data_offset, geth_step.stack.nth_last(1)?);
+            crate::util::check_geth_eq!(state, length, geth_step.stack.nth_last(2)?);
         }
 
         let call_id = state.call()?.call_id;
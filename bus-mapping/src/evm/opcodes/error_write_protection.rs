@@ -50,7 +50,7 @@ impl Opcode for ErrorWriteProtection {
             for _i in 0..3 {
                 let _v = state.stack_pop(&mut exec_step)?;
                 #[cfg(feature = "enable-stack")]
-                assert_eq!(_v, geth_step.stack.nth_last(_i)?);
+                crate::util::check_geth_eq!(state, _v, geth_step.stack.nth_last(_i)?);
             }
         }
 
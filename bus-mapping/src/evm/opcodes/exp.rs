@@ -45,8 +45,8 @@ impl Opcode for Exponentiation {
         let exponent = state.stack_pop(&mut exec_step)?;
         #[cfg(feature = "enable-stack")]
         {
-            assert_eq!(base, geth_step.stack.nth_last(0)?);
-            assert_eq!(exponent, geth_step.stack.nth_last(1)?);
+            crate::util::check_geth_eq!(state, base, geth_step.stack.nth_last(0)?);
+            crate::util::check_geth_eq!(state, exponent, geth_step.stack.nth_last(1)?);
         }
 
         let (exponentiation, _) = base.overflowing_pow(exponent);
@@ -68,9 +68,11 @@ impl Opcode for Exponentiation {
 
 #[cfg(test)]
 mod tests {
-    use eth_types::U256;
+    use eth_types::{evm_types::OpcodeId, geth_types::GethData, Bytecode, Word, U256};
+    use mock::test_ctx::TestContext;
 
     use super::exp_by_squaring;
+    use crate::{circuit_input_builder::ExecState, mock::BlockData};
 
     #[test]
     fn test_exp_by_squaring() {
@@ -98,4 +100,43 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn exp_gas_cost_by_exponent_byte_size() {
+        // (exponent, expected gas): 10 + 50 * byte_size(exponent)
+        let cases = [
+            (Word::zero(), 10),
+            (Word::one(), 60),
+            (Word::from(255), 60),
+            (Word::from(256), 110),
+            (Word::one() << 255, 1610),
+        ];
+        let mut code = Bytecode::default();
+        for (exponent, _) in cases {
+            code.push(32, exponent);
+            code.push(1, 2);
+            code.write_op(OpcodeId::EXP);
+            code.write_op(OpcodeId::POP);
+        }
+        code.write_op(OpcodeId::STOP);
+
+        let block: GethData = TestContext::<2, 1>::simple_ctx_with_bytecode(code)
+            .unwrap()
+            .into();
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let gas_costs = builder.block.txs()[0]
+            .steps()
+            .iter()
+            .filter(|step| step.exec_state == ExecState::Op(OpcodeId::EXP))
+            .map(|step| step.gas_cost.as_u64())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            gas_costs,
+            cases.iter().map(|(_, gas)| *gas).collect::<Vec<_>>()
+        );
+    }
 }
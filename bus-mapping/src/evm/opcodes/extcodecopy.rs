@@ -26,10 +26,10 @@ impl Opcode for Extcodecopy {
         let length = state.stack_pop(&mut exec_step)?;
         #[cfg(feature = "enable-stack")]
         {
-            assert_eq!(external_address_word, geth_step.stack.nth_last(0)?);
-            assert_eq!(dest_offset, geth_step.stack.nth_last(1)?);
-            assert_eq!(offset, geth_step.stack.nth_last(2)?);
-            assert_eq!(length, geth_step.stack.nth_last(3)?);
+            crate::util::check_geth_eq!(state, external_address_word, geth_step.stack.nth_last(0)?);
+            crate::util::check_geth_eq!(state, dest_offset, geth_step.stack.nth_last(1)?);
+            crate::util::check_geth_eq!(state, offset, geth_step.stack.nth_last(2)?);
+            crate::util::check_geth_eq!(state, length, geth_step.stack.nth_last(3)?);
         }
 
         for (field, value) in [
@@ -47,6 +47,7 @@ impl Opcode for Extcodecopy {
         }
 
         let is_warm = state.sdb.check_account_in_access_list(&external_address);
+        exec_step.gas_breakdown.set_account_access(is_warm);
         state.push_op_reversible(
             &mut exec_step,
             TxAccessListAccountOp {
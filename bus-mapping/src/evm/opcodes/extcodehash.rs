@@ -22,7 +22,7 @@ impl Opcode for Extcodehash {
         let external_address_word = state.stack_pop(&mut exec_step)?;
         let external_address = external_address_word.to_address();
         #[cfg(feature = "enable-stack")]
-        assert_eq!(external_address_word, step.stack.last()?);
+        crate::util::check_geth_eq!(state, external_address_word, step.stack.last()?);
 
         // Read transaction id, rw_counter_end_of_reversion, and is_persistent from call
         // context
@@ -43,6 +43,7 @@ impl Opcode for Extcodehash {
 
         // Update transaction access list for external_address
         let is_warm = state.sdb.check_account_in_access_list(&external_address);
+        exec_step.gas_breakdown.set_account_access(is_warm);
         state.push_op_reversible(
             &mut exec_step,
             TxAccessListAccountOp {
@@ -77,7 +78,7 @@ impl Opcode for Extcodehash {
             code_hash.to_word(),
         )?;
         #[cfg(feature = "enable-stack")]
-        assert_eq!(steps[1].stack.last()?, code_hash.to_word());
+        crate::util::check_geth_eq!(state, steps[1].stack.last()?, code_hash.to_word());
         // Stack write of the result of EXTCODEHASH.
         state.stack_push(&mut exec_step, code_hash.to_word())?;
 
@@ -123,6 +124,71 @@ mod extcodehash_tests {
         test_ok(true, true)
     }
 
+    #[test]
+    fn existing_account_after_selfdestruct() {
+        // Since EIP-6780 SELFDESTRUCT only deletes an account created in the
+        // same transaction, so a pre-existing contract keeps its code.
+        let beneficiary = address!("0x0000000000000000000000000000000000cafe01");
+        let destructor = address!("0xaabbccddee000000000000000000000000000000");
+        let destructor_code = bytecode! {
+            PUSH20(beneficiary.to_word())
+            SELFDESTRUCT
+        };
+        let code = bytecode! {
+            PUSH1(0) // retSize
+            PUSH1(0) // retOffset
+            PUSH1(0) // argsSize
+            PUSH1(0) // argsOffset
+            PUSH1(0) // value
+            PUSH20(destructor.to_word())
+            GAS
+            CALL
+            POP
+            PUSH20(destructor.to_word())
+            EXTCODEHASH
+            STOP
+        };
+
+        let block: GethData = TestContext::<3, 1>::new(
+            None,
+            |accs| {
+                accs[0]
+                    .address(address!("0x0000000000000000000000000000000000000010"))
+                    .code(code);
+                accs[1]
+                    .address(destructor)
+                    .balance(Word::from(800u64))
+                    .code(destructor_code.clone());
+                accs[2].address(beneficiary).balance(Word::from(1u64 << 20));
+            },
+            |mut txs, accs| {
+                txs[0].to(accs[0].address).from(accs[2].address);
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let code_hash = Word::from(keccak256(destructor_code.code()));
+        let step = builder.block.txs()[0]
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::EXTCODEHASH))
+            .unwrap();
+        let pushed = &builder.block.container.stack[step.bus_mapping_instance[6].as_usize()];
+        assert_eq!(pushed.rw(), RW::WRITE);
+        assert_eq!(pushed.op().value, code_hash);
+
+        let (_, account) = builder.sdb.get_account(&destructor);
+        assert!(account.balance.is_zero());
+        assert!(builder.sdb.destructed_accounts().is_empty());
+    }
+
     fn test_ok(exists: bool, is_warm: bool) -> Result<(), Error> {
         // In each test case, this is the external address we will call EXTCODEHASH on.
         let external_address = address!("0xaabbccddee000000000000000000000000000000");
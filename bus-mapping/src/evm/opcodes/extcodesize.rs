@@ -21,7 +21,7 @@ impl Opcode for Extcodesize {
         let address_word = state.stack_pop(&mut exec_step)?;
         let address = address_word.to_address();
         #[cfg(feature = "enable-stack")]
-        assert_eq!(address_word, geth_step.stack.last()?);
+        crate::util::check_geth_eq!(state, address_word, geth_step.stack.last()?);
 
         // Read transaction ID, rw_counter_end_of_reversion, and is_persistent from call
         // context.
@@ -41,6 +41,7 @@ impl Opcode for Extcodesize {
 
         // Update transaction access list for account address.
         let is_warm = state.sdb.check_account_in_access_list(&address);
+        exec_step.gas_breakdown.set_account_access(is_warm);
         state.push_op_reversible(
             &mut exec_step,
             TxAccessListAccountOp {
@@ -79,7 +80,7 @@ impl Opcode for Extcodesize {
 
         // Write the EXTCODESIZE result to stack.
         #[cfg(feature = "enable-stack")]
-        assert_eq!(code_size, geth_steps[1].stack.last()?);
+        crate::util::check_geth_eq!(state, code_size, geth_steps[1].stack.last()?);
         state.stack_push(&mut exec_step, code_size)?;
 
         Ok(vec![exec_step])
@@ -99,7 +100,7 @@ mod extcodesize_tests {
         evm_types::{OpcodeId, StackAddress},
         geth_types::{Account, GethData},
         state_db::CodeDB,
-        Bytecode, U256,
+        Address, Bytecode, U256,
     };
     use mock::{TestContext, MOCK_1_ETH, MOCK_ACCOUNTS, MOCK_CODES, MOCK_COINBASE};
     use pretty_assertions::assert_eq;
@@ -120,6 +121,73 @@ mod extcodesize_tests {
         test_ok(&account, true);
     }
 
+    #[test]
+    fn test_extcodesize_precompile() {
+        // Identity precompile
+        let precompile_address = Address::from_low_u64_be(0x04);
+        let code = bytecode! {
+            PUSH20(precompile_address.to_word())
+            EXTCODESIZE
+            STOP
+        };
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            |accs| {
+                accs[0]
+                    .address(MOCK_ACCOUNTS[0])
+                    .balance(*MOCK_1_ETH)
+                    .code(code);
+                accs[1].address(MOCK_ACCOUNTS[1]).balance(*MOCK_1_ETH);
+            },
+            |mut txs, accs| {
+                txs[0].to(accs[0].address).from(accs[1].address);
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let transaction = &builder.block.txs()[0];
+        let call_id = transaction.calls()[0].call_id;
+        let indices = &transaction
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::EXTCODESIZE))
+            .unwrap()
+            .bus_mapping_instance;
+        let container = &builder.block.container;
+
+        // Precompiles are added to the access list in BeginTx, the access is
+        // still recorded as warming.
+        let operation = &container.tx_access_list_account[indices[4].as_usize()];
+        assert_eq!(operation.rw(), RW::WRITE);
+        assert_eq!(
+            operation.op(),
+            &TxAccessListAccountOp {
+                tx_id: 1,
+                address: precompile_address,
+                is_warm: true,
+                is_warm_prev: true
+            }
+        );
+
+        let operation = &container.stack[indices.last().unwrap().as_usize()];
+        assert_eq!(operation.rw(), RW::WRITE);
+        assert_eq!(
+            operation.op(),
+            &StackOp {
+                call_id,
+                address: 1023u32.into(),
+                value: Word::zero(),
+            }
+        );
+    }
+
     fn test_ok(account: &Account, is_warm: bool) {
         let exists = !account.is_empty();
 
@@ -32,7 +32,7 @@ impl Opcode for GasPrice {
 
         // Stack write of the gasprice value
         #[cfg(feature = "enable-stack")]
-        assert_eq!(gasprice, geth_steps[1].stack.last()?);
+        crate::util::check_geth_eq!(state, gasprice, geth_steps[1].stack.last()?);
         state.stack_push(&mut exec_step, gasprice)?;
 
         Ok(vec![exec_step])
@@ -41,7 +41,7 @@ impl Opcode for Jumpi {
 
         #[cfg(feature = "enable-stack")]
         for (i, v) in [pc, condition].into_iter().enumerate() {
-            assert_eq!(v, geth_step.stack.nth_last(i)?);
+            crate::util::check_geth_eq!(state, v, geth_step.stack.nth_last(i)?);
         }
 
         Ok(vec![exec_step])
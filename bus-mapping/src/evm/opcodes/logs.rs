@@ -24,8 +24,8 @@ impl Opcode for Log {
         let msize = state.stack_pop(&mut exec_step)?;
         #[cfg(feature = "enable-stack")]
         {
-            assert_eq!(mstart, geth_step.stack.nth_last(0)?);
-            assert_eq!(msize, geth_step.stack.nth_last(1)?);
+            crate::util::check_geth_eq!(state, mstart, geth_step.stack.nth_last(0)?);
+            crate::util::check_geth_eq!(state, msize, geth_step.stack.nth_last(1)?);
         }
 
         let call_id = state.call()?.call_id;
@@ -75,7 +75,7 @@ impl Opcode for Log {
         for i in 0..topic_count {
             let topic = state.stack_pop(&mut exec_step)?;
             #[cfg(feature = "enable-stack")]
-            assert_eq!(topic, geth_step.stack.nth_last(2 + i)?);
+            crate::util::check_geth_eq!(state, topic, geth_step.stack.nth_last(2 + i)?);
 
             if state.call()?.is_persistent {
                 state.tx_log_write(
@@ -146,7 +146,7 @@ fn gen_copy_event(
 #[cfg(test)]
 mod log_tests {
     use crate::{
-        circuit_input_builder::{CopyDataType, ExecState, NumberOrHash},
+        circuit_input_builder::{CopyDataType, ExecState, LogEntry, NumberOrHash},
         mock::BlockData,
         operation::{CallContextField, CallContextOp, StackOp, TxLogField, TxLogOp, RW},
     };
@@ -154,10 +154,13 @@ mod log_tests {
         bytecode,
         evm_types::{OpcodeId, StackAddress},
         geth_types::GethData,
-        Bytecode, ToWord, Word,
+        Bytecode, ToBigEndian, ToWord, Word, H256,
     };
 
-    use mock::test_ctx::{helpers::*, TestContext};
+    use mock::{
+        test_ctx::{helpers::*, TestContext},
+        MOCK_ACCOUNTS,
+    };
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -179,6 +182,54 @@ mod log_tests {
         ]);
     }
 
+    #[test]
+    fn logs_entries_of_block() {
+        let value = Word::from_big_endian(&(1..=32).collect::<Vec<u8>>());
+        let topics = [Word::from(0xA0), Word::from(0xef)];
+        let code = bytecode! {
+            PUSH32(value)
+            PUSH1(0x00)
+            MSTORE
+            PUSH32(topics[1])
+            PUSH32(topics[0])
+            PUSH1(0x14) // size
+            PUSH1(0x10) // offset
+            LOG2
+            STOP
+        };
+
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            account_0_code_account_1_no_code(code),
+            tx_from_1_to_0,
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        // memory[0x10..0x24] is the second half of `value` followed by zeros.
+        let mut data = value.to_be_bytes()[0x10..].to_vec();
+        data.extend_from_slice(&[0; 4]);
+        assert_eq!(
+            builder.logs(),
+            vec![LogEntry {
+                tx_id: 1,
+                log_id: 1,
+                address: MOCK_ACCOUNTS[0],
+                topics: topics
+                    .iter()
+                    .map(|topic| H256::from(topic.to_be_bytes()))
+                    .collect(),
+                data,
+            }]
+        );
+    }
+
     fn test_logs_opcode(topics: &[Word]) {
         let log_codes = [
             OpcodeId::LOG0,
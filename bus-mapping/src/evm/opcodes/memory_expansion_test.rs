@@ -1,4 +1,14 @@
-use eth_types::{bytecode, geth_types::GethData, word, Bytecode, GethExecStep};
+use crate::{
+    circuit_input_builder::{ExecState, GasBreakdown},
+    mock::BlockData,
+    operation::{Target, RW},
+};
+use eth_types::{
+    bytecode,
+    evm_types::{Memory, OpcodeId},
+    geth_types::GethData,
+    word, Bytecode, GethExecStep, ToBigEndian,
+};
 use mock::{
     test_ctx::{
         helpers::{account_0_code_account_1_no_code, tx_from_1_to_0},
@@ -130,3 +140,122 @@ fn call_test() {
     };
     trace_and_assert(code, -2, -1, assert_expanded);
 }
+
+#[test]
+fn unaligned_access_matches_geth_memory() {
+    let code = bytecode! {
+        // MSTORE spanning two words
+        PUSH32(word!("0x0102030405060708091011121314151617181920212223242526272829303132"))
+        PUSH1(0x21)
+        MSTORE
+        // MSTORE8 in the middle of a word
+        PUSH1(0xab)
+        PUSH1(0x45)
+        MSTORE8
+        // MLOAD expanding memory by two words
+        PUSH1(0x70)
+        MLOAD
+        POP
+        // byte granular copy straddling a word boundary
+        PUSH1(5) // size
+        PUSH1(3) // offset
+        PUSH1(0x9d) // destOffset
+        CODECOPY
+        STOP
+    };
+    let block: GethData = TestContext::<2, 1>::new_with_logger_config(
+        None,
+        account_0_code_account_1_no_code(code),
+        tx_from_1_to_0,
+        |block, _tx| block.number(0xcafeu64),
+        LoggerConfig::enable_memory(),
+    )
+    .unwrap()
+    .into();
+
+    let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+    builder
+        .handle_block(&block.eth_block, &block.geth_traces)
+        .unwrap();
+
+    // Replay the word sized memory operations of each step, and compare the
+    // result with the memory geth reports before the next step.
+    let mut memory = Memory::default();
+    let steps = builder.block.txs()[0].steps();
+    let geth_steps = &block.geth_traces[0].struct_logs;
+    for (step, geth_next) in steps[1..].iter().zip(geth_steps.iter().skip(1)) {
+        for op_ref in step
+            .bus_mapping_instance
+            .iter()
+            .filter(|op_ref| op_ref.target() == Target::Memory)
+        {
+            let operation = &builder.block.container.memory[op_ref.as_usize()];
+            let op = operation.op();
+            assert_eq!(op.address.0 % 32, 0);
+            memory.extend_at_least(op.address.0 + 32);
+            if operation.rw() == RW::WRITE {
+                assert_eq!(memory.read_word(op.address), op.value_prev);
+                memory.write_chunk(op.address, &op.value.to_be_bytes());
+            } else {
+                assert_eq!(memory.read_word(op.address), op.value);
+            }
+        }
+        assert_eq!(memory, geth_next.memory, "after {:?}", step.exec_state);
+    }
+    assert_eq!(memory.len(), 0xc0);
+}
+
+#[test]
+fn memory_expansion_gas_breakdown() {
+    let code = bytecode! {
+        // Expand from 0 to 3 words: 3 * 3 + 3 * 3 / 512
+        PUSH1(0x6fu64)
+        PUSH1(0x40u64)
+        MSTORE
+        // Expand from 3 to 4 words: 4 * 3 - 3 * 3, and copy 1 word
+        PUSH1(0x20u64) // size
+        PUSH1(0x00u64) // offset
+        PUSH1(0x60u64) // destOffset
+        CALLDATACOPY
+        STOP
+    };
+    let block: GethData = TestContext::<2, 1>::new(
+        None,
+        account_0_code_account_1_no_code(code),
+        tx_from_1_to_0,
+        |block, _tx| block.number(0xcafeu64),
+    )
+    .unwrap()
+    .into();
+    let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+    builder
+        .handle_block(&block.eth_block, &block.geth_traces)
+        .unwrap();
+
+    let breakdown_of = |op| {
+        let step = builder.block.txs()[0]
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(op))
+            .unwrap();
+        assert_eq!(step.gas_breakdown.total(), step.gas_cost.as_u64());
+        step.gas_breakdown
+    };
+    assert_eq!(
+        breakdown_of(OpcodeId::MSTORE),
+        GasBreakdown {
+            base: 3,
+            memory_expansion: 9,
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        breakdown_of(OpcodeId::CALLDATACOPY),
+        GasBreakdown {
+            base: 3,
+            memory_expansion: 3,
+            dynamic: 3,
+            ..Default::default()
+        }
+    );
+}
@@ -22,8 +22,8 @@ impl<const IS_MSTORE8: bool> Opcode for Mstore<IS_MSTORE8> {
         let value = state.stack_pop(&mut exec_step)?;
         #[cfg(feature = "enable-stack")]
         {
-            assert_eq!(offset, geth_step.stack.nth_last(0)?);
-            assert_eq!(value, geth_step.stack.nth_last(1)?);
+            crate::util::check_geth_eq!(state, offset, geth_step.stack.nth_last(0)?);
+            crate::util::check_geth_eq!(state, value, geth_step.stack.nth_last(1)?);
         }
 
         let offset_u64 = offset.as_u64() as usize;
@@ -84,7 +84,7 @@ mod mstore_tests {
         bytecode,
         evm_types::{MemoryAddress, OpcodeId, StackAddress},
         geth_types::GethData,
-        Word,
+        ToBigEndian, Word,
     };
     use itertools::Itertools;
     use mock::test_ctx::{helpers::*, LoggerConfig, TestContext};
@@ -170,6 +170,48 @@ mod mstore_tests {
         )
     }
 
+    #[test]
+    fn mstore_memory_snapshot() {
+        let value = Word::from_big_endian(&[0xab; 32]);
+        let code = bytecode! {
+            PUSH32(value)
+            PUSH1(0x20)
+            MSTORE
+            STOP
+        };
+
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            account_0_code_account_1_no_code(code),
+            tx_from_1_to_0,
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let tx = &builder.block.txs()[0];
+        let snapshot_at = |op: OpcodeId| {
+            let step = tx
+                .steps()
+                .iter()
+                .find(|step| step.exec_state == ExecState::Op(op))
+                .unwrap();
+            builder.block.memory_snapshot(tx, step)
+        };
+
+        // Memory is still empty when MSTORE starts.
+        assert_eq!(snapshot_at(OpcodeId::MSTORE), Vec::<u8>::new());
+
+        let mut expected = vec![0u8; 0x20];
+        expected.extend_from_slice(&value.to_be_bytes());
+        assert_eq!(snapshot_at(OpcodeId::STOP), expected);
+    }
+
     #[test]
     fn mstore8_opcode_impl() {
         let code = bytecode! {
@@ -16,6 +16,15 @@ use eth_types::{
 };
 use ethers_core::utils::keccak256;
 
+/// Placeholder structure used to implement [`Opcode`] trait over it
+/// corresponding to the `OpcodeId::RETURN` and `OpcodeId::REVERT` `OpcodeId`s.
+///
+/// REVERT is always treated as a valid opcode. The traces are generated with
+/// every hardfork (up to Cancun) enabled from genesis, and neither the
+/// builder nor the circuits model pre-Byzantium rules, so there is no fork
+/// under which REVERT could show up as an invalid opcode. Should geth report
+/// it as invalid anyway, the step goes through the generic invalid opcode path
+/// in `get_step_err`.
 #[derive(Debug, Copy, Clone)]
 pub(crate) struct ReturnRevert;
 
@@ -31,8 +40,8 @@ impl Opcode for ReturnRevert {
         let length = state.stack_pop(&mut exec_step)?;
         #[cfg(feature = "enable-stack")]
         {
-            assert_eq!(offset, step.stack.nth_last(0)?);
-            assert_eq!(length, step.stack.nth_last(1)?);
+            crate::util::check_geth_eq!(state, offset, step.stack.nth_last(0)?);
+            crate::util::check_geth_eq!(state, length, step.stack.nth_last(1)?);
         }
 
         if !length.is_zero() {
@@ -374,8 +383,14 @@ fn handle_create(
 
 #[cfg(test)]
 mod return_tests {
-    use crate::mock::BlockData;
-    use eth_types::{bytecode, geth_types::GethData, word};
+    use crate::{circuit_input_builder::ExecState, mock::BlockData, operation::RW};
+    use eth_types::{
+        address, bytecode,
+        evm_types::{GasCost, OpcodeId},
+        geth_types::GethData,
+        state_db::CodeDB,
+        word, ToBigEndian, ToWord, Word,
+    };
     use mock::{
         test_ctx::{
             helpers::{account_0_code_account_1_no_code, tx_from_1_to_0},
@@ -481,4 +496,307 @@ mod return_tests {
             .handle_block(&block.eth_block, &block.geth_traces)
             .unwrap();
     }
+
+    #[test]
+    fn test_revert_data_is_return_data() {
+        let reason = Word::from_big_endian(&[0xab; 32]);
+        let callee_address = address!("0x0000000000000000000000000000000000000020");
+        let caller_code = bytecode! {
+            PUSH1(0) // retLength
+            PUSH1(0) // retOffset
+            PUSH1(0) // argsLength
+            PUSH1(0) // argsOffset
+            PUSH1(0) // value
+            PUSH20(callee_address.to_word())
+            GAS
+            CALL
+            POP
+            RETURNDATASIZE
+            POP
+            PUSH1(0x20) // length
+            PUSH1(0) // dataOffset
+            PUSH1(0) // memOffset
+            RETURNDATACOPY
+            STOP
+        };
+        let callee_code = bytecode! {
+            PUSH32(reason)
+            PUSH1(0)
+            MSTORE
+            PUSH1(0x20)
+            PUSH1(0)
+            REVERT
+        };
+
+        let block: GethData = TestContext::<3, 1>::new(
+            None,
+            |accs| {
+                accs[0]
+                    .address(address!("0x0000000000000000000000000000000000000010"))
+                    .balance(Word::from(1u64 << 20))
+                    .code(caller_code);
+                accs[1].address(callee_address).code(callee_code);
+                accs[2]
+                    .address(address!("0x0000000000000000000000000000000000cafe01"))
+                    .balance(Word::from(1u64 << 30));
+            },
+            |mut txs, accs| {
+                txs[0].to(accs[0].address).from(accs[2].address);
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let tx = &builder.block.txs()[0];
+        assert!(!tx.calls()[1].is_success);
+
+        // The caller sees the revert data as its return data.
+        let returndatasize = tx
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::RETURNDATASIZE))
+            .unwrap();
+        let operation =
+            &builder.block.container.stack[returndatasize.bus_mapping_instance[1].as_usize()];
+        assert_eq!(operation.rw(), RW::WRITE);
+        assert_eq!(operation.op().value, Word::from(0x20));
+
+        // RETURNDATACOPY copies the revert reason into the caller's memory.
+        let stop = tx
+            .steps()
+            .iter()
+            .find(|step| step.call_index == 0 && step.exec_state == ExecState::Op(OpcodeId::STOP))
+            .unwrap();
+        assert_eq!(
+            builder.block.memory_snapshot(tx, stop),
+            reason.to_be_bytes().to_vec()
+        );
+    }
+
+    #[test]
+    fn test_return_data_in_call() {
+        let data = Word::from_big_endian(&[0xcd; 32]);
+        let callee_address = address!("0x0000000000000000000000000000000000000020");
+        let caller_code = bytecode! {
+            PUSH1(0) // retLength
+            PUSH1(0) // retOffset
+            PUSH1(0) // argsLength
+            PUSH1(0) // argsOffset
+            PUSH1(0) // value
+            PUSH20(callee_address.to_word())
+            GAS
+            CALL
+            POP
+            RETURNDATASIZE
+            POP
+            PUSH1(0x20) // length
+            PUSH1(0) // dataOffset
+            PUSH1(0) // memOffset
+            RETURNDATACOPY
+            STOP
+        };
+        let callee_code = bytecode! {
+            PUSH32(data)
+            PUSH1(0)
+            MSTORE
+            PUSH1(0x20)
+            PUSH1(0)
+            RETURN
+        };
+        let callee_code_hash = CodeDB::hash(&callee_code.code());
+
+        let block: GethData = TestContext::<3, 1>::new(
+            None,
+            |accs| {
+                accs[0]
+                    .address(address!("0x0000000000000000000000000000000000000010"))
+                    .balance(Word::from(1u64 << 20))
+                    .code(caller_code);
+                accs[1].address(callee_address).code(callee_code);
+                accs[2]
+                    .address(address!("0x0000000000000000000000000000000000cafe01"))
+                    .balance(Word::from(1u64 << 30));
+            },
+            |mut txs, accs| {
+                txs[0].to(accs[0].address).from(accs[2].address);
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let tx = &builder.block.txs()[0];
+        assert!(tx.calls()[1].is_success);
+
+        // The returned bytes become the caller's return data.
+        let returndatasize = tx
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::RETURNDATASIZE))
+            .unwrap();
+        let operation =
+            &builder.block.container.stack[returndatasize.bus_mapping_instance[1].as_usize()];
+        assert_eq!(operation.rw(), RW::WRITE);
+        assert_eq!(operation.op().value, Word::from(0x20));
+
+        let stop = tx
+            .steps()
+            .iter()
+            .find(|step| step.call_index == 0 && step.exec_state == ExecState::Op(OpcodeId::STOP))
+            .unwrap();
+        assert_eq!(
+            builder.block.memory_snapshot(tx, stop),
+            data.to_be_bytes().to_vec()
+        );
+
+        // No code is deployed outside of a creation.
+        assert_eq!(
+            builder.sdb.get_account(&callee_address).1.code_hash,
+            callee_code_hash
+        );
+    }
+
+    #[test]
+    fn test_empty_return_in_call() {
+        let callee_address = address!("0x0000000000000000000000000000000000000020");
+        let caller_code = bytecode! {
+            PUSH1(0x20) // retLength
+            PUSH1(0) // retOffset
+            PUSH1(0) // argsLength
+            PUSH1(0) // argsOffset
+            PUSH1(0) // value
+            PUSH20(callee_address.to_word())
+            GAS
+            CALL
+            RETURNDATASIZE
+            STOP
+        };
+        let callee_code = bytecode! {
+            PUSH1(0) // length
+            PUSH1(0) // offset
+            RETURN
+        };
+
+        let block: GethData = TestContext::<3, 1>::new(
+            None,
+            |accs| {
+                accs[0]
+                    .address(address!("0x0000000000000000000000000000000000000010"))
+                    .balance(Word::from(1u64 << 20))
+                    .code(caller_code);
+                accs[1].address(callee_address).code(callee_code);
+                accs[2]
+                    .address(address!("0x0000000000000000000000000000000000cafe01"))
+                    .balance(Word::from(1u64 << 30));
+            },
+            |mut txs, accs| {
+                txs[0].to(accs[0].address).from(accs[2].address);
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let tx = &builder.block.txs()[0];
+        assert!(tx.calls()[1].is_success);
+
+        // Nothing is copied back to the caller's memory.
+        let return_step = tx
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::RETURN))
+            .unwrap();
+        assert_eq!(return_step.memory_size, 0);
+        assert!(builder.block.copy_events.is_empty());
+
+        let returndatasize = tx
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::RETURNDATASIZE))
+            .unwrap();
+        let operation =
+            &builder.block.container.stack[returndatasize.bus_mapping_instance[1].as_usize()];
+        assert_eq!(operation.rw(), RW::WRITE);
+        assert_eq!(operation.op().value, Word::zero());
+    }
+
+    #[test]
+    fn test_return_deploys_code_in_create() {
+        // The constructor returns the 12 bytes 0x6020600060003760206000F3.
+        let deployed_code = word!("6020600060003760206000F3").to_be_bytes()[20..].to_vec();
+        let code = bytecode! {
+            PUSH21(word!("6B6020600060003760206000F3600052600C6014F3"))
+            PUSH1(0)
+            MSTORE
+
+            PUSH1 (0x15)
+            PUSH1 (0xB)
+            PUSH1 (0)
+            CREATE
+            STOP
+        };
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            account_0_code_account_1_no_code(code),
+            tx_from_1_to_0,
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let tx = &builder.block.txs()[0];
+        let callee = &tx.calls()[1];
+        assert!(callee.is_create() && callee.is_success);
+
+        // The returned bytes become the code of the created account.
+        let code_hash = builder.sdb.get_account(&callee.address).1.code_hash;
+        assert_eq!(code_hash, CodeDB::hash(&deployed_code));
+        assert_eq!(builder.code_db.0[&code_hash], deployed_code);
+
+        // The creation returns no data to the caller, and the code deposit
+        // is charged on top of the RETURN cost.
+        let steps = tx.steps();
+        let create = steps
+            .iter()
+            .position(|step| step.exec_state == ExecState::Op(OpcodeId::CREATE))
+            .unwrap();
+        let ret = steps
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::RETURN))
+            .unwrap();
+        let (create_step, callee_first_step) = (&steps[create], &steps[create + 1]);
+        let caller_next_step = steps
+            .iter()
+            .skip(create + 1)
+            .find(|step| step.call_index == 0)
+            .unwrap();
+        let code_deposit_cost =
+            deployed_code.len() as u64 * GasCost::CODE_DEPOSIT_BYTE_COST.as_u64();
+        assert_eq!(
+            caller_next_step.gas_left.0,
+            create_step.gas_left.0 - create_step.gas_cost.0 - callee_first_step.gas_left.0
+                + (ret.gas_left.0 - ret.gas_cost.0 - code_deposit_cost)
+        );
+    }
 }
@@ -24,9 +24,9 @@ impl Opcode for Returndatacopy {
 
         #[cfg(feature = "enable-stack")]
         {
-            assert_eq!(memory_offset, geth_step.stack.nth_last(0)?);
-            assert_eq!(data_offset, geth_step.stack.nth_last(1)?);
-            assert_eq!(length, geth_step.stack.nth_last(2)?);
+            crate::util::check_geth_eq!(state, memory_offset, geth_step.stack.nth_last(0)?);
+            crate::util::check_geth_eq!(state, data_offset, geth_step.stack.nth_last(1)?);
+            crate::util::check_geth_eq!(state, length, geth_step.stack.nth_last(2)?);
         }
 
         let call_id = state.call()?.call_id;
@@ -109,14 +109,19 @@ fn gen_copy_event(
 
 #[cfg(test)]
 mod return_tests {
-    use crate::mock::BlockData;
-    use eth_types::{bytecode, geth_types::GethData};
+    use crate::{
+        circuit_input_builder::{ExecState, NumberOrHash},
+        mock::BlockData,
+        operation::{Target, RW},
+    };
+    use eth_types::{bytecode, evm_types::OpcodeId, geth_types::GethData, Bytecode, ToWord, Word};
     use mock::{
+        eth,
         test_ctx::{
             helpers::{account_0_code_account_1_no_code, tx_from_1_to_0},
             LoggerConfig,
         },
-        TestContext, MOCK_DEPLOYED_CONTRACT_BYTECODE,
+        TestContext, MOCK_ACCOUNTS, MOCK_DEPLOYED_CONTRACT_BYTECODE,
     };
 
     #[test]
@@ -163,4 +168,98 @@ mod return_tests {
             .handle_block(&block.eth_block, &block.geth_traces)
             .unwrap();
     }
+
+    #[test]
+    fn test_return_data_of_last_callee() {
+        // A returns 32 bytes of 0xaa.
+        let code_a = bytecode! {
+            PUSH32(Word::MAX / 0xff * 0xaa)
+            PUSH1(0)
+            MSTORE
+            PUSH1(0x20)
+            PUSH1(0)
+            RETURN
+        };
+        // B returns 16 bytes of 0xbb.
+        let code_b = bytecode! {
+            PUSH16(Word::from(u128::MAX / 0xff * 0xbb))
+            PUSH1(0)
+            MSTORE
+            PUSH1(0x10)
+            PUSH1(0x10)
+            RETURN
+        };
+        let mut code = Bytecode::default();
+        for callee in [MOCK_ACCOUNTS[2], MOCK_ACCOUNTS[3]] {
+            code.append(&bytecode! {
+                PUSH1(0) // retSize
+                PUSH1(0) // retOffset
+                PUSH1(0) // argsSize
+                PUSH1(0) // argsOffset
+                PUSH1(0) // value
+                PUSH20(callee.to_word())
+                PUSH2(0xffff) // gas
+                CALL
+                POP
+            });
+        }
+        code.append(&bytecode! {
+            RETURNDATASIZE
+            POP
+            PUSH1(0x10) // size
+            PUSH1(0) // offset
+            PUSH1(0) // destOffset
+            RETURNDATACOPY
+            STOP
+        });
+        let block: GethData = TestContext::<4, 1>::new(
+            None,
+            |accs| {
+                accs[0].address(MOCK_ACCOUNTS[0]).code(code);
+                accs[1].address(MOCK_ACCOUNTS[1]).balance(eth(10));
+                accs[2].address(MOCK_ACCOUNTS[2]).code(code_a);
+                accs[3].address(MOCK_ACCOUNTS[3]).code(code_b);
+            },
+            |mut txs, accs| {
+                txs[0].from(accs[1].address).to(accs[0].address);
+            },
+            |block, _tx| block,
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let tx = &builder.block.txs()[0];
+        let step = tx
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::RETURNDATASIZE))
+            .unwrap();
+        let size = step
+            .bus_mapping_instance
+            .iter()
+            .filter(|op_ref| op_ref.target() == Target::Stack)
+            .map(|op_ref| &builder.block.container.stack[op_ref.as_usize()])
+            .find(|op| op.rw() == RW::WRITE)
+            .unwrap();
+        assert_eq!(size.op().value, Word::from(0x10));
+
+        // RETURNDATACOPY reads the memory of B, not of A.
+        let callee_b = &tx.calls()[2];
+        assert_eq!(callee_b.address, MOCK_ACCOUNTS[3]);
+        let copy_event = builder.block.copy_events.last().unwrap();
+        assert_eq!(copy_event.src_id, NumberOrHash::Number(callee_b.call_id));
+        let copied = copy_event
+            .copy_bytes
+            .bytes
+            .iter()
+            .filter(|(_, _, is_mask)| !*is_mask)
+            .map(|(value, _, _)| *value)
+            .collect::<Vec<_>>();
+        assert_eq!(copied, vec![0xbb; 0x10]);
+    }
 }
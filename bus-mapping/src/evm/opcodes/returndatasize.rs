@@ -37,7 +37,7 @@ impl Opcode for Returndatasize {
                     real_size,
                     geth_step
                 );
-                assert_eq!(real_size, size);
+                crate::util::check_geth_eq!(state, real_size, size);
             }
         }
         state.stack_push(&mut exec_step, size.into())?;
@@ -4,7 +4,7 @@ use crate::{
     operation::{CallContextField, StorageOp, TxAccessListAccountStorageOp, RW},
     Error,
 };
-use eth_types::{GethExecStep, ToWord, Word};
+use eth_types::{evm_types::GasCost, GethExecStep, ToWord, Word};
 
 /// Placeholder structure used to implement [`Opcode`] trait over it
 /// corresponding to the [`OpcodeId::SLOAD`](crate::evm::OpcodeId::SLOAD)
@@ -54,13 +54,14 @@ impl Opcode for Sload {
         // First stack read
         let key = state.stack_pop(&mut exec_step)?;
         #[cfg(feature = "enable-stack")]
-        assert_eq!(key, geth_step.stack.last()?);
+        crate::util::check_geth_eq!(state, key, geth_step.stack.last()?);
 
         // Storage read
         let value_from_statedb = *state.sdb.get_storage(&contract_addr, &key).1;
 
         #[cfg(feature = "enable-stack")]
-        assert_eq!(
+        crate::util::check_geth_eq!(
+            state,
             value_from_statedb,
             geth_steps[1].stack.last()?,
             "inconsistent sload: step proof {value_from_statedb:?}, result {:?} in contract {contract_addr:?}, key {key:?}", geth_steps[1].stack.last()?,
@@ -71,6 +72,12 @@ impl Opcode for Sload {
         let is_warm = state
             .sdb
             .check_account_storage_in_access_list(&(contract_addr, key));
+        // A cold slot costs COLD_SLOAD instead of WARM_ACCESS (EIP-2929).
+        exec_step.gas_breakdown.set_access_list(if is_warm {
+            0
+        } else {
+            GasCost::COLD_SLOAD.as_u64() - GasCost::WARM_ACCESS.as_u64()
+        });
 
         let (_, committed_value) = state.sdb.get_committed_storage(&contract_addr, &key);
         let committed_value = *committed_value;
@@ -121,13 +128,14 @@ mod sload_tests {
     use crate::{circuit_input_builder::ExecState, mock::BlockData, operation::StackOp};
     use eth_types::{
         bytecode,
-        evm_types::{OpcodeId, StackAddress},
+        evm_types::{GasCost, OpcodeId, StackAddress},
         geth_types::GethData,
-        Word,
+        AccessList, AccessListItem, Word, H256,
     };
     use mock::{
+        eth,
         test_ctx::{helpers::*, TestContext},
-        MOCK_ACCOUNTS,
+        MOCK_ACCOUNTS, MOCK_WALLETS,
     };
     use pretty_assertions::assert_eq;
 
@@ -238,6 +246,219 @@ mod sload_tests {
         )
     }
 
+    #[test]
+    fn sload_after_reverted_subcall() {
+        // The callee writes to the caller's storage (via DELEGATECALL) and then
+        // reverts, so the caller must still see the original value.
+        let callee_code = bytecode! {
+            PUSH1(0xffu64)
+            PUSH1(0x00u64)
+            SSTORE
+            PUSH1(0x00u64)
+            PUSH1(0x00u64)
+            REVERT
+        };
+        let caller_code = bytecode! {
+            PUSH1(0x00u64) // retLength
+            PUSH1(0x00u64) // retOffset
+            PUSH1(0x00u64) // argsLength
+            PUSH1(0x00u64) // argsOffset
+            PUSH20(MOCK_ACCOUNTS[1].to_word())
+            GAS
+            DELEGATECALL
+            POP
+            PUSH1(0x00u64)
+            SLOAD
+            STOP
+        };
+
+        let block: GethData = TestContext::<3, 1>::new(
+            None,
+            |accs| {
+                accs[0]
+                    .address(MOCK_ACCOUNTS[0])
+                    .code(caller_code)
+                    .storage(vec![(Word::zero(), Word::one())].into_iter());
+                accs[1].address(MOCK_ACCOUNTS[1]).code(callee_code);
+                accs[2]
+                    .address(MOCK_ACCOUNTS[2])
+                    .balance(Word::from(1u64 << 30));
+            },
+            |mut txs, accs| {
+                txs[0].to(accs[0].address).from(accs[2].address);
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let step = builder.block.txs()[0]
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::SLOAD))
+            .unwrap();
+        let storage_op = &builder.block.container.storage[step.bus_mapping_instance[5].as_usize()];
+        assert_eq!(storage_op.op().value, Word::one());
+        assert_eq!(
+            builder.sdb.get_storage(&MOCK_ACCOUNTS[0], &Word::zero()).1,
+            &Word::one()
+        );
+    }
+
+    #[test]
+    fn sload_in_chained_block() {
+        // Increments storage slot 0 on every call.
+        let code = bytecode! {
+            PUSH1(0x00u64)
+            SLOAD
+            PUSH1(0x01u64)
+            ADD
+            PUSH1(0x00u64)
+            SSTORE
+            STOP
+        };
+
+        let block1: GethData = TestContext::<2, 1>::new(
+            None,
+            |accs| {
+                accs[0].address(MOCK_ACCOUNTS[0]).code(code.clone());
+                accs[1]
+                    .address(MOCK_ACCOUNTS[1])
+                    .balance(Word::from(1u64 << 30));
+            },
+            tx_from_1_to_0,
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+        // The tracer needs the pre-state of block 2 to be the post-state of
+        // block 1.
+        let block2: GethData = TestContext::<2, 1>::new(
+            None,
+            |accs| {
+                accs[0]
+                    .address(MOCK_ACCOUNTS[0])
+                    .code(code)
+                    .storage(vec![(Word::zero(), Word::one())].into_iter());
+                accs[1]
+                    .address(MOCK_ACCOUNTS[1])
+                    .nonce(Word::one())
+                    .balance(Word::from(1u64 << 30));
+            },
+            |mut txs, accs| {
+                txs[0]
+                    .to(accs[0].address)
+                    .from(accs[1].address)
+                    .nonce(Word::one());
+            },
+            |block, _tx| block.number(0xcafeu64 + 1),
+        )
+        .unwrap()
+        .into();
+
+        let builder = BlockData::chain(vec![block1, block2]).unwrap();
+
+        let step = builder.block.txs()[1]
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::SLOAD))
+            .unwrap();
+        let storage_op = &builder.block.container.storage[step.bus_mapping_instance[5].as_usize()];
+        assert_eq!(
+            (storage_op.rw(), storage_op.op()),
+            (
+                RW::READ,
+                &StorageOp::new(
+                    MOCK_ACCOUNTS[0],
+                    Word::zero(),
+                    Word::one(),
+                    Word::one(),
+                    2,
+                    Word::one(),
+                )
+            )
+        );
+        assert_eq!(
+            builder.sdb.get_storage(&MOCK_ACCOUNTS[0], &Word::zero()).1,
+            &Word::from(2u64)
+        );
+    }
+
+    #[test]
+    fn sload_gas_follows_slot_warmth() {
+        let code = bytecode! {
+            PUSH1(0x00u64)
+            SLOAD
+            POP
+            PUSH1(0x00u64)
+            SLOAD
+            STOP
+        };
+
+        let block: GethData = TestContext::<2, 2>::new(
+            None,
+            |accs| {
+                accs[0].address(MOCK_ACCOUNTS[0]).code(code);
+                accs[1].address(MOCK_WALLETS[0].address()).balance(eth(1));
+            },
+            |mut txs, accs| {
+                txs[0].from(MOCK_WALLETS[0].clone()).to(accs[0].address);
+                // The second tx warms slot 0 through its access list.
+                txs[1]
+                    .from(MOCK_WALLETS[0].clone())
+                    .to(accs[0].address)
+                    .transaction_type(1)
+                    .access_list(AccessList(vec![AccessListItem {
+                        address: MOCK_ACCOUNTS[0],
+                        storage_keys: vec![H256::zero()],
+                    }]));
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let sloads = |tx_index: usize| {
+            builder.block.txs()[tx_index]
+                .steps()
+                .iter()
+                .filter(|step| step.exec_state == ExecState::Op(OpcodeId::SLOAD))
+                .map(|step| {
+                    let warm_op = builder.block.container.tx_access_list_account_storage
+                        [step.bus_mapping_instance[8].as_usize()]
+                    .op();
+                    (step.gas_cost, warm_op.is_warm_prev, warm_op.is_warm)
+                })
+                .collect::<Vec<_>>()
+        };
+        // The slot is cold on its first access and warm afterwards.
+        assert_eq!(
+            sloads(0),
+            vec![
+                (GasCost::COLD_SLOAD, false, true),
+                (GasCost::WARM_ACCESS, true, true)
+            ]
+        );
+        // The access list already warmed the slot, and warmth is per tx.
+        assert_eq!(
+            sloads(1),
+            vec![
+                (GasCost::WARM_ACCESS, true, true),
+                (GasCost::WARM_ACCESS, true, true)
+            ]
+        );
+    }
+
     #[test]
     fn sload_opcode_impl_warm() {
         test_ok(true)
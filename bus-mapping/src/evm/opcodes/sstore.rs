@@ -118,8 +118,8 @@ impl Opcode for Sstore {
         let value = state.stack_pop(&mut exec_step)?;
         #[cfg(feature = "enable-stack")]
         {
-            assert_eq!(key, geth_step.stack.nth_last(0)?);
-            assert_eq!(value, geth_step.stack.nth_last(1)?);
+            crate::util::check_geth_eq!(state, key, geth_step.stack.nth_last(0)?);
+            crate::util::check_geth_eq!(state, value, geth_step.stack.nth_last(1)?);
         }
 
         let is_warm = state
@@ -188,6 +188,14 @@ impl Opcode for Sstore {
             refund
         };
 
+        // A cold slot costs COLD_SLOAD on top of the warm cost (EIP-2929).
+        exec_step.gas_breakdown.set_access_list(if is_warm {
+            0
+        } else {
+            GasCost::COLD_SLOAD.as_u64()
+        });
+        exec_step.gas_breakdown.refund_delta = refund as i64 - state.sdb.refund() as i64;
+
         state.push_op_reversible(
             &mut exec_step,
             TxRefundOp {
@@ -365,4 +373,157 @@ mod sstore_tests {
     fn sstore_opcode_impl_cold() {
         test_ok(false)
     }
+
+    #[test]
+    fn sstore_cold_slot_surcharge() {
+        let code = bytecode! {
+            // Set the cold slot 0
+            PUSH1(0x01u64)
+            PUSH1(0x00u64)
+            SSTORE
+            // Warm slot 1 with SLOAD, then set it
+            PUSH1(0x01u64)
+            SLOAD
+            POP
+            PUSH1(0x01u64)
+            PUSH1(0x01u64)
+            SSTORE
+            STOP
+        };
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            |accs| {
+                accs[0]
+                    .address(MOCK_ACCOUNTS[0])
+                    .balance(Word::from(10u64.pow(19)))
+                    .code(code);
+                accs[1]
+                    .address(MOCK_ACCOUNTS[1])
+                    .balance(Word::from(10u64.pow(19)));
+            },
+            tx_from_1_to_0,
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let sstore_steps = builder.block.txs()[0]
+            .steps()
+            .iter()
+            .filter(|step| step.exec_state == ExecState::Op(OpcodeId::SSTORE))
+            .collect::<Vec<_>>();
+        let (cold, warm) = (sstore_steps[0], sstore_steps[1]);
+
+        // Both set a zero slot, the cold one pays COLD_SLOAD on top.
+        assert_eq!(warm.gas_cost, GasCost::SSTORE_SET);
+        assert_eq!(
+            cold.gas_cost.as_u64(),
+            GasCost::SSTORE_SET.as_u64() + GasCost::COLD_SLOAD.as_u64()
+        );
+
+        // SSTORE reads the warmth of the slot, then warms it.
+        for (step, key, is_warm_prev) in [(cold, 0u64, false), (warm, 1, true)] {
+            let [read, write] = [8, 9].map(|idx| {
+                &builder.block.container.tx_access_list_account_storage
+                    [step.bus_mapping_instance[idx].as_usize()]
+            });
+            assert_eq!(
+                (read.rw(), read.op()),
+                (
+                    RW::READ,
+                    &TxAccessListAccountStorageOp {
+                        tx_id: 1,
+                        address: MOCK_ACCOUNTS[0],
+                        key: Word::from(key),
+                        is_warm: is_warm_prev,
+                        is_warm_prev,
+                    }
+                )
+            );
+            assert_eq!(
+                (write.rw(), write.op()),
+                (
+                    RW::WRITE,
+                    &TxAccessListAccountStorageOp {
+                        tx_id: 1,
+                        address: MOCK_ACCOUNTS[0],
+                        key: Word::from(key),
+                        is_warm: true,
+                        is_warm_prev,
+                    }
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn sstore_gas_breakdown() {
+        use crate::circuit_input_builder::GasBreakdown;
+
+        let code = bytecode! {
+            // Set the empty cold slot 0
+            PUSH1(0x6fu64)
+            PUSH1(0x00u64)
+            SSTORE
+            // Clear it again, now warm
+            PUSH1(0x00u64)
+            PUSH1(0x00u64)
+            SSTORE
+            STOP
+        };
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            |accs| {
+                accs[0]
+                    .address(MOCK_ACCOUNTS[0])
+                    .balance(Word::from(10u64.pow(19)))
+                    .code(code);
+                accs[1]
+                    .address(MOCK_ACCOUNTS[1])
+                    .balance(Word::from(10u64.pow(19)));
+            },
+            tx_from_1_to_0,
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let breakdowns = builder.block.txs()[0]
+            .steps()
+            .iter()
+            .filter(|step| step.exec_state == ExecState::Op(OpcodeId::SSTORE))
+            .map(|step| {
+                assert_eq!(step.gas_breakdown.total(), step.gas_cost.as_u64());
+                step.gas_breakdown
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            breakdowns,
+            vec![
+                GasBreakdown {
+                    dynamic: GasCost::SSTORE_SET.as_u64(),
+                    access_list: GasCost::COLD_SLOAD.as_u64(),
+                    ..Default::default()
+                },
+                // Restoring the original value refunds most of the set cost.
+                GasBreakdown {
+                    dynamic: GasCost::WARM_ACCESS.as_u64(),
+                    refund_delta: (GasCost::SSTORE_SET.as_u64() - GasCost::WARM_ACCESS.as_u64())
+                        as i64,
+                    ..Default::default()
+                },
+            ]
+        );
+    }
 }
@@ -25,7 +25,7 @@ impl<const N_POP: usize, const IS_ERR: bool> Opcode for StackPopOnlyOpcode<N_POP
         let _stack_inputs = state.stack_pops(&mut exec_step, N_POP)?;
         #[cfg(feature = "enable-stack")]
         for (i, v) in _stack_inputs.into_iter().enumerate() {
-            assert_eq!(v, geth_step.stack.nth_last(i)?);
+            crate::util::check_geth_eq!(state, v, geth_step.stack.nth_last(i)?);
         }
 
         if IS_ERR {
@@ -38,3 +38,76 @@ impl<const N_POP: usize, const IS_ERR: bool> Opcode for StackPopOnlyOpcode<N_POP
         Ok(vec![exec_step])
     }
 }
+
+#[cfg(test)]
+mod stackonlyop_tests {
+    use crate::{
+        circuit_input_builder::ExecState,
+        error::ExecError,
+        mock::BlockData,
+        operation::{StackOp, RW},
+    };
+    use eth_types::{
+        bytecode,
+        evm_types::{GasCost, OpcodeId, StackAddress},
+        geth_types::GethData,
+        Bytecode, Word,
+    };
+    use mock::TestContext;
+    use pretty_assertions::assert_eq;
+
+    fn build(code: Bytecode) -> crate::circuit_input_builder::CircuitInputBuilder {
+        let block: GethData = TestContext::<2, 1>::simple_ctx_with_bytecode(code)
+            .unwrap()
+            .into();
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+        builder
+    }
+
+    #[test]
+    fn pop_reads_top_of_stack() {
+        let builder = build(bytecode! {
+            PUSH1(0x42)
+            POP
+            STOP
+        });
+
+        let steps = builder.block.txs()[0].steps();
+        let index = steps
+            .iter()
+            .position(|step| step.exec_state == ExecState::Op(OpcodeId::POP))
+            .unwrap();
+        let pop = &steps[index];
+        assert_eq!(pop.gas_cost, GasCost::QUICK);
+        assert_eq!(pop.bus_mapping_instance.len(), 1);
+        let operation = &builder.block.container.stack[pop.bus_mapping_instance[0].as_usize()];
+        assert_eq!(
+            (operation.rw(), operation.op()),
+            (
+                RW::READ,
+                &StackOp::new(1, StackAddress(1023), Word::from(0x42))
+            )
+        );
+        // The stack pointer goes back up once the item is popped.
+        assert_eq!(pop.stack_size, 1);
+        assert_eq!(steps[index + 1].stack_size, 0);
+    }
+
+    #[test]
+    fn pop_on_empty_stack_underflows() {
+        let builder = build(bytecode! {
+            POP
+            STOP
+        });
+
+        let pop = builder.block.txs()[0]
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::POP))
+            .unwrap();
+        assert_eq!(pop.error, Some(ExecError::StackUnderflow));
+    }
+}
@@ -44,3 +44,162 @@ impl Opcode for Stop {
         Ok(vec![exec_step])
     }
 }
+
+#[cfg(test)]
+mod stop_tests {
+    use crate::{
+        circuit_input_builder::ExecState,
+        mock::BlockData,
+        operation::{CallContextField, CallContextOp, StackOp, Target, RW},
+    };
+    use eth_types::{
+        address, bytecode,
+        evm_types::{OpcodeId, StackAddress},
+        geth_types::GethData,
+        ToWord, Word,
+    };
+    use mock::TestContext;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn stop_in_root_call_ends_tx() {
+        let code = bytecode! {
+            PUSH1(0)
+            STOP
+        };
+        let block: GethData = TestContext::<2, 1>::simple_ctx_with_bytecode(code)
+            .unwrap()
+            .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let tx = &builder.block.txs()[0];
+        assert_eq!(tx.calls().len(), 1);
+        assert!(tx.calls()[0].is_root);
+        assert!(tx.calls()[0].is_success);
+
+        let stop_index = tx
+            .steps()
+            .iter()
+            .position(|step| step.exec_state == ExecState::Op(OpcodeId::STOP))
+            .unwrap();
+        assert_eq!(tx.steps()[stop_index + 1].exec_state, ExecState::EndTx);
+    }
+
+    #[test]
+    fn stop_in_internal_call_restores_caller() {
+        let callee_address = address!("0x0000000000000000000000000000000000000020");
+        let caller_code = bytecode! {
+            PUSH1(0) // retLength
+            PUSH1(0) // retOffset
+            PUSH1(0) // argsLength
+            PUSH1(0) // argsOffset
+            PUSH1(0) // value
+            PUSH20(callee_address.to_word())
+            GAS
+            CALL
+            RETURNDATASIZE
+            STOP
+        };
+        let callee_code = bytecode! {
+            PUSH1(0)
+            STOP
+        };
+
+        let block: GethData = TestContext::<3, 1>::new(
+            None,
+            |accs| {
+                accs[0]
+                    .address(address!("0x0000000000000000000000000000000000000010"))
+                    .balance(Word::from(1u64 << 20))
+                    .code(caller_code);
+                accs[1]
+                    .address(callee_address)
+                    .balance(Word::from(1u64 << 20))
+                    .code(callee_code);
+                accs[2]
+                    .address(address!("0x0000000000000000000000000000000000cafe01"))
+                    .balance(Word::from(1u64 << 30));
+            },
+            |mut txs, accs| {
+                txs[0].to(accs[0].address).from(accs[2].address);
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let tx = &builder.block.txs()[0];
+        assert_eq!(tx.calls().len(), 2);
+        let caller_id = tx.calls()[0].call_id;
+        let callee = &tx.calls()[1];
+        assert!(!callee.is_root);
+        assert!(callee.is_success);
+
+        // The STOP of the callee restores the caller context with an empty
+        // return data.
+        let callee_stop = tx
+            .steps()
+            .iter()
+            .find(|step| step.call_index == 1 && step.exec_state == ExecState::Op(OpcodeId::STOP))
+            .unwrap();
+        let call_context_ops = callee_stop
+            .bus_mapping_instance
+            .iter()
+            .filter(|op_ref| op_ref.target() == Target::CallContext)
+            .map(|op_ref| {
+                let operation = &builder.block.container.call_context[op_ref.as_usize()];
+                (operation.rw(), operation.op().clone())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            call_context_ops.first().unwrap(),
+            &(
+                RW::READ,
+                CallContextOp {
+                    call_id: callee.call_id,
+                    field: CallContextField::IsSuccess,
+                    value: Word::one(),
+                }
+            )
+        );
+        assert_eq!(
+            call_context_ops.last().unwrap(),
+            &(
+                RW::WRITE,
+                CallContextOp {
+                    call_id: caller_id,
+                    field: CallContextField::LastCalleeReturnDataLength,
+                    value: Word::zero(),
+                }
+            )
+        );
+
+        // Back in the caller, RETURNDATASIZE observes zero return data.
+        let returndatasize = tx
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::RETURNDATASIZE))
+            .unwrap();
+        assert_eq!(returndatasize.call_index, 0);
+        assert_eq!(
+            {
+                let operation = &builder.block.container.stack
+                    [returndatasize.bus_mapping_instance[1].as_usize()];
+                (operation.rw(), operation.op())
+            },
+            (
+                RW::WRITE,
+                &StackOp::new(caller_id, StackAddress::from(1022), Word::zero())
+            )
+        );
+    }
+}
@@ -29,11 +29,15 @@ impl<const N: usize> Opcode for Swap<N> {
 
         #[cfg(feature = "enable-stack")]
         {
-            assert_eq!(stack_b_value_read, geth_step.stack.nth_last(N)?);
-            assert_eq!(stack_b_position, geth_step.stack.nth_last_filled(N));
+            crate::util::check_geth_eq!(state, stack_b_value_read, geth_step.stack.nth_last(N)?);
+            crate::util::check_geth_eq!(
+                state,
+                stack_b_position,
+                geth_step.stack.nth_last_filled(N)
+            );
 
-            assert_eq!(stack_a_value_read, geth_step.stack.last()?);
-            assert_eq!(stack_a_position, geth_step.stack.last_filled());
+            crate::util::check_geth_eq!(state, stack_a_value_read, geth_step.stack.last()?);
+            crate::util::check_geth_eq!(state, stack_a_position, geth_step.stack.last_filled());
         }
 
         // Write a into b_position, write b into a_position
@@ -48,6 +52,7 @@ impl<const N: usize> Opcode for Swap<N> {
 #[cfg(test)]
 mod swap_tests {
     use crate::{
+        circuit_input_builder::ExecState,
         mock::BlockData,
         operation::{StackOp, RW},
     };
@@ -113,4 +118,110 @@ mod swap_tests {
             );
         }
     }
+
+    #[test]
+    fn swap3_leaves_other_positions_untouched() {
+        let code = bytecode! {
+            PUSH1(0x1)
+            PUSH1(0x2)
+            PUSH1(0x3)
+            PUSH1(0x4)
+            PUSH1(0x5)
+            PUSH1(0x6) // [1,2,3,4,5,6]
+            SWAP3      // [1,2,6,4,5,3]
+            // Each DUP6 copies the next position, from the bottom up.
+            DUP6
+            DUP6
+            DUP6
+            DUP6
+            DUP6
+            DUP6
+            STOP
+        };
+        let block: GethData = TestContext::<2, 1>::simple_ctx_with_bytecode(code)
+            .unwrap()
+            .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let steps = builder.block.txs()[0].steps();
+        let swap = steps.iter().find(|step| step.exec_state.is_swap()).unwrap();
+        // Only the top and the fourth item are read and written.
+        assert_eq!(swap.bus_mapping_instance.len(), 4);
+        let positions = swap
+            .bus_mapping_instance
+            .iter()
+            .map(|op_ref| {
+                builder.block.container.stack[op_ref.as_usize()]
+                    .op()
+                    .address()
+            })
+            .copied()
+            .sorted()
+            .dedup()
+            .collect_vec();
+        assert_eq!(
+            positions,
+            vec![StackAddress(1024 - 6), StackAddress(1024 - 3)]
+        );
+
+        let dup_values = steps
+            .iter()
+            .filter(|step| step.exec_state.is_dup())
+            .map(|step| {
+                builder.block.container.stack[step.bus_mapping_instance[0].as_usize()]
+                    .op()
+                    .value()
+                    .to_owned()
+            })
+            .collect_vec();
+        assert_eq!(dup_values, [1, 2, 6, 4, 5, 3].map(Word::from).to_vec());
+    }
+
+    #[test]
+    fn stack_snapshot_after_pushes_and_swaps() {
+        let code = bytecode! {
+            PUSH1(0x1)
+            PUSH1(0x2)
+            PUSH1(0x3) // [1,2,3]
+            SWAP2      // [3,2,1]
+            PUSH1(0x4) // [3,2,1,4]
+            SWAP1      // [3,2,4,1]
+            STOP
+        };
+        let block: GethData = TestContext::<2, 1>::simple_ctx_with_bytecode(code)
+            .unwrap()
+            .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let tx = &builder.block.txs()[0];
+        let snapshots = tx
+            .steps()
+            .iter()
+            .filter(|step| matches!(step.exec_state, ExecState::Op(_)))
+            .map(|step| step.stack_snapshot(tx, &builder.block.container))
+            .collect_vec();
+        let expected: [&[u64]; 7] = [
+            &[],           // PUSH1
+            &[1],          // PUSH1
+            &[1, 2],       // PUSH1
+            &[1, 2, 3],    // SWAP2
+            &[3, 2, 1],    // PUSH1
+            &[3, 2, 1, 4], // SWAP1
+            &[3, 2, 4, 1], // STOP
+        ];
+        assert_eq!(
+            snapshots,
+            expected
+                .map(|stack| stack.iter().copied().map(Word::from).collect_vec())
+                .to_vec()
+        );
+    }
 }
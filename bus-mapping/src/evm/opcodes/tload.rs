@@ -40,12 +40,13 @@ impl Opcode for Tload {
         // First stack read
         let key = state.stack_pop(&mut exec_step)?;
         #[cfg(feature = "enable-stack")]
-        assert_eq!(key, geth_step.stack.last()?);
+        crate::util::check_geth_eq!(state, key, geth_step.stack.last()?);
 
         // Transient Storage read
         let (_, &value) = state.sdb.get_transient_storage(&contract_addr, &key);
         #[cfg(feature = "enable-stack")]
-        assert_eq!(
+        crate::util::check_geth_eq!(
+            state,
             value,
             geth_steps[1].stack.last()?,
             "inconsistent tload: step proof {value:?}, result {:?} in contract {contract_addr:?}, key {key:?}", geth_steps[1].stack.last()?,
@@ -65,8 +65,8 @@ impl Opcode for Tstore {
         let value = state.stack_pop(&mut exec_step)?;
         #[cfg(feature = "enable-stack")]
         {
-            assert_eq!(key, geth_step.stack.nth_last(0)?);
-            assert_eq!(value, geth_step.stack.nth_last(1)?);
+            crate::util::check_geth_eq!(state, key, geth_step.stack.nth_last(0)?);
+            crate::util::check_geth_eq!(state, value, geth_step.stack.nth_last(1)?);
         }
 
         // state.stack_read(&mut exec_step, key_stack_position, key)?;
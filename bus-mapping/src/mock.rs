@@ -1,7 +1,10 @@
 //! Mock types and functions to generate mock data useful for tests
 
-use crate::circuit_input_builder::{
-    AccessSet, Block, BlockHead, CircuitInputBuilder, CircuitsParams,
+pub mod foundry;
+
+use crate::{
+    circuit_input_builder::{AccessSet, Block, BlockHead, CircuitInputBuilder, CircuitsParams},
+    Error,
 };
 use eth_types::{
     geth_types::GethData,
@@ -57,33 +60,7 @@ impl BlockData {
     ) -> Self {
         let mut sdb = StateDB::new();
         let mut code_db = CodeDB::new();
-
-        let access_set = AccessSet::from_geth_data(&geth_data);
-        // Initialize all accesses accounts to zero
-        for addr in access_set.state.keys() {
-            sdb.set_account(addr, state_db::Account::zero());
-        }
-
-        for account in geth_data.accounts {
-            let keccak_code_hash = H256(keccak256(&account.code));
-            log::trace!(
-                "trace code {:?} {:?}",
-                keccak_code_hash,
-                hex::encode(&account.code)
-            );
-            let code_hash = code_db.insert(account.code.to_vec());
-            sdb.set_account(
-                &account.address,
-                state_db::Account {
-                    nonce: account.nonce,
-                    balance: account.balance,
-                    storage: account.storage,
-                    code_hash,
-                    keccak_code_hash,
-                    code_size: account.code.len().to_word(),
-                },
-            );
-        }
+        load_state(&geth_data, &mut sdb, &mut code_db);
 
         Self {
             sdb,
@@ -100,6 +77,78 @@ impl BlockData {
     pub fn new_from_geth_data(geth_data: GethData) -> Self {
         Self::new_from_geth_data_with_params(geth_data, CircuitsParams::default())
     }
+
+    /// Generate a CircuitInputBuilder that handles consecutive `blocks` in
+    /// order, where each block starts from the post-state of the previous
+    /// one. Only accounts not yet known from earlier blocks are initialized
+    /// from the pre-state of a later block.
+    pub fn chain(blocks: Vec<GethData>) -> Result<CircuitInputBuilder, Error> {
+        let mut builder = CircuitInputBuilder::new_from_headers(
+            CircuitsParams::default(),
+            StateDB::new(),
+            CodeDB::new(),
+            &[],
+        );
+        // FIXME: better fetch a real state root instead of a mock one
+        builder.block.prev_state_root = MOCK_OLD_STATE_ROOT.into();
+        if let Some(geth_data) = blocks.first() {
+            builder.block.chain_id = geth_data.chain_id;
+        }
+
+        let num_blocks = blocks.len();
+        for (idx, geth_data) in blocks.into_iter().enumerate() {
+            load_state(&geth_data, &mut builder.sdb, &mut builder.code_db);
+            let header = BlockHead::new(
+                geth_data.chain_id,
+                geth_data.history_hashes,
+                &geth_data.eth_block,
+            )?;
+            builder.block.headers.insert(header.number.as_u64(), header);
+            let is_last = idx + 1 == num_blocks;
+            builder.handle_block_inner(
+                &geth_data.eth_block,
+                &geth_data.geth_traces,
+                is_last,
+                is_last,
+            )?;
+        }
+        Ok(builder)
+    }
+}
+
+/// Initialize the accounts of `geth_data` which are not in `sdb` yet.
+fn load_state(geth_data: &GethData, sdb: &mut StateDB, code_db: &mut CodeDB) {
+    for account in &geth_data.accounts {
+        if sdb.get_account(&account.address).0 {
+            continue;
+        }
+        let keccak_code_hash = H256(keccak256(&account.code));
+        log::trace!(
+            "trace code {:?} {:?}",
+            keccak_code_hash,
+            hex::encode(&account.code)
+        );
+        let code_hash = code_db.insert(account.code.to_vec());
+        sdb.set_account(
+            &account.address,
+            state_db::Account {
+                nonce: account.nonce,
+                balance: account.balance,
+                storage: account.storage.clone(),
+                code_hash,
+                keccak_code_hash,
+                code_size: account.code.len().to_word(),
+            },
+        );
+    }
+
+    let access_set = AccessSet::from_geth_data(geth_data);
+    // Initialize all other accessed accounts to zero
+    for addr in access_set.state.keys() {
+        if !sdb.get_account(addr).0 {
+            sdb.set_account(addr, state_db::Account::zero());
+        }
+    }
 }
 
 #[cfg(test)]
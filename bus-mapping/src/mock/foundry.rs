@@ -0,0 +1,219 @@
+//! Adapter from the trace artifacts of Foundry (anvil) or Hardhat to
+//! [`GethData`].
+//!
+//! The artifact is a JSON object describing a single transaction:
+//!
+//! ```json
+//! {
+//!   "chainId": 31337,
+//!   "env": { "number", "timestamp", "coinbase", "gasLimit", "baseFee", "difficulty" },
+//!   "transaction": { ... },
+//!   "preState": { "<address>": { "balance", "nonce", "code", "storage" } },
+//!   "trace": { "gas", "failed", "returnValue", "structLogs": [ ... ] },
+//!   "callTrace": { ... }
+//! }
+//! ```
+//!
+//! - `transaction` is the transaction as returned by `eth_getTransactionByHash`.
+//! - `preState` is the result of `debug_traceTransaction` with the `prestateTracer`, and must
+//!   include the coinbase.
+//! - `trace` is the result of `debug_traceTransaction` with the default struct logger, with the
+//!   stack and memory enabled.
+//! - `callTrace` is the result of `debug_traceTransaction` with the `callTracer`. It can be omitted
+//!   when the transaction doesn't make any inner call, in which case it's derived from `trace`.
+//!
+//! Unsupported: blocks with several transactions, BLOCKHASH (the history hashes are left
+//! empty), the L1 fee of scroll traces (taken as 0), and the Foundry debugger's own
+//! (non struct logger) trace format.
+
+use super::BlockData;
+use crate::Error;
+use eth_types::{
+    geth_types::{Account, GethData},
+    Address, Block, GethExecTrace, GethPrestateTrace, Transaction, Word, H256, U64,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FoundryTrace {
+    chain_id: u64,
+    env: FoundryEnv,
+    transaction: Transaction,
+    pre_state: HashMap<Address, GethPrestateTrace>,
+    trace: Value,
+    call_trace: Option<Value>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FoundryEnv {
+    number: U64,
+    timestamp: Word,
+    coinbase: Address,
+    gas_limit: Word,
+    #[serde(default)]
+    base_fee: Option<Word>,
+    #[serde(default)]
+    difficulty: Word,
+}
+
+impl BlockData {
+    /// Create a new block from a Foundry or Hardhat trace artifact, see
+    /// [`foundry_trace_to_geth_data`].
+    pub fn from_foundry_trace(json: &str) -> Result<Self, Error> {
+        Ok(Self::new_from_geth_data(foundry_trace_to_geth_data(json)?))
+    }
+}
+
+/// Convert a Foundry or Hardhat trace artifact of a single transaction into
+/// [`GethData`]. See the [module](self) documentation for the expected schema.
+pub fn foundry_trace_to_geth_data(json: &str) -> Result<GethData, Error> {
+    let FoundryTrace {
+        chain_id,
+        env,
+        mut transaction,
+        pre_state,
+        mut trace,
+        call_trace,
+    } = serde_json::from_str(json).map_err(Error::SerdeError)?;
+
+    transaction.block_number = Some(env.number);
+    transaction.transaction_index = Some(U64::zero());
+    transaction.block_hash = Some(H256::zero());
+
+    let call_trace = match call_trace {
+        Some(call_trace) => call_trace,
+        None => root_call_trace(&transaction, &trace)?,
+    };
+    let trace_fields = trace
+        .as_object_mut()
+        .ok_or(Error::InvalidGethExecTrace("trace is not an object"))?;
+    trace_fields.insert(
+        "prestate".to_string(),
+        serde_json::to_value(&pre_state).map_err(Error::SerdeError)?,
+    );
+    trace_fields.insert("callTrace".to_string(), call_trace);
+    let geth_trace: GethExecTrace = serde_json::from_value(trace).map_err(Error::SerdeError)?;
+
+    let accounts = pre_state
+        .into_iter()
+        .map(|(address, state)| Account {
+            address,
+            nonce: state.nonce.unwrap_or_default().into(),
+            balance: state.balance.unwrap_or_default(),
+            code: state.code.unwrap_or_default(),
+            storage: state.storage.unwrap_or_default(),
+        })
+        .collect();
+
+    let eth_block = Block {
+        number: Some(env.number),
+        timestamp: env.timestamp,
+        author: Some(env.coinbase),
+        gas_limit: env.gas_limit,
+        base_fee_per_gas: env.base_fee,
+        difficulty: env.difficulty,
+        transactions: vec![transaction],
+        ..Default::default()
+    };
+
+    Ok(GethData {
+        chain_id,
+        history_hashes: vec![],
+        eth_block,
+        geth_traces: vec![geth_trace],
+        accounts,
+        #[cfg(feature = "scroll")]
+        block_trace: Default::default(),
+    })
+}
+
+/// Build the call trace of a transaction without inner calls from its struct
+/// logs.
+fn root_call_trace(tx: &Transaction, trace: &Value) -> Result<Value, Error> {
+    let struct_logs = trace["structLogs"]
+        .as_array()
+        .ok_or(Error::InvalidGethExecTrace("missing structLogs"))?;
+    if struct_logs
+        .iter()
+        .any(|step| step["depth"].as_u64() != Some(1))
+    {
+        return Err(Error::InvalidGethExecTrace(
+            "a callTrace is required for a transaction with inner calls",
+        ));
+    }
+
+    let error = if trace["failed"].as_bool().unwrap_or_default() {
+        // The error of the last step, or a revert if it ended on REVERT.
+        struct_logs
+            .last()
+            .and_then(|step| step["error"].as_str())
+            .unwrap_or("execution reverted")
+            .into()
+    } else {
+        Value::Null
+    };
+
+    Ok(json!({
+        "from": tx.from,
+        "to": tx.to,
+        "gasUsed": Word::from(trace["gas"].as_u64().unwrap_or_default()),
+        "error": error,
+        "type": if tx.to.is_some() { "CALL" } else { "CREATE" },
+    }))
+}
+
+#[cfg(test)]
+mod foundry_tests {
+    use super::*;
+    use crate::circuit_input_builder::ExecState;
+    use eth_types::{address, evm_types::OpcodeId};
+
+    const FOUNDRY_TRACE: &str = include_str!("foundry_trace.json");
+
+    #[test]
+    fn foundry_trace_builds_block() {
+        let block = BlockData::from_foundry_trace(FOUNDRY_TRACE).unwrap();
+        assert_eq!(block.chain_id, 31337);
+
+        let mut builder = block.new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let tx = &builder.block.txs()[0];
+        assert_eq!(
+            tx.from,
+            address!("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266")
+        );
+        assert!(tx.calls()[0].is_success);
+        assert_eq!(
+            tx.steps()
+                .iter()
+                .map(|step| step.exec_state.clone())
+                .collect::<Vec<_>>(),
+            [
+                ExecState::BeginTx,
+                ExecState::Op(OpcodeId::PUSH1),
+                ExecState::Op(OpcodeId::PUSH1),
+                ExecState::Op(OpcodeId::ADD),
+                ExecState::Op(OpcodeId::POP),
+                ExecState::Op(OpcodeId::STOP),
+                ExecState::EndTx,
+            ]
+        );
+    }
+
+    #[test]
+    fn foundry_trace_with_inner_calls_needs_call_trace() {
+        let mut artifact: Value = serde_json::from_str(FOUNDRY_TRACE).unwrap();
+        artifact["trace"]["structLogs"][2]["depth"] = 2.into();
+        assert!(matches!(
+            foundry_trace_to_geth_data(&artifact.to_string()),
+            Err(Error::InvalidGethExecTrace(_))
+        ));
+    }
+}
@@ -8,11 +8,12 @@ pub use container::OperationContainer;
 pub use eth_types::evm_types::{MemoryAddress, StackAddress};
 
 use core::{cmp::Ordering, fmt, fmt::Debug};
-use eth_types::{Address, Word};
+use eth_types::{Address, ToWord, Word};
+use serde::{Deserialize, Serialize};
 use std::mem::swap;
 
 /// Marker that defines whether an Operation performs a `READ` or a `WRITE`.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum RW {
     /// Marks op as READ.
     READ,
@@ -35,7 +36,7 @@ impl RW {
 /// Wrapper type over `usize` which represents the global counter. The purpose
 /// of the `RWCounter` is to enforce that each Opcode/Instruction and Operation
 /// is unique and just executed once.
-#[derive(Clone, Copy, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct RWCounter(pub usize);
 
 impl fmt::Debug for RWCounter {
@@ -124,7 +125,7 @@ pub trait Op: Clone + Eq + Ord {
 /// Represents a [`READ`](RW::READ)/[`WRITE`](RW::WRITE) into the memory implied
 /// by an specific [`OpcodeId`](eth_types::evm_types::opcode_ids::OpcodeId) of
 /// the [`ExecStep`](crate::circuit_input_builder::ExecStep).
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MemoryOp {
     /// Call ID
     pub call_id: usize,
@@ -219,7 +220,7 @@ impl Ord for MemoryOp {
 /// Represents a [`READ`](RW::READ)/[`WRITE`](RW::WRITE) into the stack implied
 /// by an specific [`OpcodeId`](eth_types::evm_types::opcode_ids::OpcodeId) of
 /// the [`ExecStep`](crate::circuit_input_builder::ExecStep).
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct StackOp {
     /// Call ID
     pub call_id: usize,
@@ -297,7 +298,7 @@ impl Ord for StackOp {
 /// implied by a specific
 /// [`OpcodeId`](eth_types::evm_types::opcode_ids::OpcodeId) of
 /// the [`ExecStep`](crate::circuit_input_builder::ExecStep).
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct StorageOp {
     /// Account Address
     pub address: Address,
@@ -398,7 +399,7 @@ impl Ord for StorageOp {
 /// implied by a specific
 /// [`OpcodeId`](eth_types::evm_types::opcode_ids::OpcodeId) of
 /// the [`ExecStep`](crate::circuit_input_builder::ExecStep).
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TransientStorageOp {
     /// Account Address
     pub address: Address,
@@ -494,7 +495,7 @@ impl Ord for TransientStorageOp {
 /// Represents a change in the Account AccessList implied by a `BeginTx`,
 /// `EXTCODECOPY`, `EXTCODESIZE`, `EXTCODEHASH` `BALANCE`, `SELFDESTRUCT`,
 /// `*CALL`* or `CREATE*` step.
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TxAccessListAccountOp {
     /// Transaction ID: Transaction index in the block starting at 1.
     pub tx_id: usize,
@@ -544,7 +545,7 @@ impl Op for TxAccessListAccountOp {
 
 /// Represents a change in the Storage AccessList implied by an `SSTORE` or
 /// `SLOAD` step of the [`ExecStep`](crate::circuit_input_builder::ExecStep).
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TxAccessListAccountStorageOp {
     /// Transaction ID: Transaction index in the block starting at 1.
     pub tx_id: usize,
@@ -596,7 +597,7 @@ impl Op for TxAccessListAccountStorageOp {
 /// Represents a change in the Transaction Refund AccessList implied by an
 /// `SSTORE`, `STOP`, `RETURN` or `REVERT` step of the
 /// [`ExecStep`](crate::circuit_input_builder::ExecStep).
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TxRefundOp {
     /// Transaction ID: Transaction index in the block starting at 1.
     pub tx_id: usize,
@@ -643,7 +644,7 @@ impl Op for TxRefundOp {
 
 /// Represents a field parameter of the Account that can be accessed via EVM
 /// execution.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum AccountField {
     /// Account Nonce
     Nonce,
@@ -660,7 +661,7 @@ pub enum AccountField {
 /// Represents a change in the Account field implied by a `BeginTx`,
 /// `EXTCODECOPY`, `EXTCODESIZE`, `BALANCE`, `SELFDESTRUCT`, `*CALL`*,
 /// `CREATE*`, `STOP`, `RETURN` or `REVERT` step.
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AccountOp {
     /// Account Address
     pub address: Address,
@@ -726,7 +727,7 @@ impl Op for AccountOp {
 
 /// Represents a field parameter of the CallContext that can be accessed via EVM
 /// execution.
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum CallContextField {
     /// RwCounterEndOfReversion
     RwCounterEndOfReversion,
@@ -783,7 +784,7 @@ pub enum CallContextField {
 }
 
 /// Represents an CallContext read/write operation.
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CallContextOp {
     /// call_id of CallContext
     pub call_id: usize,
@@ -854,7 +855,7 @@ impl CallContextOp {
 
 /// Represents a field parameter of the TxLog that can be accessed via EVM
 /// execution.
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum TxLogField {
     /// contract address
     Address,
@@ -867,7 +868,7 @@ pub enum TxLogField {
 }
 
 /// Represents TxLog read/write operation.
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TxLogOp {
     /// tx_id of TxLog, starts with 1 in rw table, and it's unique per Tx
     pub tx_id: usize,
@@ -941,7 +942,7 @@ impl Op for TxLogOp {
 
 /// Represents a field parameter of the TxReceipt that can be accessed via EVM
 /// execution.
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum TxReceiptField {
     /// flag indicates whether a tx succeed or not
     PostStateOrStatus,
@@ -953,7 +954,7 @@ pub enum TxReceiptField {
 }
 
 /// Represent a Start padding operation
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct StartOp {}
 
 impl PartialOrd for StartOp {
@@ -979,7 +980,7 @@ impl Op for StartOp {
 }
 
 /// Represents TxReceipt read/write operation.
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TxReceiptOp {
     /// tx_id of TxReceipt
     pub tx_id: usize,
@@ -1024,7 +1025,7 @@ impl Op for TxReceiptOp {
 
 /// Generic enum that wraps over all the operation types possible.
 /// In particular [`StackOp`], [`MemoryOp`] and [`StorageOp`].
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum OpEnum {
     /// Stack
     Stack(StackOp),
@@ -1052,8 +1053,46 @@ pub enum OpEnum {
     Start(StartOp),
 }
 
+impl OpEnum {
+    /// Return the call id or tx id the operation is scoped to, if any.
+    pub fn id(&self) -> Option<usize> {
+        match self {
+            Self::Stack(op) => Some(op.call_id),
+            Self::Memory(op) => Some(op.call_id),
+            Self::CallContext(op) => Some(op.call_id),
+            Self::Storage(op) => Some(op.tx_id),
+            Self::TransientStorage(op) => Some(op.tx_id),
+            Self::TxAccessListAccount(op) => Some(op.tx_id),
+            Self::TxAccessListAccountStorage(op) => Some(op.tx_id),
+            Self::TxRefund(op) => Some(op.tx_id),
+            Self::TxReceipt(op) => Some(op.tx_id),
+            Self::TxLog(op) => Some(op.tx_id),
+            Self::Account(_) | Self::Start(_) => None,
+        }
+    }
+
+    /// Return the address the operation accesses, if any: the stack or memory
+    /// address for [`StackOp`] and [`MemoryOp`], or the account address.
+    pub fn address(&self) -> Option<Word> {
+        match self {
+            Self::Stack(op) => Some(op.address.0.into()),
+            Self::Memory(op) => Some(op.address.0.into()),
+            Self::Storage(op) => Some(op.address.to_word()),
+            Self::TransientStorage(op) => Some(op.address.to_word()),
+            Self::TxAccessListAccount(op) => Some(op.address.to_word()),
+            Self::TxAccessListAccountStorage(op) => Some(op.address.to_word()),
+            Self::Account(op) => Some(op.address.to_word()),
+            Self::CallContext(_)
+            | Self::TxRefund(_)
+            | Self::TxReceipt(_)
+            | Self::TxLog(_)
+            | Self::Start(_) => None,
+        }
+    }
+}
+
 /// Operation is a Wrapper over a type that implements Op with a RWCounter.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Operation<T: Op> {
     rwc: RWCounter,
     rw: RW,
@@ -4,7 +4,9 @@ use super::{
     TxLogOp, TxReceiptOp, TxRefundOp, RW,
 };
 use crate::exec_trace::OperationRef;
+use eth_types::Word;
 use itertools::Itertools;
+use serde::{Deserialize, Serialize};
 
 /// The `OperationContainer` is meant to store all of the [`Operation`]s that an
 /// [`ExecStep`](crate::circuit_input_builder::ExecStep) performs during its
@@ -20,7 +22,7 @@ use itertools::Itertools;
 /// they have specified.
 /// That serves as a way to get an input with which is easy to work with in
 /// order to construct the State proof.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct OperationContainer {
     /// Operations of MemoryOp
     pub memory: Vec<Operation<MemoryOp>>,
@@ -196,6 +198,45 @@ impl OperationContainer {
     pub fn sorted_storage(&self) -> Vec<Operation<StorageOp>> {
         self.storage.iter().sorted().cloned().collect()
     }
+
+    /// Returns, in insertion order, the operations of `target` scoped to `id`
+    /// and accessing `address`, see [`OpEnum::id`] and [`OpEnum::address`].
+    ///
+    /// Operations for which [`OpEnum::id`] or [`OpEnum::address`] is `None`
+    /// are not filtered on it, so the corresponding argument is ignored: `id`
+    /// for [`Target::Account`] and [`Target::Start`], and `address` for
+    /// [`Target::CallContext`], [`Target::TxRefund`], [`Target::TxReceipt`],
+    /// [`Target::TxLog`] and [`Target::Start`].
+    pub fn find_op(&self, target: Target, id: usize, address: Word) -> Vec<(RW, OpEnum)> {
+        fn matching<T: Op>(ops: &[Operation<T>], id: usize, address: Word) -> Vec<(RW, OpEnum)> {
+            ops.iter()
+                .map(|op| (op.rw(), op.op().clone().into_enum()))
+                .filter(|(_, op)| {
+                    op.id().map_or(true, |op_id| op_id == id)
+                        && op
+                            .address()
+                            .map_or(true, |op_address| op_address == address)
+                })
+                .collect()
+        }
+
+        match target {
+            Target::Start => matching(&self.start, id, address),
+            Target::Memory => matching(&self.memory, id, address),
+            Target::Stack => matching(&self.stack, id, address),
+            Target::Storage => matching(&self.storage, id, address),
+            Target::TransientStorage => matching(&self.transient_storage, id, address),
+            Target::TxAccessListAccount => matching(&self.tx_access_list_account, id, address),
+            Target::TxAccessListAccountStorage => {
+                matching(&self.tx_access_list_account_storage, id, address)
+            }
+            Target::TxRefund => matching(&self.tx_refund, id, address),
+            Target::Account => matching(&self.account, id, address),
+            Target::CallContext => matching(&self.call_context, id, address),
+            Target::TxReceipt => matching(&self.tx_receipt, id, address),
+            Target::TxLog => matching(&self.tx_log, id, address),
+        }
+    }
 }
 
 #[cfg(test)]
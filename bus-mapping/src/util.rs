@@ -1,5 +1,7 @@
 //! ..
+use eth_types::{trie::trie_root, H256};
 pub use eth_types::{KECCAK_CODE_HASH_EMPTY, POSEIDON_CODE_HASH_EMPTY};
+use ethers_core::utils::rlp;
 use std::{convert::Infallible, str::FromStr, sync::LazyLock};
 
 /// ..
@@ -8,6 +10,29 @@ pub fn read_env_var<T: Clone + FromStr>(var_name: &'static str, default: T) -> T
         .map(|s| s.parse::<T>().unwrap_or_else(|_| default.clone()))
         .unwrap_or(default)
 }
+/// Compare a value computed by the builder with the one in the geth trace,
+/// reporting a difference through `CircuitInputStateRef::geth_mismatch`.
+/// Both values are evaluated once.
+#[cfg(feature = "enable-stack")]
+macro_rules! check_geth_eq {
+    ($state:expr, $left:expr, $right:expr $(,)?) => {{
+        let (left, right) = (&$left, &$right);
+        if *left != *right {
+            let mismatch = format!("{:?} != {:?}", left, right);
+            $state.geth_mismatch(concat!(file!(), ":", line!()), mismatch);
+        }
+    }};
+    ($state:expr, $left:expr, $right:expr, $($arg:tt)+) => {{
+        let (left, right) = (&$left, &$right);
+        if *left != *right {
+            let mismatch = format!("{:?} != {:?}, {}", left, right, format_args!($($arg)+));
+            $state.geth_mismatch(concat!(file!(), ":", line!()), mismatch);
+        }
+    }};
+}
+#[cfg(feature = "enable-stack")]
+pub(crate) use check_geth_eq;
+
 /// env var for Geth trace sanity check level
 pub static GETH_TRACE_CHECK_LEVEL: LazyLock<GethTraceSanityCheckLevel> =
     LazyLock::new(|| read_env_var("GETH_TRACE_CHECK_LEVEL", GethTraceSanityCheckLevel::None));
@@ -46,3 +71,14 @@ impl FromStr for GethTraceSanityCheckLevel {
         }
     }
 }
+
+/// Root of the Merkle Patricia trie mapping `rlp(i)` to `values[i]`, as used
+/// for the transactions and receipts roots of a block header.
+pub fn ordered_trie_root(values: &[Vec<u8>]) -> H256 {
+    let items = values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| (rlp::encode(&(i as u64)).to_vec(), value.clone()))
+        .collect::<Vec<_>>();
+    trie_root(&items)
+}
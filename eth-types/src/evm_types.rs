@@ -69,7 +69,9 @@ pub const MAX_CODE_SIZE: u64 = 24576;
 /// square operation of gas calculation.
 /// <https://github.com/ethereum/go-ethereum/blob/e6b6a8b738069ad0579f6798ee59fde93ed13b43/core/vm/gas_table.go#L38>
 pub const MAX_EXPANDED_MEMORY_ADDRESS: u64 = 0x1FFFFFFFE0;
-/// Quotient for max refund of gas used
+/// Quotient for max refund of gas used, as set by EIP-3529 (London). Only
+/// post-London rules are supported, so the pre-London quotient of 2 is never
+/// used.
 pub const MAX_REFUND_QUOTIENT_OF_GAS_USED: usize = 5;
 /// Gas stipend when CALL or CALLCODE is attached with value.
 pub const GAS_STIPEND_CALL_WITH_VALUE: u64 = 2300;
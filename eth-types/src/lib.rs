@@ -29,6 +29,7 @@ pub mod geth_types;
 pub mod l2_types;
 pub mod sign_types;
 pub mod state_db;
+pub mod trie;
 pub mod utils;
 
 use crate::evm_types::{Gas, GasCost, OpcodeId, ProgramCounter};
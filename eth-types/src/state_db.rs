@@ -142,11 +142,38 @@ pub struct StateDB {
     // Accounts that have been through `SELFDESTRUCT` under the situation that `is_persistent` is
     // `true`. These accounts will be reset once `commit_tx` is called.
     destructed_account: HashSet<Address>,
+    // Accounts created in the current transaction. Under EIP-6780, `SELFDESTRUCT` only deletes
+    // an account that was created in the same transaction.
+    created_account: HashSet<Address>,
     // Accounts that are still "empty", but an Account Rw {value_prev: 0x0, value: empty_code_hash}
     // has already been applied.
     // TODO: a better name?
     touched_account: HashSet<Address>,
     refund: u64,
+    // Undo entries of the changes made since the first checkpoint, replayed
+    // backwards by `revert_to`.
+    journal: Vec<JournalEntry>,
+    // Length of `journal` when each checkpoint was taken by `snapshot`.
+    checkpoints: Vec<usize>,
+}
+
+/// Undo entry of the [`StateDB`] journal, holding the value a change
+/// overwrote.
+///
+/// `touched_account` has no entry on purpose, as it tracks which accounts
+/// already have an Account Rw applied, which a reversion doesn't undo.
+#[derive(Debug, Clone)]
+enum JournalEntry {
+    Account(Address, Option<Account>),
+    DirtyStorage((Address, Word), Option<Word>),
+    TransientStorage((Address, Word), Option<Word>),
+    // `true` when the entry was added to the access list, `false` when it
+    // was removed.
+    AccessListAccount(Address, bool),
+    AccessListAccountStorage((Address, U256), bool),
+    Destructed(Address),
+    Created(Address),
+    Refund(u64),
 }
 
 impl StateDB {
@@ -157,6 +184,7 @@ impl StateDB {
 
     /// Set an [`Account`] at `addr` in the StateDB.
     pub fn set_account(&mut self, addr: &Address, acc: Account) {
+        self.journal_account(addr);
         self.state.insert(*addr, acc);
     }
 
@@ -196,6 +224,7 @@ impl StateDB {
     /// [`Account`] is not found in the state, a zero one will be inserted
     /// and returned along with false.
     pub fn get_account_mut(&mut self, addr: &Address) -> (bool, &mut Account) {
+        self.journal_account(addr);
         let found = if self.state.contains_key(addr) {
             true
         } else {
@@ -262,7 +291,8 @@ impl StateDB {
     /// After transaction execution, `dirty_storage` is committed into `storage`
     /// in `commit_tx` method.
     pub fn set_storage(&mut self, addr: &Address, key: &Word, value: &Word) {
-        self.dirty_storage.insert((*addr, *key), *value);
+        let prev = self.dirty_storage.insert((*addr, *key), *value);
+        self.journal(JournalEntry::DirtyStorage((*addr, *key), prev));
     }
 
     /// Get balance of account with the given address.
@@ -274,7 +304,8 @@ impl StateDB {
     /// Set transient storage value at `addr` and `key`.
     /// Transient storage is cleared after transaction execution.
     pub fn set_transient_storage(&mut self, addr: &Address, key: &Word, value: &Word) {
-        self.transient_storage.insert((*addr, *key), *value);
+        let prev = self.transient_storage.insert((*addr, *key), *value);
+        self.journal(JournalEntry::TransientStorage((*addr, *key), prev));
     }
 
     /// Get nonce of account with `addr`.
@@ -302,13 +333,20 @@ impl StateDB {
     /// Add `addr` into account access list. Returns `true` if it's not in the
     /// access list before.
     pub fn add_account_to_access_list(&mut self, addr: Address) -> bool {
-        self.access_list_account.insert(addr)
+        let added = self.access_list_account.insert(addr);
+        if added {
+            self.journal(JournalEntry::AccessListAccount(addr, true));
+        }
+        added
     }
 
     /// Remove `addr` from account access list.
     pub fn remove_account_from_access_list(&mut self, addr: &Address) {
         let exist = self.access_list_account.remove(addr);
         debug_assert!(exist);
+        if exist {
+            self.journal(JournalEntry::AccessListAccount(*addr, false));
+        }
     }
 
     /// Check whether `(addr, key)` exists in account storage access list.
@@ -319,19 +357,60 @@ impl StateDB {
     /// Add `(addr, key)` into account storage access list. Returns `true` if
     /// it's not in the access list before.
     pub fn add_account_storage_to_access_list(&mut self, (addr, key): (Address, Word)) -> bool {
-        self.access_list_account_storage.insert((addr, key))
+        let added = self.access_list_account_storage.insert((addr, key));
+        if added {
+            self.journal(JournalEntry::AccessListAccountStorage((addr, key), true));
+        }
+        added
     }
 
     /// Remove `(addr, key)` from account storage access list.
     pub fn remove_account_storage_from_access_list(&mut self, pair: &(Address, Word)) {
         let exist = self.access_list_account_storage.remove(pair);
         debug_assert!(exist);
+        if exist {
+            self.journal(JournalEntry::AccessListAccountStorage(*pair, false));
+        }
+    }
+
+    /// Warm addresses and warm storage slots of each address (EIP-2929) in
+    /// the current transaction.
+    pub fn access_list_snapshot(&self) -> (HashSet<Address>, HashMap<Address, HashSet<U256>>) {
+        let mut storage: HashMap<Address, HashSet<U256>> = HashMap::new();
+        for (addr, key) in self.access_list_account_storage.iter() {
+            storage.entry(*addr).or_default().insert(*key);
+        }
+        (self.access_list_account.clone(), storage)
     }
 
     /// Set account as self destructed.
     pub fn destruct_account(&mut self, addr: Address) {
-        self.state.insert(addr, Account::zero());
-        self.destructed_account.insert(addr);
+        self.set_account(&addr, Account::zero());
+        if self.destructed_account.insert(addr) {
+            self.journal(JournalEntry::Destructed(addr));
+        }
+    }
+
+    /// Addresses of the accounts deleted by `SELFDESTRUCT` so far, sorted.
+    /// An account that self destructs without having been created in the
+    /// same transaction is not deleted (EIP-6780), so it isn't listed.
+    pub fn destructed_accounts(&self) -> Vec<Address> {
+        let addrs: BTreeSet<_> = self.destructed_account.iter().copied().collect();
+        addrs.into_iter().collect()
+    }
+
+    /// Mark the account at `addr` as created in the current transaction.
+    pub fn set_created(&mut self, addr: &Address) -> bool {
+        let created = self.created_account.insert(*addr);
+        if created {
+            self.journal(JournalEntry::Created(*addr));
+        }
+        created
+    }
+
+    /// Whether the account at `addr` was created in the current transaction.
+    pub fn is_created(&self, addr: &Address) -> bool {
+        self.created_account.contains(addr)
     }
 
     /// Retrieve refund.
@@ -341,13 +420,96 @@ impl StateDB {
 
     /// Set refund
     pub fn set_refund(&mut self, value: u64) {
-        self.refund = value;
+        let prev = std::mem::replace(&mut self.refund, value);
+        self.journal(JournalEntry::Refund(prev));
+    }
+
+    /// Take a checkpoint of the current state, to which the [`StateDB`] can be
+    /// restored later with [`StateDB::revert_to`]. Checkpoints are nested,
+    /// the returned value identifies the checkpoint in the checkpoint stack.
+    ///
+    /// Like geth, no state is copied: from the first checkpoint on, every
+    /// change is recorded in a journal of undo entries instead.
+    pub fn snapshot(&mut self) -> usize {
+        self.checkpoints.push(self.journal.len());
+        self.checkpoints.len() - 1
+    }
+
+    /// Restore the state as it was when `checkpoint` was taken, by undoing the
+    /// changes journaled since then in reverse order. The checkpoint and all
+    /// the checkpoints taken after it are discarded.
+    pub fn revert_to(&mut self, checkpoint: usize) {
+        assert!(
+            checkpoint < self.checkpoints.len(),
+            "invalid checkpoint {checkpoint}, only {} snapshots taken",
+            self.checkpoints.len()
+        );
+        let journal_len = self.checkpoints[checkpoint];
+        self.checkpoints.truncate(checkpoint);
+        for entry in self.journal.split_off(journal_len).into_iter().rev() {
+            match entry {
+                JournalEntry::Account(addr, Some(acc)) => {
+                    self.state.insert(addr, acc);
+                }
+                JournalEntry::Account(addr, None) => {
+                    self.state.remove(&addr);
+                }
+                JournalEntry::DirtyStorage(key, Some(value)) => {
+                    self.dirty_storage.insert(key, value);
+                }
+                JournalEntry::DirtyStorage(key, None) => {
+                    self.dirty_storage.remove(&key);
+                }
+                JournalEntry::TransientStorage(key, Some(value)) => {
+                    self.transient_storage.insert(key, value);
+                }
+                JournalEntry::TransientStorage(key, None) => {
+                    self.transient_storage.remove(&key);
+                }
+                JournalEntry::AccessListAccount(addr, true) => {
+                    self.access_list_account.remove(&addr);
+                }
+                JournalEntry::AccessListAccount(addr, false) => {
+                    self.access_list_account.insert(addr);
+                }
+                JournalEntry::AccessListAccountStorage(pair, true) => {
+                    self.access_list_account_storage.remove(&pair);
+                }
+                JournalEntry::AccessListAccountStorage(pair, false) => {
+                    self.access_list_account_storage.insert(pair);
+                }
+                JournalEntry::Destructed(addr) => {
+                    self.destructed_account.remove(&addr);
+                }
+                JournalEntry::Created(addr) => {
+                    self.created_account.remove(&addr);
+                }
+                JournalEntry::Refund(refund) => self.refund = refund,
+            }
+        }
+    }
+
+    // Record `entry` in the journal, only needed once a checkpoint is taken.
+    fn journal(&mut self, entry: JournalEntry) {
+        if !self.checkpoints.is_empty() {
+            self.journal.push(entry);
+        }
+    }
+
+    // Record the account at `addr` before it's changed.
+    fn journal_account(&mut self, addr: &Address) {
+        if !self.checkpoints.is_empty() {
+            let prev = self.state.get(addr).cloned();
+            self.journal.push(JournalEntry::Account(*addr, prev));
+        }
     }
 
     /// Clear access list and refund, and commit dirty storage.
     /// It should be invoked before processing
     /// with new transaction with the same [`StateDB`].
     pub fn commit_tx(&mut self) {
+        self.journal = Vec::new();
+        self.checkpoints = Vec::new();
         self.access_list_account = HashSet::new();
         self.access_list_account_storage = HashSet::new();
         for ((addr, key), value) in self.dirty_storage.clone() {
@@ -356,6 +518,7 @@ impl StateDB {
         }
         self.dirty_storage = HashMap::new();
         self.touched_account = HashSet::new();
+        self.created_account = HashSet::new();
         for addr in self.destructed_account.clone() {
             let (_, account) = self.get_account_mut(&addr);
             *account = ACCOUNT_ZERO.clone();
@@ -428,4 +591,112 @@ mod statedb_tests {
         assert!(found);
         assert_eq!(value, &Word::from(102));
     }
+
+    #[test]
+    fn statedb_snapshot_revert() {
+        let addr_a = address!("0x0000000000000000000000000000000000000001");
+        let addr_b = address!("0x0000000000000000000000000000000000000002");
+        let mut statedb = StateDB::new();
+        statedb.get_account_mut(&addr_a).1.balance = Word::from(100);
+        statedb.set_storage(&addr_a, &Word::from(1), &Word::from(10));
+
+        let outer = statedb.snapshot();
+        statedb.get_account_mut(&addr_a).1.balance = Word::from(50);
+        statedb.increase_nonce(&addr_a);
+        statedb.set_storage(&addr_a, &Word::from(1), &Word::from(11));
+        statedb.add_account_to_access_list(addr_b);
+
+        let inner = statedb.snapshot();
+        statedb.get_account_mut(&addr_b).1.balance = Word::from(50);
+        statedb.set_storage(&addr_a, &Word::from(1), &Word::from(12));
+        statedb.set_refund(20);
+
+        // Revert the inner checkpoint only
+        statedb.revert_to(inner);
+        assert!(!statedb.get_account(&addr_b).0);
+        assert_eq!(
+            statedb.get_storage(&addr_a, &Word::from(1)).1,
+            &Word::from(11)
+        );
+        assert_eq!(statedb.refund(), 0);
+        assert!(statedb.check_account_in_access_list(&addr_b));
+
+        // Revert the outer checkpoint
+        statedb.revert_to(outer);
+        assert_eq!(statedb.get_balance(&addr_a), Word::from(100));
+        assert_eq!(statedb.get_nonce(&addr_a), 0);
+        assert_eq!(
+            statedb.get_storage(&addr_a, &Word::from(1)).1,
+            &Word::from(10)
+        );
+        assert!(!statedb.check_account_in_access_list(&addr_b));
+    }
+
+    #[test]
+    fn statedb_journal_revert() {
+        let addr_a = address!("0x0000000000000000000000000000000000000001");
+        let addr_b = address!("0x0000000000000000000000000000000000000002");
+        let key = Word::from(1);
+        let mut statedb = StateDB::new();
+        statedb.get_account_mut(&addr_a).1.balance = Word::from(100);
+        statedb.add_account_storage_to_access_list((addr_a, key));
+
+        let checkpoint = statedb.snapshot();
+        // The same slot written twice is restored to its value at the
+        // checkpoint, not to the intermediate one.
+        statedb.set_transient_storage(&addr_a, &key, &Word::from(1));
+        statedb.set_transient_storage(&addr_a, &key, &Word::from(2));
+        statedb.remove_account_storage_from_access_list(&(addr_a, key));
+        statedb.set_created(&addr_b);
+        statedb.get_account_mut(&addr_b).1.balance = Word::from(10);
+        statedb.destruct_account(addr_a);
+        assert_eq!(statedb.destructed_accounts(), vec![addr_a]);
+
+        statedb.revert_to(checkpoint);
+        assert_eq!(statedb.get_balance(&addr_a), Word::from(100));
+        assert!(statedb.destructed_accounts().is_empty());
+        assert!(!statedb.is_created(&addr_b));
+        assert!(!statedb.get_account(&addr_b).0);
+        assert_eq!(
+            statedb.get_transient_storage(&addr_a, &key),
+            (false, &Word::zero())
+        );
+        assert!(statedb.check_account_storage_in_access_list(&(addr_a, key)));
+    }
+
+    #[test]
+    fn statedb_access_list_snapshot() {
+        let caller = address!("0x00000000000000000000000000000000000000c0");
+        let callee = address!("0x00000000000000000000000000000000000000c1");
+        let coinbase = address!("0x00000000000000000000000000000000000000cb");
+        let addr_a = address!("0x000000000000000000000000000000000000000a");
+        let addr_b = address!("0x000000000000000000000000000000000000000b");
+        let mut statedb = StateDB::new();
+
+        // Always warm: precompiles, caller, callee and coinbase (Shanghai).
+        let mut warm: HashSet<_> = (1..=9).map(Address::from_low_u64_be).collect();
+        warm.extend([caller, callee, coinbase]);
+        for addr in warm.iter() {
+            statedb.add_account_to_access_list(*addr);
+        }
+        // BALANCE on two addresses, SLOAD of a slot of the callee.
+        statedb.add_account_to_access_list(addr_a);
+        statedb.add_account_to_access_list(addr_b);
+        statedb.add_account_storage_to_access_list((callee, Word::from(7)));
+        // Warming an address twice doesn't change anything.
+        statedb.add_account_to_access_list(addr_a);
+
+        let (addresses, slots) = statedb.access_list_snapshot();
+        warm.extend([addr_a, addr_b]);
+        assert_eq!(addresses, warm);
+        assert_eq!(
+            slots,
+            HashMap::from([(callee, HashSet::from([Word::from(7)]))])
+        );
+
+        statedb.commit_tx();
+        let (addresses, slots) = statedb.access_list_snapshot();
+        assert!(addresses.is_empty());
+        assert!(slots.is_empty());
+    }
 }
@@ -0,0 +1,147 @@
+//! Merkle Patricia trie built in memory from the full list of its key-value
+//! pairs, used to compute trie roots.
+
+use crate::H256;
+use ethers_core::utils::{
+    keccak256,
+    rlp::{self, RlpStream},
+};
+
+/// Root of the Merkle Patricia trie holding `items` as `(key, value)` pairs.
+pub fn trie_root(items: &[(Vec<u8>, Vec<u8>)]) -> H256 {
+    if items.is_empty() {
+        return H256(keccak256(rlp::NULL_RLP));
+    }
+    H256(keccak256(trie_node(&nibble_items(items), 0)))
+}
+
+fn to_nibbles(bytes: &[u8]) -> Vec<u8> {
+    bytes.iter().flat_map(|b| [b >> 4, b & 0x0f]).collect()
+}
+
+fn nibble_items(items: &[(Vec<u8>, Vec<u8>)]) -> Vec<(Vec<u8>, &[u8])> {
+    items
+        .iter()
+        .map(|(key, value)| (to_nibbles(key), value.as_slice()))
+        .collect()
+}
+
+/// RLP encoding of the trie node holding `items`, whose keys all share their
+/// first `depth` nibbles.
+fn trie_node(items: &[(Vec<u8>, &[u8])], depth: usize) -> Vec<u8> {
+    let mut stream = RlpStream::new();
+    if let [(item_key, value)] = items {
+        stream.begin_list(2);
+        stream.append(&hex_prefix(&item_key[depth..], true));
+        stream.append(*value);
+    } else {
+        let (first, _) = &items[0];
+        let prefix_len = (depth..first.len())
+            .take_while(|&i| {
+                items
+                    .iter()
+                    .all(|(item_key, _)| item_key.get(i) == Some(&first[i]))
+            })
+            .count();
+        if prefix_len > 0 {
+            let prefix = &first[depth..depth + prefix_len];
+            stream.begin_list(2);
+            stream.append(&hex_prefix(prefix, false));
+            append_child(&mut stream, trie_node(items, depth + prefix_len));
+        } else {
+            stream.begin_list(17);
+            for nibble in 0..16 {
+                let children = items
+                    .iter()
+                    .filter(|(item_key, _)| item_key.get(depth) == Some(&nibble))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                if children.is_empty() {
+                    stream.append_empty_data();
+                } else {
+                    append_child(&mut stream, trie_node(&children, depth + 1));
+                }
+            }
+            match items.iter().find(|(item_key, _)| item_key.len() == depth) {
+                Some((_, value)) => stream.append(*value),
+                None => stream.append_empty_data(),
+            };
+        }
+    }
+
+    stream.out().to_vec()
+}
+
+/// Reference a child node from its parent: nodes shorter than 32 bytes are
+/// inlined, larger ones are referenced by hash.
+fn append_child(stream: &mut RlpStream, node: Vec<u8>) {
+    if node.len() < 32 {
+        stream.append_raw(&node, 1);
+    } else {
+        stream.append(&keccak256(node)[..]);
+    }
+}
+
+/// Hex-prefix encoding of a nibble path.
+fn hex_prefix(nibbles: &[u8], is_leaf: bool) -> Vec<u8> {
+    let flag = if is_leaf { 2 } else { 0 };
+    let mut encoded = if nibbles.len() % 2 == 1 {
+        vec![((flag + 1) << 4) | nibbles[0]]
+    } else {
+        vec![flag << 4]
+    };
+    let rest = &nibbles[nibbles.len() % 2..];
+    encoded.extend(rest.chunks(2).map(|pair| (pair[0] << 4) | pair[1]));
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn items(pairs: &[(&str, &str)]) -> Vec<(Vec<u8>, Vec<u8>)> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.as_bytes().to_vec(), value.as_bytes().to_vec()))
+            .collect()
+    }
+
+    fn root(hex: &str) -> H256 {
+        H256::from_str(hex).unwrap()
+    }
+
+    #[test]
+    fn empty_trie_root() {
+        assert_eq!(
+            trie_root(&[]),
+            root("0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421")
+        );
+    }
+
+    // Roots from `TestInsert` and `TestDelete` of geth's trie/trie_test.go.
+    #[test]
+    fn trie_root_geth_fixtures() {
+        let inserted = items(&[
+            ("doe", "reindeer"),
+            ("dog", "puppy"),
+            ("dogglesworth", "cat"),
+        ]);
+        assert_eq!(
+            trie_root(&inserted),
+            root("0x8aad789dff2f538bca5d8ea56e8abe10f4c7ba3a5dea95fea4cd6e7c3a1168d3")
+        );
+
+        // What's left of `TestDelete` once "ether" and "shaman" are deleted.
+        let deleted = items(&[
+            ("do", "verb"),
+            ("horse", "stallion"),
+            ("doge", "coin"),
+            ("dog", "puppy"),
+        ]);
+        assert_eq!(
+            trie_root(&deleted),
+            root("0x5991bb8c6514148a29db676a14ac506cd2cd5775ace63c30a4fe457715e9ac84")
+        );
+    }
+}
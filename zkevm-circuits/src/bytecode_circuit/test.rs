@@ -320,3 +320,45 @@ fn bytecode_soundness_bug_1() {
     let prover = MockProver::<Fr>::run(k, &circuit, Vec::new()).unwrap();
     prover.assert_satisfied_par();
 }
+
+/// The code of a called contract is bound to the code hash read from its
+/// account: the EVM circuit looks the code up by that hash, and the bytecode
+/// circuit checks the hash of the code.
+#[test]
+fn bytecode_called_contract_code_hash() {
+    use bus_mapping::mock::BlockData;
+    use eth_types::{bytecode, geth_types::GethData};
+    use mock::TestContext;
+
+    let k = 9;
+    let code = bytecode! {
+        PUSH1(1)
+        PUSH1(2)
+        ADD
+        STOP
+    };
+    let block: GethData = TestContext::<2, 1>::simple_ctx_with_bytecode(code.clone())
+        .unwrap()
+        .into();
+    let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+    builder
+        .handle_block(&block.eth_block, &block.geth_traces)
+        .unwrap();
+    let block = crate::witness::block_convert::<Fr>(&builder.block, &builder.code_db).unwrap();
+
+    let code_hash = CodeDB::hash(&code.code()).to_word();
+    assert_eq!(
+        builder.block.txs()[0].calls()[0].code_hash.to_word(),
+        code_hash
+    );
+    assert_eq!(block.bytecodes[&code_hash].bytes, code.code());
+    test_bytecode_circuit_unrolled::<Fr>(
+        k,
+        BytecodeCircuit::<Fr>::new_from_block_sized(&block, 1 << k).bytecodes,
+        true,
+    );
+
+    // The called code under a mismatched hash
+    let invalid = unroll_with_codehash(code_hash + Word::one(), code.code());
+    test_bytecode_circuit_unrolled::<Fr>(k, vec![invalid], false);
+}
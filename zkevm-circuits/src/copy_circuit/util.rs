@@ -16,7 +16,7 @@ pub fn number_or_hash_to_field<F: Field>(v: &NumberOrHash, challenge: Value<F>)
                 b
             };
             challenge.map(|challenge| {
-                rlc::value(
+                rlc::value_le(
                     &le_bytes,
                     if cfg!(feature = "poseidon-codehash") {
                         0x100u64.into()
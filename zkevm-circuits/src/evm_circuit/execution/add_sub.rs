@@ -128,6 +128,17 @@ mod test {
         test_ok(OpcodeId::SUB, 0x090705.into(), 0x060504.into());
     }
 
+    #[test]
+    fn sub_gadget_underflow_wraps() {
+        // `test_ok(SUB, a, b)` computes `b - a`.
+        // 0 - 1 = 2^256 - 1
+        test_ok(OpcodeId::SUB, 1.into(), 0.into());
+        // 5 - 10 = 2^256 - 5
+        test_ok(OpcodeId::SUB, 10.into(), 5.into());
+        // 0 - (2^256 - 1) = 1
+        test_ok(OpcodeId::SUB, Word::MAX, 0.into());
+    }
+
     #[test]
     fn add_gadget_rand() {
         let a = rand_word();
@@ -1,7 +1,6 @@
 use crate::{
     evm_circuit::{
         execution::ExecutionGadget,
-        param::N_BYTES_ACCOUNT_ADDRESS,
         step::ExecutionState,
         util::{
             common_gadget::SameContextGadget,
@@ -9,22 +8,21 @@ use crate::{
                 ConstrainBuilderCommon, EVMConstraintBuilder, ReversionInfo, StepStateTransition,
                 Transition::Delta,
             },
-            from_bytes,
-            math_gadget::IsZeroGadget,
-            not, select, CachedRegion, Cell, Word,
+            math_gadget::{IsZeroGadget, WordToAddressGadget},
+            not, select, CachedRegion, Cell,
         },
         witness::{Block, Call, ExecStep, Transaction},
     },
     table::{AccountFieldTag, CallContextFieldTag},
     util::{Expr, Field},
 };
-use eth_types::{evm_types::GasCost, ToLittleEndian};
+use eth_types::evm_types::GasCost;
 use halo2_proofs::{circuit::Value, plonk::Error};
 
 #[derive(Clone, Debug)]
 pub(crate) struct BalanceGadget<F> {
     same_context: SameContextGadget<F>,
-    address_word: Word<F>,
+    address_word: WordToAddressGadget<F>,
     reversion_info: ReversionInfo<F>,
     tx_id: Cell<F>,
     is_warm: Cell<F>,
@@ -39,8 +37,8 @@ impl<F: Field> ExecutionGadget<F> for BalanceGadget<F> {
     const EXECUTION_STATE: ExecutionState = ExecutionState::BALANCE;
 
     fn configure(cb: &mut EVMConstraintBuilder<F>) -> Self {
-        let address_word = cb.query_word_rlc();
-        let address = from_bytes::expr(&address_word.cells[..N_BYTES_ACCOUNT_ADDRESS]);
+        let address_word = WordToAddressGadget::construct(cb, false);
+        let address = address_word.address();
         cb.stack_pop(address_word.expr());
 
         let tx_id = cb.call_context(None, CallContextFieldTag::TxId);
@@ -110,8 +108,7 @@ impl<F: Field> ExecutionGadget<F> for BalanceGadget<F> {
         self.same_context.assign_exec_step(region, offset, step)?;
 
         let address = block.rws[step.rw_indices[0]].stack_value();
-        self.address_word
-            .assign(region, offset, Some(address.to_le_bytes()))?;
+        self.address_word.assign(region, offset, address)?;
 
         self.tx_id
             .assign(region, offset, Value::known(F::from(tx.id as u64)))?;
@@ -147,7 +144,9 @@ impl<F: Field> ExecutionGadget<F> for BalanceGadget<F> {
 #[cfg(test)]
 mod test {
     use crate::{evm_circuit::test::rand_bytes, test_util::CircuitTestBuilder};
-    use eth_types::{address, bytecode, geth_types::Account, Address, Bytecode, Word, U256};
+    use eth_types::{
+        address, bytecode, geth_types::Account, Address, Bytecode, ToWord, Word, U256,
+    };
     use mock::{generate_mock_call_bytecode, test_ctx::TestContext, MockCallBytecodeParams};
     use std::sync::LazyLock;
 
@@ -201,6 +200,38 @@ mod test {
         test_internal_ok(0x1010, 0xff, &account, true);
     }
 
+    #[test]
+    fn balance_gadget_address_with_high_bytes() {
+        // Only the low 20 bytes of the stack word are used as the address.
+        let address_word = (Word::from(0xdeadbeef_u64) << 160) | TEST_ADDRESS.to_word();
+        let code = bytecode! {
+            PUSH32(address_word)
+            BALANCE
+            STOP
+        };
+
+        let ctx = TestContext::<3, 1>::new(
+            None,
+            |accs| {
+                accs[0]
+                    .address(address!("0x000000000000000000000000000000000000cafe"))
+                    .balance(Word::from(1_u64 << 20))
+                    .code(code);
+                accs[1].address(*TEST_ADDRESS).balance(Word::from(900));
+                accs[2]
+                    .address(address!("0x0000000000000000000000000000000000000020"))
+                    .balance(Word::from(1_u64 << 20));
+            },
+            |mut txs, accs| {
+                txs[0].to(accs[0].address).from(accs[2].address);
+            },
+            |block, _tx| block,
+        )
+        .unwrap();
+
+        CircuitTestBuilder::new_from_test_ctx(ctx).run();
+    }
+
     fn test_root_ok(account: &Option<Account>, is_warm: bool) {
         let address = account.as_ref().map(|a| a.address).unwrap_or(*TEST_ADDRESS);
 
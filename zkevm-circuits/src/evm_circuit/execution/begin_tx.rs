@@ -648,6 +648,7 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
                     precompile_input_bytes_rlc.expr(),
                     None,
                     None,
+                    None,
                 );
 
                 (precompile_gadget, precompile_input_bytes_rlc)
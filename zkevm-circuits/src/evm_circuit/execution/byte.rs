@@ -152,6 +152,16 @@ mod test {
         test_ok(256.into(), 0x030201.into());
     }
 
+    #[test]
+    fn byte_gadget_big_endian_and_out_of_range() {
+        let value =
+            Word::from_big_endian(&[[0xaa, 0xbb].as_slice(), &[0x11; 29], &[0xcc]].concat());
+        // The most significant byte, the least significant one and 0 past it.
+        test_ok(0.into(), value);
+        test_ok(31.into(), value);
+        test_ok(32.into(), value);
+    }
+
     #[test]
     fn byte_gadget_rand() {
         let index = rand_word();
@@ -105,4 +105,40 @@ mod test {
         )
         .run();
     }
+
+    #[test]
+    fn caller_gadget_in_delegatecall() {
+        use eth_types::ToWord;
+        use mock::{eth, MOCK_ACCOUNTS};
+
+        let callee_code = bytecode! {
+            CALLER
+            STOP
+        };
+        let code = bytecode! {
+            PUSH1(0) // retLength
+            PUSH1(0) // retOffset
+            PUSH1(0) // argsLength
+            PUSH1(0) // argsOffset
+            PUSH20(MOCK_ACCOUNTS[1].to_word())
+            GAS
+            DELEGATECALL
+            STOP
+        };
+        let ctx = TestContext::<3, 1>::new(
+            None,
+            |accs| {
+                accs[0].address(MOCK_ACCOUNTS[0]).code(code);
+                accs[1].address(MOCK_ACCOUNTS[1]).code(callee_code);
+                accs[2].address(MOCK_ACCOUNTS[2]).balance(eth(10));
+            },
+            |mut txs, accs| {
+                txs[0].from(accs[2].address).to(accs[0].address);
+            },
+            |block, _tx| block,
+        )
+        .unwrap();
+
+        CircuitTestBuilder::new_from_test_ctx(ctx).run();
+    }
 }
@@ -502,6 +502,7 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
                     precompile_input_bytes_rlc.expr(),
                     Some(precompile_output_bytes_rlc.expr()),
                     Some(precompile_return_bytes_rlc.expr()),
+                    Some(precompile_return_length.expr()),
                 );
 
                 (
@@ -1276,6 +1277,26 @@ mod test {
             });
     }
 
+    #[test]
+    fn callop_value_stipend_only_sstore_oog() {
+        // With no gas forwarded, the callee only gets the stipend of a value
+        // call, so its SSTORE runs out of gas while the caller continues.
+        let stack = Stack {
+            value: Word::one(),
+            ..Default::default()
+        };
+        let callee = callee(bytecode! {
+            PUSH1(1)
+            PUSH1(0)
+            SSTORE
+            STOP
+        });
+
+        for opcode in [OpcodeId::CALL, OpcodeId::CALLCODE] {
+            test_ok(caller(&opcode, stack, true), callee.clone(), None);
+        }
+    }
+
     #[test]
     fn callop_overflow_offset_and_zero_length() {
         let stack = Stack {
@@ -249,4 +249,32 @@ mod tests {
     fn codecopy_gadget_overflow_memory_offset_and_zero_size() {
         test_ok(0x102.into(), Word::MAX, 0, false);
     }
+
+    #[test]
+    fn codecopy_gadget_in_create() {
+        // The init code copies 0x20 bytes of itself, past its 8 bytes end.
+        let init_code = bytecode! {
+            PUSH1(0x20)
+            PUSH1(0)
+            PUSH1(0)
+            CODECOPY
+            STOP
+        };
+        let code = bytecode! {
+            PUSH8(Word::from_big_endian(&init_code.to_vec()))
+            PUSH1(0)
+            MSTORE
+
+            PUSH1(8)
+            PUSH1(24)
+            PUSH1(0)
+            CREATE
+            STOP
+        };
+
+        CircuitTestBuilder::new_from_test_ctx(
+            TestContext::<2, 1>::simple_ctx_with_bytecode(code).unwrap(),
+        )
+        .run();
+    }
 }
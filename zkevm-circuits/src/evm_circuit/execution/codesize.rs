@@ -127,4 +127,26 @@ mod tests {
     fn test_codesize_gadget_large() {
         test_ok(true);
     }
+
+    #[test]
+    fn test_codesize_gadget_in_create() {
+        // CREATE with init code CODESIZE STOP, stored at memory[30..32].
+        let code = bytecode! {
+            PUSH2(0x3800)
+            PUSH1(0)
+            MSTORE
+
+            PUSH1(2)
+            PUSH1(30)
+            PUSH1(0)
+            CREATE
+            CODESIZE
+            STOP
+        };
+
+        CircuitTestBuilder::new_from_test_ctx(
+            TestContext::<2, 1>::simple_ctx_with_bytecode(code).unwrap(),
+        )
+        .run();
+    }
 }
@@ -27,7 +27,7 @@ use crate::{
 };
 use bus_mapping::{circuit_input_builder::CopyDataType, evm::OpcodeId};
 use eth_types::{
-    evm_types::{GasCost, CREATE2_GAS_PER_CODE_WORD, CREATE_GAS_PER_CODE_WORD, MAX_INIT_CODE_SIZE},
+    evm_types::{GasCost, INIT_CODE_WORD_GAS, MAX_INIT_CODE_SIZE},
     state_db::CodeDB,
     ToBigEndian, ToLittleEndian, ToScalar, ToWord, H256, KECCAK_CODE_HASH_EMPTY, U256,
 };
@@ -238,15 +238,19 @@ impl<F: Field, const IS_CREATE2: bool, const S: ExecutionState> ExecutionGadget<
             init_code.length() + (N_BYTES_WORD - 1).expr(),
             N_BYTES_WORD as u64,
         );
-        let keccak_gas_cost = init_code_word_size.quotient()
-            * if IS_CREATE2 {
-                CREATE2_GAS_PER_CODE_WORD
-            } else {
-                CREATE_GAS_PER_CODE_WORD
-            }
-            .expr();
+        // EIP-3860 charges every init code word, and CREATE2 additionally pays
+        // for hashing the init code to derive the contract address.
+        let init_code_gas_cost = init_code_word_size.quotient() * INIT_CODE_WORD_GAS.expr();
+        let keccak_gas_cost = if IS_CREATE2 {
+            init_code_word_size.quotient() * GasCost::COPY_SHA3.expr()
+        } else {
+            0.expr()
+        };
 
-        let gas_cost = GasCost::CREATE.expr() + memory_expansion.gas_cost() + keccak_gas_cost;
+        let gas_cost = GasCost::CREATE.expr()
+            + memory_expansion.gas_cost()
+            + init_code_gas_cost
+            + keccak_gas_cost;
         let gas_remaining = cb.curr.state.gas_left.expr() - gas_cost.clone();
         let gas_left = ConstantDivisionGadget::construct(cb, gas_remaining.clone(), 64);
         let callee_gas_left = gas_remaining - gas_left.quotient();
@@ -695,14 +699,18 @@ impl<F: Field, const IS_CREATE2: bool, const S: ExecutionState> ExecutionGadget<
             F::from(MAX_INIT_CODE_SIZE + 1),
         )?;
 
-        let keccak_gas_cost = u64::try_from(init_code_word_size).unwrap()
-            * if IS_CREATE2 {
-                CREATE2_GAS_PER_CODE_WORD
-            } else {
-                CREATE_GAS_PER_CODE_WORD
-            };
-        let gas_left =
-            step.gas_left - GasCost::CREATE.as_u64() - memory_expansion_gas_cost - keccak_gas_cost;
+        let init_code_word_size = u64::try_from(init_code_word_size).unwrap();
+        let init_code_gas_cost = init_code_word_size * INIT_CODE_WORD_GAS;
+        let keccak_gas_cost = if IS_CREATE2 {
+            init_code_word_size * GasCost::COPY_SHA3.as_u64()
+        } else {
+            0
+        };
+        let gas_left = step.gas_left
+            - GasCost::CREATE.as_u64()
+            - memory_expansion_gas_cost
+            - init_code_gas_cost
+            - keccak_gas_cost;
         self.gas_left.assign(region, offset, gas_left.into())?;
 
         self.callee_reversion_info.assign(
@@ -997,6 +1005,29 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_create_init_code_sstore() {
+        for is_create2 in [false, true] {
+            // The init code writes to the storage of the contract being created.
+            let mut init_code = bytecode! {
+                PUSH1(0x2a)
+                PUSH1(0)
+                SSTORE
+            };
+            init_code.append(&initialization_bytecode(true));
+            let root_code = creator_bytecode(init_code, 23414.into(), is_create2, true);
+
+            let caller = Account {
+                address: *CALLER_ADDRESS,
+                code: root_code.into(),
+                nonce: Word::one(),
+                balance: eth(10),
+                ..Default::default()
+            };
+            run_test_circuits(test_context(caller));
+        }
+    }
+
     #[test]
     fn test_create_rlp_nonce() {
         for nonce in [0, 1, 127, 128, 255, 256, 0x10000, u64::MAX - 1] {
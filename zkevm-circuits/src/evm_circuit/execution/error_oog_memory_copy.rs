@@ -298,6 +298,11 @@ mod tests {
         test_for_edge_memory_size(0xffffffff1, 0xffffffff0);
     }
 
+    #[test]
+    fn test_oog_memory_copy_2_pow_32_address() {
+        test_for_edge_memory_size(1 << 32, 0x20);
+    }
+
     #[test]
     fn test_oog_memory_copy_max_u64_address() {
         test_for_edge_memory_size(u64::MAX, u64::MAX);
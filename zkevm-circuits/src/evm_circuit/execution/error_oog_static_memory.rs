@@ -216,6 +216,14 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_oog_static_memory_2_pow_32_address() {
+        for code in testing_bytecodes((1u64 << 32).into()).iter() {
+            test_root(code);
+            test_internal(code);
+        }
+    }
+
     #[test]
     fn test_oog_static_memory_max_expanded_address() {
         // > MAX_EXPANDED_MEMORY_ADDRESS (0x1fffffffe0)
@@ -143,7 +143,7 @@ impl<F: Field> ExecutionGadget<F> for ErrorWriteProtectionGadget<F> {
 
 #[cfg(test)]
 mod test {
-    use crate::test_util::CircuitTestBuilder;
+    use crate::{evm_circuit::step::ExecutionState, test_util::CircuitTestBuilder};
     use eth_types::{
         address, bytecode, bytecode::Bytecode, evm_types::OpcodeId, geth_types::Account, Address,
         ToWord, Word,
@@ -280,6 +280,79 @@ mod test {
         );
     }
 
+    // The static flag set by STATICCALL is inherited by a nested CALL, so an
+    // SSTORE two levels below the STATICCALL still hits the write protection.
+    #[test]
+    fn test_write_protection_nested_call() {
+        let middle = Address::repeat_byte(0xfd);
+        let leaf = Address::repeat_byte(0xff);
+        let caller_bytecode = bytecode! {
+            PUSH1(0)
+            PUSH1(0)
+            PUSH1(0)
+            PUSH1(0)
+            PUSH20(middle.to_word())
+            PUSH3(100000) // gas
+            STATICCALL
+            STOP
+        };
+        let middle_bytecode = bytecode! {
+            PUSH1(0)
+            PUSH1(0)
+            PUSH1(0)
+            PUSH1(0)
+            PUSH1(0) // zero value is allowed in a static context
+            PUSH20(leaf.to_word())
+            PUSH3(50000) // gas
+            CALL
+            STOP
+        };
+        let leaf_bytecode = bytecode! {
+            PUSH1(42)
+            PUSH1(0x02)
+            // this SSTORE got error: ErrorWriteProtection
+            SSTORE
+            STOP
+        };
+
+        let ctx = TestContext::<4, 1>::new(
+            None,
+            |accs| {
+                accs[0]
+                    .address(address!("0x000000000000000000000000000000000000cafe"))
+                    .balance(Word::from(10u64.pow(19)));
+                accs[1]
+                    .address(Address::repeat_byte(0xfe))
+                    .code(caller_bytecode);
+                accs[2].address(middle).code(middle_bytecode).nonce(1);
+                accs[3].address(leaf).code(leaf_bytecode).nonce(1);
+            },
+            |mut txs, accs| {
+                txs[0]
+                    .from(accs[0].address)
+                    .to(accs[1].address)
+                    .gas(200000.into());
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap();
+
+        CircuitTestBuilder::new_from_test_ctx(ctx)
+            .block_modifier(Box::new(|block| {
+                let tx = &block.txs[0];
+                let step = tx
+                    .steps
+                    .iter()
+                    .find(|step| step.execution_state == ExecutionState::ErrorWriteProtection)
+                    .expect("SSTORE should fail with write protection");
+                let call = &tx.calls[step.call_index];
+                assert_eq!(call.depth, 3);
+                assert!(call.is_static);
+                assert!(tx.calls.iter().skip(1).all(|call| call.is_static));
+            }))
+            .run();
+    }
+
     fn test_ok(caller: Account, callee: Account) {
         let ctx = TestContext::<3, 1>::new(
             None,
@@ -270,4 +270,18 @@ mod tests {
         test_ok(Word::MAX, 2.into());
         test_ok(Word::MAX, 3.into());
     }
+
+    #[test]
+    fn exp_gadget_gas_by_exponent_byte_size() {
+        // The gas cost is 10 + 50 * byte_size(exponent), with byte_size(0) == 0.
+        for exponent in [
+            Word::zero(),
+            Word::one(),
+            255.into(),
+            256.into(),
+            Word::one() << 255,
+        ] {
+            test_ok(3.into(), exponent);
+        }
+    }
 }
@@ -249,10 +249,14 @@ impl<F: Field> ExecutionGadget<F> for ExtcodecopyGadget<F> {
 
 #[cfg(test)]
 mod test {
-    use crate::{evm_circuit::test::rand_bytes_array, test_util::CircuitTestBuilder};
+    use crate::{
+        evm_circuit::{step::ExecutionState, test::rand_bytes_array},
+        test_util::CircuitTestBuilder,
+    };
     use bus_mapping::circuit_input_builder::CircuitsParams;
     use eth_types::{
-        address, bytecode, geth_types::Account, Address, Bytecode, Bytes, ToWord, Word,
+        address, bytecode, evm_types::GasCost, geth_types::Account, Address, Bytecode, Bytes,
+        ToWord, Word,
     };
     use mock::TestContext;
     use std::sync::LazyLock;
@@ -326,6 +330,52 @@ mod test {
             .run();
     }
 
+    #[test]
+    fn extcodecopy_gas_components() {
+        // Copy 0x40 bytes of a 2 byte code into fresh memory at 0x20.
+        let code = bytecode! {
+            PUSH1(0x40) // length
+            PUSH1(0x00) // code offset
+            PUSH1(0x20) // memory offset
+            PUSH20(EXTERNAL_ADDRESS.to_word())
+            EXTCODECOPY
+            STOP
+        };
+        let ctx = TestContext::<3, 1>::new(
+            None,
+            |accs| {
+                accs[0]
+                    .address(address!("0x000000000000000000000000000000000000cafe"))
+                    .code(code);
+                accs[1]
+                    .address(address!("0x0000000000000000000000000000000000000010"))
+                    .balance(Word::from(1u64 << 20));
+                accs[2].address(*EXTERNAL_ADDRESS).code([10, 40]);
+            },
+            |mut txs, accs| {
+                txs[0].to(accs[0].address).from(accs[1].address);
+            },
+            |block, _tx| block.number(0x1111111),
+        )
+        .unwrap();
+
+        // The memory expands to 3 words, the copy covers 2 words regardless of
+        // the code length, and the external account is cold.
+        let memory_expansion_gas = 3 * 3 + 3 * 3 / 512;
+        let copy_gas = 2 * GasCost::COPY.as_u64();
+        let expected_gas = memory_expansion_gas + copy_gas + GasCost::COLD_ACCOUNT_ACCESS.as_u64();
+        CircuitTestBuilder::new_from_test_ctx(ctx)
+            .block_modifier(Box::new(move |block| {
+                let step = block.txs[0]
+                    .steps
+                    .iter()
+                    .find(|step| step.execution_state == ExecutionState::EXTCODECOPY)
+                    .unwrap();
+                assert_eq!(step.gas_cost, expected_gas);
+            }))
+            .run();
+    }
+
     #[test]
     fn extcodecopy_empty_account() {
         test_ok(None, Word::zero(), Word::zero(), 0x36, true); // warm account
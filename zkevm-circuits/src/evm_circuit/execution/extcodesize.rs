@@ -164,7 +164,7 @@ impl<F: Field> ExecutionGadget<F> for ExtcodesizeGadget<F> {
 #[cfg(test)]
 mod test {
     use crate::{evm_circuit::test::rand_bytes, test_util::CircuitTestBuilder};
-    use eth_types::{bytecode, geth_types::Account, Bytecode, ToWord};
+    use eth_types::{bytecode, geth_types::Account, Address, Bytecode, ToWord};
     use mock::{
         generate_mock_call_bytecode, MockCallBytecodeParams, TestContext, MOCK_1_ETH,
         MOCK_ACCOUNTS, MOCK_CODES,
@@ -209,6 +209,17 @@ mod test {
         test_ok(&account, true);
     }
 
+    #[test]
+    fn test_extcodesize_gadget_precompile() {
+        // Identity precompile has no code and is already warm at tx begin.
+        let account = Account {
+            address: Address::from_low_u64_be(0x04),
+            ..Default::default()
+        };
+        test_ok(&account, false);
+        test_ok(&account, true);
+    }
+
     fn test_ok(account: &Account, is_warm: bool) {
         let account_exists = !account.is_empty();
 
@@ -96,4 +96,13 @@ mod test {
         test_ok(0x060504.into());
         test_ok(0x0.into());
     }
+
+    #[test]
+    fn is_zero_gadget_high_bits() {
+        // The value is the RLC of all 32 bytes, so a word with only its high
+        // bits set is not zero.
+        test_ok(Word::zero());
+        test_ok(Word::one());
+        test_ok(Word::one() << 255);
+    }
 }
@@ -231,7 +231,10 @@ impl<F: Field> ExecutionGadget<F> for MemoryGadget<F> {
 
 #[cfg(test)]
 mod test {
-    use crate::{evm_circuit::test::rand_word, test_util::CircuitTestBuilder};
+    use crate::{
+        evm_circuit::{step::ExecutionState, test::rand_word},
+        test_util::CircuitTestBuilder,
+    };
     use eth_types::{
         bytecode,
         evm_types::{GasCost, OpcodeId},
@@ -328,6 +331,34 @@ mod test {
         }
     }
 
+    #[test]
+    fn mstore8_expands_memory_to_one_byte() {
+        // At offset 1, MSTORE8 only needs memory up to byte 2 (one word) while
+        // MSTORE needs memory up to byte 33 (two words).
+        for (opcode, memory_word_size) in [(OpcodeId::MSTORE8, 1), (OpcodeId::MSTORE, 2)] {
+            let bytecode = bytecode! {
+                PUSH1(0xff)
+                PUSH1(1)
+                .write_op(opcode)
+                STOP
+            };
+            let ctx = TestContext::<2, 1>::simple_ctx_with_bytecode(bytecode).unwrap();
+            CircuitTestBuilder::new_from_test_ctx(ctx)
+                .block_modifier(Box::new(move |block| {
+                    let step = block.txs[0]
+                        .steps
+                        .iter()
+                        .find(|step| step.execution_state == ExecutionState::MEMORY)
+                        .unwrap();
+                    assert_eq!(
+                        step.gas_cost,
+                        GasCost::FASTEST.as_u64() + 3 * memory_word_size
+                    );
+                }))
+                .run();
+        }
+    }
+
     #[test]
     fn oog_static_memory_case() {
         test_ok(
@@ -99,4 +99,25 @@ mod test {
         )
         .run();
     }
+
+    #[test]
+    fn msize_gadget_word_aligned() {
+        let bytecode = bytecode! {
+            MSIZE
+            PUSH1(0x2a)
+            PUSH1(0)
+            MSTORE
+            MSIZE
+            PUSH1(0x2a)
+            PUSH1(40)
+            MSTORE
+            MSIZE
+            STOP
+        };
+
+        CircuitTestBuilder::new_from_test_ctx(
+            TestContext::<2, 1>::simple_ctx_with_bytecode(bytecode).unwrap(),
+        )
+        .run();
+    }
 }
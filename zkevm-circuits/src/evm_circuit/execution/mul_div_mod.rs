@@ -186,6 +186,13 @@ mod test {
         let a = Word::from_dec_str("3402823669209384634633746074317682114560").unwrap(); // 2**128 * 10
         let b = Word::from_dec_str("34028236692093846346337460743176821145500").unwrap(); // (2**128 - 1) * 100
         test_ok(OpcodeId::MUL, a, b);
+
+        // 2**128 * 2**128 == 2**256, which wraps to 0
+        let a = Word::one() << 128;
+        test_ok(OpcodeId::MUL, a, a);
+
+        // (2**256 - 1)**2 == 1 mod 2**256
+        test_ok(OpcodeId::MUL, Word::MAX, Word::MAX);
     }
 
     #[test]
@@ -208,6 +215,16 @@ mod test {
         );
     }
 
+    #[test]
+    fn div_gadget_edge_cases() {
+        // x / 0 = 0
+        test_ok(OpcodeId::DIV, Word::MAX, 0.into());
+        test_ok(OpcodeId::DIV, 0.into(), 0.into());
+        // (2^256 - 1) / 2 = 2^255 - 1
+        test_ok(OpcodeId::DIV, Word::MAX, 2.into());
+        test_ok(OpcodeId::DIV, Word::MAX, Word::MAX);
+    }
+
     #[test]
     fn div_gadget_rand() {
         let dividend = rand_word();
@@ -23,6 +23,7 @@ use halo2_proofs::plonk::Error;
 /// MulModGadget verifies opcode MULMOD
 /// Verify a * b = r (mod n)
 /// where a, b, n, r are 256-bit words
+/// The product a * b is kept as the 512-bit pair (d, e) and never truncated.
 #[derive(Clone, Debug)]
 pub(crate) struct MulModGadget<F> {
     same_context: SameContextGadget<F>,
@@ -166,8 +167,8 @@ impl<F: Field> ExecutionGadget<F> for MulModGadget<F> {
 
 #[cfg(test)]
 mod test {
-    use crate::test_util::CircuitTestBuilder;
-    use eth_types::{bytecode, Word, U256};
+    use crate::{evm_circuit::step::ExecutionState, test_util::CircuitTestBuilder};
+    use eth_types::{bytecode, Word, U256, U512};
     use mock::TestContext;
 
     #[cfg(feature = "enable-stack")]
@@ -208,6 +209,35 @@ mod test {
         ctb.run()
     }
 
+    // Checks the witnessed result against a reference MULMOD computed over
+    // the full 512-bit product.
+    fn test_reference(a: Word, b: Word, n: Word) {
+        let bytecode = bytecode! {
+            PUSH32(n)
+            PUSH32(b)
+            PUSH32(a)
+            MULMOD
+            STOP
+        };
+        let expected = if n.is_zero() {
+            Word::zero()
+        } else {
+            Word::try_from(a.full_mul(b) % U512::from(n)).unwrap()
+        };
+
+        let ctx = TestContext::<2, 1>::simple_ctx_with_bytecode(bytecode).unwrap();
+        CircuitTestBuilder::new_from_test_ctx(ctx)
+            .block_modifier(Box::new(move |block| {
+                let step = block.txs[0]
+                    .steps
+                    .iter()
+                    .find(|step| step.execution_state == ExecutionState::MULMOD)
+                    .unwrap();
+                assert_eq!(block.rws[step.rw_indices[3]].stack_value(), expected);
+            }))
+            .run()
+    }
+
     fn test_ok_u32(a: u32, b: u32, n: u32, r: Option<u32>) {
         test(a.into(), b.into(), n.into(), r.map(Word::from), true)
     }
@@ -255,6 +285,23 @@ mod test {
         );
     }
 
+    #[test]
+    fn mulmod_product_overflows_256_bits() {
+        let half = Word::one() << 128;
+        for (a, b, n) in [
+            (Word::MAX, Word::MAX, Word::MAX - 1),
+            (Word::MAX, Word::MAX, Word::from(12345u64)),
+            (Word::MAX, Word::from(2), Word::MAX),
+            (half, half, Word::MAX),
+            (half, half, half + 1),
+            (Word::MAX - 1, Word::MAX - 2, Word::from(u64::MAX)),
+            (Word::MAX, Word::MAX, Word::zero()),
+        ] {
+            assert!(a.full_mul(b) > U512::from(Word::MAX));
+            test_reference(a, b, n);
+        }
+    }
+
     #[test]
     fn mulmod_division_by_zero() {
         test_ok_u32(7, 1, 0, None);
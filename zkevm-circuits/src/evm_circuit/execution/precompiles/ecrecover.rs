@@ -105,6 +105,8 @@ impl<F: Field> ExecutionGadget<F> for EcrecoverGadget<F> {
         let sig_r = cb.query_word_rlc();
         let sig_r_canonical = LtWordGadget::construct(cb, &sig_r, &fq_modulus);
         let sig_s = cb.query_word_rlc();
+        // Unlike transaction signatures (EIP-2), the precompile accepts any s
+        // below the curve order, high s included.
         let sig_s_canonical = LtWordGadget::construct(cb, &sig_s, &fq_modulus);
         let r_s_canonical = and::expr([sig_r_canonical.expr(), sig_s_canonical.expr()]);
 
@@ -270,7 +272,10 @@ impl<F: Field> ExecutionGadget<F> for EcrecoverGadget<F> {
                 + (sig_r_keccak_rlc.expr() * r_pow_32)
                 + sig_s_keccak_rlc.expr(),
         );
-        // RLC of output bytes always equals RLC of the recovered address.
+        // RLC of output bytes always equals RLC of the recovered address. The
+        // output is 32 bytes with the address in the low 20, and since the RLC
+        // is accumulated big-endian, the 12 leading bytes only keep this RLC if
+        // they are all zero.
         cb.require_equal(
             "output bytes (RLC) = recovered address",
             output_bytes_rlc.expr(),
@@ -519,7 +524,7 @@ mod test {
         evm::{OpcodeId, PrecompileCallArgs},
         precompile::PrecompileCalls,
     };
-    use eth_types::{bytecode, word, ToWord};
+    use eth_types::{bytecode, word, ToWord, Word};
     use mock::TestContext;
     use rayon::{iter::ParallelIterator, prelude::IntoParallelRefIterator};
     use std::sync::LazyLock;
@@ -808,6 +813,35 @@ mod test {
                 address: PrecompileCalls::Ecrecover.address().to_word(),
                 ..Default::default()
             },
+            PrecompileCallArgs {
+                // EIP-2 only restricts s of transaction signatures, the
+                // precompile accepts any s below the curve order.
+                name: "ecrecover (high sig_s, addr recovered)",
+                setup_code: bytecode! {
+                    // msg hash from 0x00
+                    PUSH32(word!("0x456e9aea5e197a1f1af7a3e85a3212fa4049a3ba34c2289b4c860fc0b0c64ef3"))
+                    PUSH1(0x00)
+                    MSTORE
+                    // signature v from 0x20, flipped along with s
+                    PUSH1(27)
+                    PUSH1(0x20)
+                    MSTORE
+                    // signature r from 0x40
+                    PUSH32(word!("0x9242685bf161793cc25603c231bc2f568eb630ea16aa137d2664ac8038825608"))
+                    PUSH1(0x40)
+                    MSTORE
+                    // signature s from 0x60, n - s of the low-s signature above
+                    PUSH32(word!("0xb0751c428acadb72f42bb7d6733d1df79c5843b9a7d3c407b39bd3a37fb11667"))
+                    PUSH1(0x60)
+                    MSTORE
+                },
+                call_data_offset: 0x00.into(),
+                call_data_length: 0x80.into(),
+                ret_offset: 0x80.into(),
+                ret_size: 0x20.into(),
+                address: PrecompileCalls::Ecrecover.address().to_word(),
+                ..Default::default()
+            },
         ]
     });
 
@@ -887,4 +921,59 @@ mod test {
             }
         })
     }
+
+    #[test]
+    fn precompile_ecrecover_output_over_dirty_memory() {
+        // The return buffer is all 0xff before the call, so the 12 high bytes
+        // of the output must be written as zeros.
+        let mut setup_code = TEST_VECTOR[1].setup_code.clone();
+        setup_code
+            .push(32, Word::MAX)
+            .push(1, 0x80)
+            .write_op(OpcodeId::MSTORE);
+        let test_vector = PrecompileCallArgs {
+            name: "ecrecover (output over dirty memory)",
+            setup_code,
+            call_data_offset: 0x00.into(),
+            call_data_length: 0x80.into(),
+            ret_offset: 0x80.into(),
+            ret_size: 0x20.into(),
+            address: PrecompileCalls::Ecrecover.address().to_word(),
+            stack_value: vec![(
+                0x80.into(),
+                word!("0x7156526fbd7a3c72969b54f64e42c10fbb768c8a"),
+            )],
+            ..Default::default()
+        };
+        let bytecode = test_vector.with_call_op(OpcodeId::STATICCALL);
+
+        CircuitTestBuilder::new_from_test_ctx(
+            TestContext::<2, 1>::simple_ctx_with_bytecode(bytecode).unwrap(),
+        )
+        .run();
+    }
+
+    #[test]
+    fn precompile_ecrecover_gas_test() {
+        // Exactly the 3000 gas cost, 1 short of it (OOG) and 500 extra.
+        [3000u64, 2999, 3500].par_iter().for_each(|&gas| {
+            let test_vector = PrecompileCallArgs {
+                name: "ecrecover (gas)",
+                setup_code: OOG_TEST_VECTOR[0].setup_code.clone(),
+                call_data_offset: 0x00.into(),
+                call_data_length: 0x80.into(),
+                ret_offset: 0x80.into(),
+                ret_size: 0x20.into(),
+                gas: gas.into(),
+                address: PrecompileCalls::Ecrecover.address().to_word(),
+                ..Default::default()
+            };
+            let bytecode = test_vector.with_call_op(OpcodeId::STATICCALL);
+
+            CircuitTestBuilder::new_from_test_ctx(
+                TestContext::<2, 1>::simple_ctx_with_bytecode(bytecode).unwrap(),
+            )
+            .run();
+        })
+    }
 }
@@ -11,8 +11,8 @@ use crate::{
         step::ExecutionState,
         util::{
             common_gadget::RestoreContextGadget,
-            constraint_builder::{ConstrainBuilderCommon, EVMConstraintBuilder},
-            math_gadget::ConstantDivisionGadget,
+            constraint_builder::EVMConstraintBuilder,
+            math_gadget::{BytesEqualGadget, ConstantDivisionGadget},
             rlc, CachedRegion, Cell,
         },
     },
@@ -25,8 +25,10 @@ pub struct IdentityGadget<F> {
     input_bytes_rlc: Cell<F>,
     output_bytes_rlc: Cell<F>,
     return_bytes_rlc: Cell<F>,
+    output_bytes_len: Cell<F>,
 
     input_word_size: ConstantDivisionGadget<F, N_BYTES_MEMORY_WORD_SIZE>,
+    input_output_equal: BytesEqualGadget<F>,
     is_success: Cell<F>,
     callee_address: Cell<F>,
     is_root: Cell<F>,
@@ -48,6 +50,8 @@ impl<F: Field> ExecutionGadget<F> for IdentityGadget<F> {
             cb.query_cell_phase2(),
             cb.query_cell_phase2(),
         );
+        // must be the first Phase1 cell, see PrecompileGadget.
+        let output_bytes_len = cb.query_cell();
         let [is_success, callee_address, is_root, call_data_offset, call_data_length, return_data_offset, return_data_length] =
             [
                 CallContextFieldTag::IsSuccess,
@@ -79,10 +83,11 @@ impl<F: Field> ExecutionGadget<F> for IdentityGadget<F> {
             cb.execution_state().precompile_base_gas_cost().expr(),
         );
 
-        cb.require_equal(
-            "input and output bytes are the same",
-            input_bytes_rlc.expr(),
-            output_bytes_rlc.expr(),
+        // The output is a copy of the input.
+        let input_output_equal = BytesEqualGadget::construct(
+            cb,
+            (input_bytes_rlc.expr(), call_data_length.expr()),
+            (output_bytes_rlc.expr(), output_bytes_len.expr()),
         );
 
         let restore_context = super::gen_restore_context(
@@ -97,8 +102,10 @@ impl<F: Field> ExecutionGadget<F> for IdentityGadget<F> {
             input_bytes_rlc,
             output_bytes_rlc,
             return_bytes_rlc,
+            output_bytes_len,
 
             input_word_size,
+            input_output_equal,
             is_success,
             callee_address,
             is_root,
@@ -131,7 +138,7 @@ impl<F: Field> ExecutionGadget<F> for IdentityGadget<F> {
                 region
                     .challenges()
                     .keccak_input()
-                    .map(|r| rlc::value(input_bytes.iter().rev(), r)),
+                    .map(|r| rlc::value_be(input_bytes, r)),
             )?;
             self.output_bytes_rlc.assign(
                 region,
@@ -139,7 +146,7 @@ impl<F: Field> ExecutionGadget<F> for IdentityGadget<F> {
                 region
                     .challenges()
                     .keccak_input()
-                    .map(|r| rlc::value(output_bytes.iter().rev(), r)),
+                    .map(|r| rlc::value_be(output_bytes, r)),
             )?;
             self.return_bytes_rlc.assign(
                 region,
@@ -147,7 +154,12 @@ impl<F: Field> ExecutionGadget<F> for IdentityGadget<F> {
                 region
                     .challenges()
                     .keccak_input()
-                    .map(|r| rlc::value(return_bytes.iter().rev(), r)),
+                    .map(|r| rlc::value_be(return_bytes, r)),
+            )?;
+            self.output_bytes_len.assign(
+                region,
+                offset,
+                Value::known(F::from(output_bytes.len() as u64)),
             )?;
         } else {
             log::error!("unexpected aux_data {:?} for identity", step.aux_data);
@@ -197,10 +209,10 @@ impl<F: Field> ExecutionGadget<F> for IdentityGadget<F> {
 
 #[cfg(test)]
 mod test {
-    use crate::test_util::CircuitTestBuilder;
+    use crate::{evm_circuit::step::ExecutionState, test_util::CircuitTestBuilder};
     use bus_mapping::{
         evm::{OpcodeId, PrecompileCallArgs},
-        precompile::PrecompileCalls,
+        precompile::{PrecompileAuxData, PrecompileCalls},
     };
     use eth_types::{bytecode, word, ToWord};
     use itertools::Itertools;
@@ -280,4 +292,33 @@ mod test {
             .run();
         }
     }
+
+    #[test]
+    fn precompile_identity_output_length_mismatch() {
+        let bytecode = TEST_VECTOR[1].with_call_op(OpcodeId::STATICCALL);
+
+        CircuitTestBuilder::new_from_test_ctx(
+            TestContext::<2, 1>::simple_ctx_with_bytecode(bytecode).unwrap(),
+        )
+        .block_modifier(Box::new(|block| {
+            // A leading zero byte keeps RLC(output_bytes) unchanged, only the length differs
+            // from the input.
+            let step = block.txs[0]
+                .steps
+                .iter_mut()
+                .find(|step| step.execution_state == ExecutionState::PrecompileIdentity)
+                .unwrap();
+            if let Some(PrecompileAuxData::Identity { output_bytes, .. }) = &mut step.aux_data {
+                output_bytes.insert(0, 0);
+            } else {
+                unreachable!();
+            }
+        }))
+        .evm_checks(Some(Box::new(|prover, gate_rows, lookup_rows| {
+            assert!(prover
+                .verify_at_rows_par(gate_rows.iter().cloned(), lookup_rows.iter().cloned())
+                .is_err())
+        })))
+        .run();
+    }
 }
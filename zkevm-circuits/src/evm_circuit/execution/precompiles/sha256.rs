@@ -212,7 +212,7 @@ mod test {
         evm::{OpcodeId, PrecompileCallArgs},
         precompile::PrecompileCalls,
     };
-    use eth_types::{bytecode, word, ToWord};
+    use eth_types::{bytecode, word, ToBigEndian, ToWord};
     use itertools::Itertools;
     use mock::TestContext;
     use std::sync::LazyLock;
@@ -375,4 +375,67 @@ mod test {
             .run();
         }
     }
+
+    #[test]
+    fn precompile_sha256_known_digests() {
+        let a32 = word!("0x6161616161616161616161616161616161616161616161616161616161616161");
+        let test_vectors = [
+            (
+                PrecompileCallArgs {
+                    name: "empty input",
+                    setup_code: bytecode! {},
+                    call_data_offset: 0x00.into(),
+                    call_data_length: 0x00.into(),
+                    ret_offset: 0x00.into(),
+                    ret_size: 0x20.into(),
+                    address: PrecompileCalls::Sha256.address().to_word(),
+                    ..Default::default()
+                },
+                word!("0xe3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"),
+            ),
+            (
+                // 128 bytes of "a", i.e. 2 full blocks before the padding.
+                PrecompileCallArgs {
+                    name: "multi-block input",
+                    setup_code: bytecode! {
+                        PUSH32(a32)
+                        PUSH1(0x00)
+                        MSTORE
+                        PUSH32(a32)
+                        PUSH1(0x20)
+                        MSTORE
+                        PUSH32(a32)
+                        PUSH1(0x40)
+                        MSTORE
+                        PUSH32(a32)
+                        PUSH1(0x60)
+                        MSTORE
+                    },
+                    call_data_offset: 0x00.into(),
+                    call_data_length: 0x80.into(),
+                    ret_offset: 0x80.into(),
+                    ret_size: 0x20.into(),
+                    address: PrecompileCalls::Sha256.address().to_word(),
+                    ..Default::default()
+                },
+                word!("0x6836cf13bac400e9105071cd6af47084dfacad4e5e302c94bfed24e013afb73e"),
+            ),
+        ];
+
+        for (test_vector, digest) in test_vectors {
+            let bytecode = test_vector.with_call_op(OpcodeId::STATICCALL);
+            let input_len = test_vector.call_data_length.as_usize();
+
+            CircuitTestBuilder::new_from_test_ctx(
+                TestContext::<2, 1>::simple_ctx_with_bytecode(bytecode).unwrap(),
+            )
+            .block_modifier(Box::new(move |blk| {
+                let evts = blk.get_sha256();
+                assert_eq!(evts.len(), 1);
+                assert_eq!(evts[0].input.len(), input_len);
+                assert_eq!(evts[0].digest, digest.to_be_bytes());
+            }))
+            .run();
+        }
+    }
 }
@@ -136,6 +136,21 @@ mod test {
         test_ok(OpcodeId::PUSH16, &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
     }
 
+    #[test]
+    fn push_gadget_truncated_at_code_end() {
+        // No trailing STOP, so the immediate really runs past the end of code.
+        let mut bytecode = bytecode! {
+            .write_op(OpcodeId::PUSH4)
+        };
+        bytecode.write(0xaa, false);
+        bytecode.write(0xbb, false);
+
+        CircuitTestBuilder::new_from_test_ctx(
+            TestContext::<2, 1>::simple_ctx_with_bytecode(bytecode).unwrap(),
+        )
+        .run();
+    }
+
     #[test]
     fn push_gadget_rand() {
         for (idx, opcode) in vec![
@@ -79,14 +79,17 @@ impl<F: Field> ExecutionGadget<F> for SignedDivModGadget<F> {
             (1.expr() - remainder_abs_lt_divisor_abs.expr()) * (1.expr() - divisor_is_zero.expr()),
         );
 
+        // The remainder always takes the sign of the dividend. This must also
+        // hold when quotient is zero (e.g. `-2 % 3 == -2`), otherwise only
+        // `|remainder| == |dividend|` would be enforced.
         cb.condition(
-            (1.expr() - quotient_is_zero.expr())
-            * (1.expr() - divisor_is_zero.expr())
-            * (1.expr() - remainder_is_zero.expr()),
-            |cb| cb.add_constraint(
-                "sign(dividend) == sign(remainder) when quotient, divisor and remainder are all non-zero",
-                dividend_abs_word.is_neg().expr() - remainder_abs_word.is_neg().expr(),
-            )
+            (1.expr() - divisor_is_zero.expr()) * (1.expr() - remainder_is_zero.expr()),
+            |cb| {
+                cb.add_constraint(
+                    "sign(dividend) == sign(remainder) when divisor and remainder are both non-zero",
+                    dividend_abs_word.is_neg().expr() - remainder_abs_word.is_neg().expr(),
+                )
+            },
         );
 
         // For a special `SDIV` case, when input `dividend = -(1 << 255)` and
@@ -245,7 +248,11 @@ fn is_neg(x: U256) -> bool {
 
 #[cfg(test)]
 mod test {
-    use crate::{evm_circuit::test::rand_word, test_util::CircuitTestBuilder};
+    use crate::{
+        evm_circuit::{step::ExecutionState, test::rand_word},
+        test_util::CircuitTestBuilder,
+        witness::Rw,
+    };
     use eth_types::{bytecode, evm_types::OpcodeId, Word};
     use mock::TestContext;
 
@@ -295,6 +302,22 @@ mod test {
         );
     }
 
+    #[test]
+    fn sdiv_gadget_edge_cases() {
+        let neg = |x: u64| Word::zero().overflowing_sub(Word::from(x)).0;
+        let int_min = Word::one() << 255;
+        // (-2^255) / (-1) overflows back to -2^255
+        test_ok(OpcodeId::SDIV, int_min, neg(1));
+        // 7 / (-2) == -3, truncated toward zero
+        test_ok(OpcodeId::SDIV, 7.into(), neg(2));
+        // (-7) / 2 == -3
+        test_ok(OpcodeId::SDIV, neg(7), 2.into());
+        // x / 0 == 0
+        test_ok(OpcodeId::SDIV, 7.into(), 0.into());
+        test_ok(OpcodeId::SDIV, neg(7), 0.into());
+        test_ok(OpcodeId::SDIV, int_min, 0.into());
+    }
+
     #[test]
     fn sdiv_gadget_rand() {
         let dividend = rand_word();
@@ -332,6 +355,60 @@ mod test {
         );
     }
 
+    #[test]
+    fn smod_gadget_sign_follows_dividend() {
+        let neg = |x: u64| Word::zero().overflowing_sub(Word::from(x)).0;
+        // (-8) % 3 == -2
+        test_ok(OpcodeId::SMOD, neg(8), 3.into());
+        // 8 % (-3) == 2
+        test_ok(OpcodeId::SMOD, 8.into(), neg(3));
+        // (-8) % (-3) == -2
+        test_ok(OpcodeId::SMOD, neg(8), neg(3));
+        // (-2) % 3 == -2, quotient is zero
+        test_ok(OpcodeId::SMOD, neg(2), 3.into());
+        // x % 0 == 0
+        test_ok(OpcodeId::SMOD, neg(8), 0.into());
+        test_ok(OpcodeId::SMOD, 8.into(), 0.into());
+    }
+
+    #[test]
+    fn smod_gadget_zero_quotient_wrong_remainder_sign() {
+        let neg = |x: u64| Word::zero().overflowing_sub(Word::from(x)).0;
+        let bytecode = bytecode! {
+            PUSH32(3)
+            PUSH32(neg(2))
+            #[start]
+            SMOD
+            STOP
+        };
+
+        CircuitTestBuilder::new_from_test_ctx(
+            TestContext::<2, 1>::simple_ctx_with_bytecode(bytecode).unwrap(),
+        )
+        .block_modifier(Box::new(|block| {
+            // (-2) % 3 == -2, with a zero quotient. Pushing 2 instead keeps
+            // |remainder| == |dividend| but gives the remainder the wrong sign.
+            let step = block.txs[0]
+                .steps
+                .iter()
+                .find(|step| step.execution_state == ExecutionState::SDIV_SMOD)
+                .unwrap();
+            let (tag, idx) = step.rw_indices[2];
+            if let Rw::Stack { value, .. } = &mut block.rws.0.get_mut(&tag).unwrap()[idx] {
+                *value = 2.into();
+            } else {
+                unreachable!();
+            }
+        }))
+        .state_checks(None)
+        .evm_checks(Some(Box::new(|prover, gate_rows, lookup_rows| {
+            assert!(prover
+                .verify_at_rows_par(gate_rows.iter().cloned(), lookup_rows.iter().cloned())
+                .is_err())
+        })))
+        .run();
+    }
+
     #[test]
     fn smod_gadget_rand() {
         let dividend = rand_word();
@@ -300,6 +300,18 @@ mod test {
         test_ok(vec![(OpcodeId::SLT, a, a), (OpcodeId::SGT, a, a)]);
     }
 
+    #[test]
+    fn signed_comparator_gadget_neg_vs_pos() {
+        let minus_1 = Word::MAX;
+        let min_neg = Word::one() << 255;
+        test_ok(vec![
+            (OpcodeId::SLT, minus_1, Word::zero()),
+            (OpcodeId::SGT, minus_1, Word::zero()),
+            (OpcodeId::LT, min_neg, Word::one()),
+            (OpcodeId::SLT, min_neg, Word::one()),
+        ]);
+    }
+
     #[test]
     fn signed_comparator_gadget_rand() {
         let a = rand_word();
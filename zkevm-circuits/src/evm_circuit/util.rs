@@ -662,11 +662,52 @@ pub(crate) mod rlc {
             .into_iter()
             .map(|v| F::from(*v as u64))
             .collect::<Vec<F>>();
-        if !values.is_empty() {
-            generic(values, randomness)
+        let rlc = if !values.is_empty() {
+            generic(values.iter().copied(), randomness)
         } else {
             F::zero()
+        };
+
+        #[cfg(feature = "debug-rlc")]
+        assert_eq!(
+            rlc,
+            reference_value(&values, randomness),
+            "rlc of {values:?} with randomness {randomness:?} diverges from the reference"
+        );
+
+        rlc
+    }
+
+    /// Naive evaluation of v_0 * R^0 + v_1 * R^1 + ..., cross-checked against
+    /// [`value`] when the `debug-rlc` feature is enabled.
+    #[cfg(any(feature = "debug-rlc", test))]
+    fn reference_value<F: Field>(values: &[F], randomness: F) -> F {
+        let mut power = F::one();
+        let mut rlc = F::zero();
+        for value in values {
+            rlc += *value * power;
+            power *= randomness;
         }
+        rlc
+    }
+
+    /// Little-endian RLC: v_0 * R^0 + v_1 * R^1 + ..., i.e. [`value`].
+    pub(crate) fn value_le<'a, F: Field, I>(values: I, randomness: F) -> F
+    where
+        I: IntoIterator<Item = &'a u8>,
+        <I as IntoIterator>::IntoIter: DoubleEndedIterator,
+    {
+        value(values, randomness)
+    }
+
+    /// Big-endian RLC: v_0 * R^(n-1) + ... + v_(n-1) * R^0, as accumulated
+    /// byte by byte (acc * R + v) by the copy circuit.
+    pub(crate) fn value_be<'a, F: Field, I>(values: I, randomness: F) -> F
+    where
+        I: IntoIterator<Item = &'a u8>,
+        <I as IntoIterator>::IntoIter: DoubleEndedIterator,
+    {
+        value(values.into_iter().rev(), randomness)
     }
 
     fn generic<V, I>(values: I, randomness: V) -> V
@@ -680,6 +721,45 @@ pub(crate) mod rlc {
 
         values.fold(init, |acc, value| acc * randomness.clone() + value)
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::evm_circuit::test::rand_bytes;
+        use halo2_proofs::halo2curves::bn256::Fr;
+
+        #[test]
+        fn rlc_value_be_is_value_le_reversed() {
+            let bytes = [0x01u8, 0x02, 0x03, 0xff];
+            let r = Fr::from(0x100);
+            assert_eq!(value_be(&bytes, r), value_le(bytes.iter().rev(), r));
+            assert_eq!(value_le(&bytes, r), Fr::from(0xff030201));
+            assert_eq!(value_be(&bytes, r), Fr::from(0x010203ff));
+
+            // The copy circuit accumulation.
+            let r = Fr::from(0xcafe);
+            assert_eq!(
+                value_be(&bytes, r),
+                bytes
+                    .iter()
+                    .fold(Fr::from(0), |acc, byte| acc * r + Fr::from(*byte as u64))
+            );
+            assert_eq!(value_be::<Fr, _>(&[], r), Fr::from(0));
+        }
+
+        #[test]
+        fn rlc_value_matches_reference() {
+            for len in [0, 1, 2, 31, 32, 33, 100] {
+                let bytes = rand_bytes(len);
+                let r = Fr::from(rand::random::<u64>());
+                let values = bytes
+                    .iter()
+                    .map(|byte| Fr::from(*byte as u64))
+                    .collect::<Vec<_>>();
+                assert_eq!(value(&bytes, r), reference_value(&values, r));
+            }
+        }
+    }
 }
 
 /// Returns 2**by as Field
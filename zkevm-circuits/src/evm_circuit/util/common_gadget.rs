@@ -1,13 +1,13 @@
 use super::{
     constraint_builder::ConstrainBuilderCommon,
     from_bytes,
-    math_gadget::{IsEqualGadget, IsZeroGadget, LtGadget},
+    math_gadget::{IsEqualGadget, IsZeroGadget, LtGadget, WordToAddressGadget},
     memory_gadget::{CommonMemoryAddressGadget, MemoryExpansionGadget},
     CachedRegion,
 };
 use crate::{
     evm_circuit::{
-        param::{N_BYTES_ACCOUNT_ADDRESS, N_BYTES_GAS, N_BYTES_MEMORY_WORD_SIZE, N_BYTES_U64},
+        param::{N_BYTES_GAS, N_BYTES_MEMORY_WORD_SIZE, N_BYTES_U64},
         step::ExecutionState,
         table::{FixedTableTag, Lookup},
         util::{
@@ -1056,7 +1056,7 @@ pub(crate) struct CommonCallGadget<F, MemAddrGadget, const IS_SUCCESS_CALL: bool
 
     pub gas: Word<F>,
     pub gas_is_u64: IsZeroGadget<F>,
-    pub callee_address: Word<F>,
+    pub callee_address: WordToAddressGadget<F>,
     pub value: Word<F>,
     pub cd_address: MemAddrGadget,
     pub rd_address: MemAddrGadget,
@@ -1094,7 +1094,9 @@ impl<F: Field, MemAddrGadget: CommonMemoryAddressGadget<F>, const IS_SUCCESS_CAL
         );
 
         let gas_word = cb.query_word_rlc();
-        let callee_address_word = cb.query_word_rlc();
+        // The EVM ignores the high 12 bytes of the callee address word, so they
+        // must not be constrained to zero here.
+        let callee_address_word = WordToAddressGadget::construct(cb, false);
         let value = cb.query_word_rlc();
         let is_success = cb.query_bool();
 
@@ -1143,7 +1145,7 @@ impl<F: Field, MemAddrGadget: CommonMemoryAddressGadget<F>, const IS_SUCCESS_CAL
 
         let phase2_callee_code_hash = cb.query_cell_with_type(CellType::StoragePhase2);
         cb.account_read(
-            from_bytes::expr(&callee_address_word.cells[..N_BYTES_ACCOUNT_ADDRESS]),
+            callee_address_word.address(),
             AccountFieldTag::CodeHash,
             phase2_callee_code_hash.expr(),
         );
@@ -1173,7 +1175,7 @@ impl<F: Field, MemAddrGadget: CommonMemoryAddressGadget<F>, const IS_SUCCESS_CAL
     }
 
     pub fn callee_address_expr(&self) -> Expression<F> {
-        from_bytes::expr(&self.callee_address.cells[..N_BYTES_ACCOUNT_ADDRESS])
+        self.callee_address.address()
     }
 
     pub fn gas_expr(&self) -> Expression<F> {
@@ -1213,8 +1215,7 @@ impl<F: Field, MemAddrGadget: CommonMemoryAddressGadget<F>, const IS_SUCCESS_CAL
         phase2_callee_code_hash: Value<F>,
     ) -> Result<u64, Error> {
         self.gas.assign(region, offset, Some(gas.to_le_bytes()))?;
-        self.callee_address
-            .assign(region, offset, Some(callee_address.to_le_bytes()))?;
+        self.callee_address.assign(region, offset, callee_address)?;
         self.value
             .assign(region, offset, Some(value.to_le_bytes()))?;
         if IS_SUCCESS_CALL {
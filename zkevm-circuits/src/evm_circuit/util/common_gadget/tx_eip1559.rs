@@ -9,7 +9,9 @@ use crate::{
     evm_circuit::{
         util::{
             constraint_builder::{ConstrainBuilderCommon, EVMConstraintBuilder},
-            math_gadget::{AddWordsGadget, IsEqualGadget, LtWordGadget, MulWordByU64Gadget},
+            math_gadget::{
+                AddWordsGadget, IsEqualGadget, LtWordGadget, MinMaxWordGadget, MulWordByU64Gadget,
+            },
             sum, Expr, Word,
         },
         witness::Transaction,
@@ -18,7 +20,6 @@ use crate::{
     util::Field,
 };
 use eth_types::{geth_types::TxType, ToLittleEndian, U256};
-use gadgets::util::select;
 use halo2_proofs::plonk::{Error, Expression};
 
 /// Transaction EIP-1559 gadget to check sender balance before transfer
@@ -29,9 +30,8 @@ pub(crate) struct TxEip1559Gadget<F> {
     gas_fee_cap: Word<F>,
     // MaxPriorityFeePerGas
     gas_tip_cap: Word<F>,
-    // condition for min(
-    // gas_tip_cap, gas_fee_cap - base_fee_per_gas)
-    gas_tip_cap_lt_gas_fee_cap_minus_base_fee: LtWordGadget<F>,
+    // min(gas_tip_cap, gas_fee_cap - base_fee_per_gas)
+    priority_fee_per_gas: MinMaxWordGadget<F>,
     // gas_fee_cap - base_fee_per_gas
     gas_sub_base_fee: AddWordsGadget<F, 2, true>,
     // check tx_gas_price = effective_gas_price = priority_fee_per_gas +
@@ -78,7 +78,7 @@ impl<F: Field> TxEip1559Gadget<F> {
             gas_fee_cap_lt_gas_tip_cap,
             base_fee,
             gas_fee_cap_lt_base_fee,
-            gas_tip_cap_lt_gas_fee_cap_minus_base_fee,
+            priority_fee_per_gas,
             gas_sub_base_fee,
             effective_gas_price_check,
         ) = cb.condition(is_eip1559_tx.expr(), |cb| {
@@ -112,13 +112,10 @@ impl<F: Field> TxEip1559Gadget<F> {
             // );
             let gas_fee_cap_minus_base_fee_per_gas = cb.query_word_rlc();
             let gas_fee_cap_minus_base_fee_per_gas_check = AddWordsGadget::construct(cb, [base_fee.clone(), gas_fee_cap_minus_base_fee_per_gas.clone()], gas_fee_cap.clone());
-            let tip_comparator = LtWordGadget::construct(cb, &gas_tip_cap, &gas_fee_cap_minus_base_fee_per_gas);
+            let priority_fee_per_gas_min = MinMaxWordGadget::construct(cb, &gas_tip_cap, &gas_fee_cap_minus_base_fee_per_gas);
             // let effective_gas_price = priority_fee_per_gas + base_fee_per_gas;
             let priority_fee_per_gas = cb.query_word_rlc();
-            cb.require_equal("constrain priority_fee_per_gas = min(gas_tip_cap, gas_fee_cap - base_fee_per_gas)", priority_fee_per_gas.expr(), select::expr(
-                tip_comparator.expr(),
-                gas_tip_cap.expr(),
-                gas_fee_cap_minus_base_fee_per_gas.expr()));
+            cb.require_equal("constrain priority_fee_per_gas = min(gas_tip_cap, gas_fee_cap - base_fee_per_gas)", priority_fee_per_gas.expr(), priority_fee_per_gas_min.min());
             // constrain tx_gas_price = effective_gas_price within below `AddWordsGadget`.
             let effective_gas_price_check = AddWordsGadget::construct(cb, [base_fee.clone(), priority_fee_per_gas], tx_gas_price.clone());
 
@@ -138,7 +135,7 @@ impl<F: Field> TxEip1559Gadget<F> {
                 gas_fee_cap_lt_gas_tip_cap,
                 base_fee,
                 gas_fee_cap_lt_base_fee,
-                tip_comparator,
+                priority_fee_per_gas_min,
                 gas_fee_cap_minus_base_fee_per_gas_check,
                 effective_gas_price_check,
             )
@@ -148,7 +145,7 @@ impl<F: Field> TxEip1559Gadget<F> {
             is_eip1559_tx,
             gas_fee_cap,
             gas_tip_cap,
-            gas_tip_cap_lt_gas_fee_cap_minus_base_fee,
+            priority_fee_per_gas,
             gas_sub_base_fee,
             effective_gas_price_check,
             mul_gas_fee_cap_by_gas,
@@ -183,14 +180,13 @@ impl<F: Field> TxEip1559Gadget<F> {
             Some(tx.max_priority_fee_per_gas.to_le_bytes()),
         )?;
         let diff_gas_base_fee = tx.max_fee_per_gas - base_fee;
-        let priority_fee_per_gas = tx.max_priority_fee_per_gas.min(diff_gas_base_fee);
         self.gas_sub_base_fee.assign(
             region,
             offset,
             [base_fee, diff_gas_base_fee],
             tx.max_fee_per_gas,
         )?;
-        self.gas_tip_cap_lt_gas_fee_cap_minus_base_fee.assign(
+        let (priority_fee_per_gas, _) = self.priority_fee_per_gas.assign(
             region,
             offset,
             tx.max_priority_fee_per_gas,
@@ -299,6 +295,42 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_eip1559_tx_effective_gas_price() {
+        let base_fee = gwei(1);
+        for (max_fee_per_gas, max_priority_fee_per_gas, effective_gas_price) in [
+            // the tip is capped by `max_fee_per_gas - base_fee`
+            (gwei(2), gwei(2), gwei(2)),
+            // the whole tip is paid
+            (gwei(3), gwei(1), gwei(2)),
+        ] {
+            let ctx = TestContext::<2, 1>::new(
+                None,
+                |accs| {
+                    accs[0].address(MOCK_WALLETS[0].address()).balance(eth(1));
+                    accs[1].address(MOCK_ACCOUNTS[0]).balance(eth(1));
+                },
+                |mut txs, _accs| {
+                    txs[0]
+                        .from(MOCK_WALLETS[0].clone())
+                        .to(MOCK_ACCOUNTS[0])
+                        .gas(30_000.into())
+                        .max_fee_per_gas(max_fee_per_gas)
+                        .max_priority_fee_per_gas(max_priority_fee_per_gas)
+                        .transaction_type(2); // Set tx type to EIP-1559.
+                },
+                |block, _tx| block.number(0xcafeu64).base_fee_per_gas(base_fee),
+            )
+            .unwrap();
+            assert_eq!(
+                ctx.eth_block.transactions[0].gas_price,
+                Some(effective_gas_price)
+            );
+
+            CircuitTestBuilder::new_from_test_ctx(ctx).run();
+        }
+    }
+
     fn build_ctx(
         sender_balance: Word,
         max_fee_per_gas: Word,
@@ -6,6 +6,7 @@ mod add_words;
 mod batched_is_zero;
 mod binary_number;
 mod byte_size;
+mod bytes_equal;
 mod cmp_words;
 mod comparison;
 mod constant_division;
@@ -17,12 +18,15 @@ mod min_max;
 mod modulo;
 mod mul_add_words;
 mod mul_add_words512;
+mod mul_word;
 mod mul_word_u64;
 mod pair_select;
 mod range_check;
 mod rlp;
+mod select_by_index;
 #[cfg(test)]
 pub mod test_util;
+mod word_to_address;
 
 pub(crate) use abs_word::AbsWordGadget;
 pub(crate) use add_words::AddWordsGadget;
@@ -30,6 +34,7 @@ pub(crate) use add_words::AddWordsGadget;
 pub(crate) use batched_is_zero::BatchedIsZeroGadget;
 pub(crate) use binary_number::BinaryNumberGadget;
 pub(crate) use byte_size::{BitLengthGadget, ByteOrWord, ByteSizeGadget};
+pub(crate) use bytes_equal::BytesEqualGadget;
 pub(crate) use cmp_words::CmpWordsGadget;
 pub(crate) use comparison::ComparisonGadget;
 pub(crate) use constant_division::ConstantDivisionGadget;
@@ -37,7 +42,7 @@ pub(crate) use is_equal::IsEqualGadget;
 pub(crate) use is_zero::IsZeroGadget;
 pub(crate) use lt::LtGadget;
 pub(crate) use lt_word::LtWordGadget;
-pub(crate) use min_max::MinMaxGadget;
+pub(crate) use min_max::{MinMaxGadget, MinMaxWordGadget};
 pub(crate) use modulo::ModGadget;
 pub(crate) use mul_add_words::MulAddWordsGadget;
 pub(crate) use mul_add_words512::MulAddWords512Gadget;
@@ -45,6 +50,7 @@ pub(crate) use mul_word_u64::MulWordByU64Gadget;
 pub(crate) use pair_select::PairSelectGadget;
 pub(crate) use range_check::RangeCheckGadget;
 pub(crate) use rlp::ContractCreateGadget;
+pub(crate) use word_to_address::WordToAddressGadget;
 
 // This function generates a Lagrange polynomial in the range [start, end) which
 // will be evaluated to 1 when `exp == value`, otherwise 0
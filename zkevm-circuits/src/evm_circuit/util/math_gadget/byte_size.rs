@@ -276,6 +276,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_bytesize_255_eq_1() {
+        try_test!(ByteSizeGadgetContainer<Fr, 1>, [Word::from(255)], true)
+    }
+
+    #[test]
+    fn test_bytesize_2_pow_255_eq_32() {
+        try_test!(ByteSizeGadgetContainer<Fr, 32>, [Word::one() << 255], true)
+    }
+
     #[test]
     fn test_bytesize_wordmax_eq_32() {
         try_test!(ByteSizeGadgetContainer<Fr, 32>, [Word::MAX], true)
@@ -0,0 +1,128 @@
+use crate::{
+    evm_circuit::util::constraint_builder::{ConstrainBuilderCommon, EVMConstraintBuilder},
+    util::Field,
+};
+use halo2_proofs::plonk::Expression;
+use std::marker::PhantomData;
+
+/// Requires two byte arrays, given as their RLC and length, to be equal.
+/// Comparing the RLCs alone isn't enough, since leading zero bytes don't
+/// change a big-endian RLC, e.g. `[0x00, 0xab]` and `[0xab]` collide.
+#[derive(Clone, Debug)]
+pub(crate) struct BytesEqualGadget<F> {
+    _marker: PhantomData<F>,
+}
+
+impl<F: Field> BytesEqualGadget<F> {
+    /// `lhs` and `rhs` are `(bytes_rlc, bytes_length)` pairs.
+    pub(crate) fn construct(
+        cb: &mut EVMConstraintBuilder<F>,
+        lhs: (Expression<F>, Expression<F>),
+        rhs: (Expression<F>, Expression<F>),
+    ) -> Self {
+        cb.require_equal("bytes have the same length", lhs.1, rhs.1);
+        cb.require_equal("bytes have the same rlc", lhs.0, rhs.0);
+
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::*, *};
+    use crate::{
+        evm_circuit::util::{CachedRegion, Cell},
+        util::Expr,
+    };
+    use eth_types::{ToScalar, Word};
+    use halo2_proofs::{circuit::Value, halo2curves::bn256::Fr, plonk::Error};
+
+    #[derive(Clone)]
+    /// BytesEqualTestContainer: require(lhs == rhs)
+    struct BytesEqualTestContainer<F> {
+        _bytes_equal_gadget: BytesEqualGadget<F>,
+        // lhs rlc, lhs length, rhs rlc, rhs length
+        cells: [Cell<F>; 4],
+    }
+
+    impl<F: Field> MathGadgetContainer<F> for BytesEqualTestContainer<F> {
+        fn configure_gadget_container(cb: &mut EVMConstraintBuilder<F>) -> Self {
+            let cells = [(); 4].map(|_| cb.query_cell());
+            let _bytes_equal_gadget = BytesEqualGadget::construct(
+                cb,
+                (cells[0].expr(), cells[1].expr()),
+                (cells[2].expr(), cells[3].expr()),
+            );
+
+            BytesEqualTestContainer {
+                _bytes_equal_gadget,
+                cells,
+            }
+        }
+
+        fn assign_gadget_container(
+            &self,
+            witnesses: &[Word],
+            region: &mut CachedRegion<'_, '_, F>,
+        ) -> Result<(), Error> {
+            let offset = 0;
+            for (cell, witness) in self.cells.iter().zip(witnesses) {
+                cell.assign(region, offset, Value::known(witness.to_scalar().unwrap()))?;
+            }
+            Ok(())
+        }
+    }
+
+    // The RLCs below use 256 as randomness, so the RLC of a big-endian byte
+    // array is the number it encodes.
+
+    #[test]
+    fn test_bytes_equal() {
+        try_test!(
+            BytesEqualTestContainer<Fr>,
+            [
+                Word::from(0xabcd),
+                Word::from(2),
+                Word::from(0xabcd),
+                Word::from(2)
+            ],
+            true,
+        );
+        try_test!(
+            BytesEqualTestContainer<Fr>,
+            [Word::zero(), Word::zero(), Word::zero(), Word::zero()],
+            true,
+        );
+    }
+
+    #[test]
+    fn test_bytes_equal_different_bytes() {
+        try_test!(
+            BytesEqualTestContainer<Fr>,
+            [
+                Word::from(0xabcd),
+                Word::from(2),
+                Word::from(0xabce),
+                Word::from(2)
+            ],
+            false,
+        );
+    }
+
+    #[test]
+    fn test_bytes_equal_same_rlc_different_length() {
+        // [0xab, 0xcd] vs [0x00, 0xab, 0xcd]
+        try_test!(
+            BytesEqualTestContainer<Fr>,
+            [
+                Word::from(0xabcd),
+                Word::from(2),
+                Word::from(0xabcd),
+                Word::from(3)
+            ],
+            false,
+        );
+    }
+}
@@ -19,6 +19,10 @@ use halo2_proofs::{
 /// - `quotient < 256**N_BYTES`
 /// - `quotient * denominator < field size`
 /// - `remainder < denominator` requires a range lookup table for `denominator`
+///
+/// The largest numerator that can be handled is therefore
+/// `denominator * 256**N_BYTES - 1`, `assign` returns an error for any numerator
+/// beyond it rather than assigning a truncated quotient.
 #[derive(Clone, Debug)]
 pub struct ConstantDivisionGadget<F, const N_BYTES: usize> {
     quotient: Cell<F>,
@@ -77,6 +81,18 @@ impl<F: Field, const N_BYTES: usize> ConstantDivisionGadget<F, N_BYTES> {
         let quotient = numerator / denominator;
         let remainder = numerator % denominator;
 
+        // quotient must fit in N_BYTES, otherwise the range check of quotient fails
+        if quotient
+            .checked_shr((N_BYTES * 8) as u32)
+            .unwrap_or_default()
+            != 0
+        {
+            log::error!(
+                "ConstantDivisionGadget numerator {numerator} overflows: quotient {quotient} exceeds {N_BYTES} bytes (denominator {denominator})"
+            );
+            return Err(Error::Synthesis);
+        }
+
         self.quotient
             .assign(region, offset, Value::known(F::from_u128(quotient)))?;
         self.remainder
@@ -202,11 +218,28 @@ mod tests {
     }
 
     #[test]
-    fn test_constantdivisiongadget_quotient_overflow() {
+    fn test_constantdivisiongadget_max_numerator() {
+        // 5 * 256^4 - 1 = 5 * (256^4 - 1) + 4
         try_test!(
+            ConstantDivisionTestContainer<Fr, 4, 5, 4294967295u64, 4>,
+            [Word::from(1u64 << (4 * 8)) * 5 - 1],
+            true,
+        );
+    }
+
+    #[test]
+    fn test_constantdivisiongadget_max_numerator_plus_one() {
+        try_assign_error!(
+            ConstantDivisionTestContainer<Fr, 4, 5, 4294967296u64, 0>,
+            [Word::from(1u64 << (4 * 8)) * 5],
+        );
+    }
+
+    #[test]
+    fn test_constantdivisiongadget_quotient_overflow() {
+        try_assign_error!(
             ConstantDivisionTestContainer<Fr, 4, 5, 4294967296u64, 1>,
             [Word::from(1u64 << (4 * 8)) * 5 + 1],
-            false,
         );
     }
 
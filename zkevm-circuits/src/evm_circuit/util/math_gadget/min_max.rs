@@ -1,10 +1,11 @@
 use crate::{
     evm_circuit::util::{
-        constraint_builder::EVMConstraintBuilder, math_gadget::*, select, transpose_val_ret,
+        self, constraint_builder::EVMConstraintBuilder, math_gadget::*, select, transpose_val_ret,
         CachedRegion,
     },
     util::Field,
 };
+use eth_types::Word;
 use halo2_proofs::{
     circuit::Value,
     plonk::{Error, Expression},
@@ -69,11 +70,53 @@ impl<F: Field, const N_BYTES: usize> MinMaxGadget<F, N_BYTES> {
     }
 }
 
+/// Same as [`MinMaxGadget`] for lhs and rhs of 256 bits, `min` and `max`
+/// are the RLC of the selected word.
+#[derive(Clone, Debug)]
+pub struct MinMaxWordGadget<F> {
+    lt: LtWordGadget<F>,
+    min: Expression<F>,
+    max: Expression<F>,
+}
+
+impl<F: Field> MinMaxWordGadget<F> {
+    pub(crate) fn construct(
+        cb: &mut EVMConstraintBuilder<F>,
+        lhs: &util::Word<F>,
+        rhs: &util::Word<F>,
+    ) -> Self {
+        let lt = LtWordGadget::construct(cb, lhs, rhs);
+        let max = select::expr(lt.expr(), rhs.expr(), lhs.expr());
+        let min = select::expr(lt.expr(), lhs.expr(), rhs.expr());
+
+        Self { lt, min, max }
+    }
+
+    pub(crate) fn min(&self) -> Expression<F> {
+        self.min.clone()
+    }
+
+    pub(crate) fn max(&self) -> Expression<F> {
+        self.max.clone()
+    }
+
+    pub(crate) fn assign(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        lhs: Word,
+        rhs: Word,
+    ) -> Result<(Word, Word), Error> {
+        self.lt.assign(region, offset, lhs, rhs)?;
+        Ok(if lhs < rhs { (lhs, rhs) } else { (rhs, lhs) })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{test_util::*, *};
     use crate::evm_circuit::util::{constraint_builder::ConstrainBuilderCommon, Cell};
-    use eth_types::{ToScalar, Word};
+    use eth_types::{ToLittleEndian, ToScalar, Word};
     use halo2_proofs::{halo2curves::bn256::Fr, plonk::Error};
 
     #[derive(Clone)]
@@ -142,6 +185,12 @@ mod tests {
             [WORD_LOW_MAX, WORD_LOW_MAX],
             true,
         );
+        // with equal values either operand is both the min and the max
+        try_test!(
+            MinMaxTestContainer<Fr, 4, false>,
+            [Word::from(5), Word::from(5)],
+            true,
+        );
     }
 
     #[test]
@@ -205,4 +254,100 @@ mod tests {
             true,
         );
     }
+
+    #[derive(Clone)]
+    /// MinMaxWordTestContainer: require(min(a, b) == (a if MIN_IS_A else b))
+    struct MinMaxWordTestContainer<F, const MIN_IS_A: bool> {
+        minmax_word_gadget: MinMaxWordGadget<F>,
+        a: util::Word<F>,
+        b: util::Word<F>,
+    }
+
+    impl<F: Field, const MIN_IS_A: bool> MathGadgetContainer<F>
+        for MinMaxWordTestContainer<F, MIN_IS_A>
+    {
+        fn configure_gadget_container(cb: &mut EVMConstraintBuilder<F>) -> Self {
+            let a = cb.query_word_rlc();
+            let b = cb.query_word_rlc();
+            let minmax_word_gadget = MinMaxWordGadget::construct(cb, &a, &b);
+
+            if MIN_IS_A {
+                cb.require_equal("min == a", minmax_word_gadget.min(), a.expr());
+                cb.require_equal("max == b", minmax_word_gadget.max(), b.expr());
+            } else {
+                cb.require_equal("min == b", minmax_word_gadget.min(), b.expr());
+                cb.require_equal("max == a", minmax_word_gadget.max(), a.expr());
+            }
+
+            MinMaxWordTestContainer {
+                minmax_word_gadget,
+                a,
+                b,
+            }
+        }
+
+        fn assign_gadget_container(
+            &self,
+            witnesses: &[Word],
+            region: &mut CachedRegion<'_, '_, F>,
+        ) -> Result<(), Error> {
+            let offset = 0;
+
+            self.a
+                .assign(region, offset, Some(witnesses[0].to_le_bytes()))?;
+            self.b
+                .assign(region, offset, Some(witnesses[1].to_le_bytes()))?;
+            self.minmax_word_gadget
+                .assign(region, offset, witnesses[0], witnesses[1])?;
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_minmax_word_expect_min_a() {
+        try_test!(
+            MinMaxWordTestContainer<Fr, true>,
+            [Word::from(3), Word::from(5)],
+            true,
+        );
+        try_test!(
+            MinMaxWordTestContainer<Fr, true>,
+            [WORD_LOW_MAX, WORD_HIGH_MAX],
+            true,
+        );
+        try_test!(
+            MinMaxWordTestContainer<Fr, true>,
+            [Word::MAX, Word::from(5)],
+            false,
+        );
+    }
+
+    #[test]
+    fn test_minmax_word_expect_min_b() {
+        try_test!(
+            MinMaxWordTestContainer<Fr, false>,
+            [Word::MAX, WORD_HIGH_MAX],
+            true,
+        );
+        try_test!(
+            MinMaxWordTestContainer<Fr, false>,
+            [Word::from(3), Word::from(5)],
+            false,
+        );
+    }
+
+    #[test]
+    fn test_minmax_word_eq() {
+        try_test!(
+            MinMaxWordTestContainer<Fr, true>,
+            [Word::MAX, Word::MAX],
+            true,
+        );
+        try_test!(
+            MinMaxWordTestContainer<Fr, false>,
+            [Word::MAX, Word::MAX],
+            true,
+        );
+    }
 }
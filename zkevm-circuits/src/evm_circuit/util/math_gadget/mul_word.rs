@@ -0,0 +1,194 @@
+use crate::{
+    evm_circuit::util::{
+        self,
+        constraint_builder::{ConstrainBuilderCommon, EVMConstraintBuilder},
+        math_gadget::MulAddWordsGadget,
+        sum, CachedRegion,
+    },
+    util::Field,
+};
+use eth_types::{ToLittleEndian, Word};
+use halo2_proofs::plonk::{Error, Expression};
+
+/// Construction of the product of 2 256-bit words modulo 2^256, which is
+/// useful for opcode MUL and fee computations.
+///
+/// The 512-bit product is split as a * b == product_hi * 2^256 + product by
+/// [`MulAddWordsGadget`] with a zero addend, where product is the low half
+/// and the result, and product_hi is the discarded high half.
+#[derive(Clone, Debug)]
+pub(crate) struct MulWordGadget<F> {
+    factors: [util::Word<F>; 2],
+    product: util::Word<F>,
+    zero: util::Word<F>,
+    mul_add_words: MulAddWordsGadget<F>,
+}
+
+impl<F: Field> MulWordGadget<F> {
+    pub(crate) fn construct(
+        cb: &mut EVMConstraintBuilder<F>,
+        factors: [util::Word<F>; 2],
+        product: util::Word<F>,
+    ) -> Self {
+        let zero = cb.query_word_rlc();
+        cb.require_zero("addend == 0", sum::expr(&zero.cells));
+        let mul_add_words =
+            MulAddWordsGadget::construct(cb, [&factors[0], &factors[1], &zero, &product]);
+
+        Self {
+            factors,
+            product,
+            zero,
+            mul_add_words,
+        }
+    }
+
+    pub(crate) fn assign(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        factors: [Word; 2],
+        product: Word,
+    ) -> Result<(), Error> {
+        for (word, value) in self.factors.iter().zip(factors.iter()) {
+            word.assign(region, offset, Some(value.to_le_bytes()))?;
+        }
+        self.product
+            .assign(region, offset, Some(product.to_le_bytes()))?;
+        self.zero.assign(region, offset, Some([0; 32]))?;
+
+        self.mul_add_words.assign(
+            region,
+            offset,
+            [factors[0], factors[1], Word::zero(), product],
+        )
+    }
+
+    pub(crate) fn factors(&self) -> &[util::Word<F>] {
+        &self.factors
+    }
+
+    pub(crate) fn product(&self) -> &util::Word<F> {
+        &self.product
+    }
+
+    /// The high 256 bits of the product, which are discarded.
+    pub(crate) fn product_hi(&self) -> Expression<F> {
+        self.mul_add_words.overflow()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::*, *};
+    use crate::util::Expr;
+    use eth_types::{ToScalar, Word, U256};
+    use halo2_proofs::{circuit::Value, halo2curves::bn256::Fr, plonk::Error};
+
+    #[derive(Clone)]
+    /// MulWordTestContainer: require(product == a * b mod 2^256) and
+    /// require(product_hi == a * b / 2^256)
+    struct MulWordTestContainer<F> {
+        mulword_gadget: MulWordGadget<F>,
+        product_hi: util::Cell<F>,
+    }
+
+    impl<F: Field> MathGadgetContainer<F> for MulWordTestContainer<F> {
+        fn configure_gadget_container(cb: &mut EVMConstraintBuilder<F>) -> Self {
+            let factors = [cb.query_word_rlc(), cb.query_word_rlc()];
+            let product = cb.query_word_rlc();
+            let product_hi = cb.query_cell();
+            let mulword_gadget = MulWordGadget::construct(cb, factors, product);
+            cb.require_equal(
+                "product_hi is correct",
+                mulword_gadget.product_hi(),
+                product_hi.expr(),
+            );
+
+            MulWordTestContainer {
+                mulword_gadget,
+                product_hi,
+            }
+        }
+
+        fn assign_gadget_container(
+            &self,
+            witnesses: &[Word],
+            region: &mut CachedRegion<'_, '_, F>,
+        ) -> Result<(), Error> {
+            let product_hi = witnesses[0].full_mul(witnesses[1]) >> 256;
+            let product_hi = Word::try_from(product_hi).unwrap();
+            self.product_hi
+                .assign(region, 0, Value::known(product_hi.to_scalar().unwrap()))?;
+            self.mulword_gadget
+                .assign(region, 0, [witnesses[0], witnesses[1]], witnesses[2])
+        }
+    }
+
+    #[test]
+    fn test_mulword_small() {
+        try_test!(
+            MulWordTestContainer<Fr>,
+            [Word::from(0), Word::from(7), Word::from(0)],
+            true
+        );
+        try_test!(
+            MulWordTestContainer<Fr>,
+            [Word::from(100), Word::from(54), Word::from(5400)],
+            true
+        );
+        try_test!(
+            MulWordTestContainer<Fr>,
+            [WORD_LOW_MAX, Word::from(1), WORD_LOW_MAX],
+            true
+        );
+    }
+
+    #[test]
+    fn test_mulword_exactly_2_pow_256() {
+        // 2^128 * 2^128 == 2^256 wraps to 0
+        let pow_128 = U256::one() << 128;
+        try_test!(
+            MulWordTestContainer<Fr>,
+            [pow_128, pow_128, Word::from(0)],
+            true
+        );
+        // 2^255 * 2 == 2^256 wraps to 0
+        try_test!(
+            MulWordTestContainer<Fr>,
+            [U256::one() << 255, Word::from(2), Word::from(0)],
+            true
+        );
+    }
+
+    #[test]
+    fn test_mulword_wraparound() {
+        // (2^256 - 1) * 3 == 2 * 2^256 + 2^256 - 3
+        try_test!(
+            MulWordTestContainer<Fr>,
+            [Word::MAX, Word::from(3), Word::MAX - 2],
+            true
+        );
+        // (2^255 + 1) * 4 == 2 * 2^256 + 4
+        try_test!(
+            MulWordTestContainer<Fr>,
+            [(U256::one() << 255) + 1, Word::from(4), Word::from(4)],
+            true
+        );
+    }
+
+    #[test]
+    fn test_mulword_wrong_product() {
+        // the full product is not a valid result
+        try_test!(
+            MulWordTestContainer<Fr>,
+            [U256::one() << 255, Word::from(2), Word::from(1)],
+            false
+        );
+        try_test!(
+            MulWordTestContainer<Fr>,
+            [Word::from(100), Word::from(54), Word::from(5401)],
+            false
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use crate::{
+    evm_circuit::util::{
+        constraint_builder::{ConstrainBuilderCommon, EVMConstraintBuilder},
+        sum, CachedRegion, Cell,
+    },
+    util::{Expr, Field},
+};
+use halo2_proofs::{
+    circuit::Value,
+    plonk::{Error, Expression},
+};
+
+/// Returns `values[index]` for a dynamic `index` in `0..N`.
+/// The index is decomposed into `N` one-hot boolean cells, so `index` is
+/// required to be in range.
+#[derive(Clone, Debug)]
+pub(crate) struct SelectByIndexGadget<F, const N: usize> {
+    one_hot: [Cell<F>; N],
+    selected: Expression<F>,
+}
+
+impl<F: Field, const N: usize> SelectByIndexGadget<F, N> {
+    pub(crate) fn construct(
+        cb: &mut EVMConstraintBuilder<F>,
+        index: Expression<F>,
+        values: [Expression<F>; N],
+    ) -> Self {
+        let one_hot = [(); N].map(|_| cb.query_bool());
+
+        cb.require_equal(
+            "exactly one value is selected",
+            sum::expr(&one_hot),
+            1.expr(),
+        );
+        cb.require_equal(
+            "the selected position is index",
+            sum::expr(
+                one_hot
+                    .iter()
+                    .enumerate()
+                    .map(|(i, is_selected)| i.expr() * is_selected.expr()),
+            ),
+            index,
+        );
+
+        let selected = sum::expr(
+            one_hot
+                .iter()
+                .zip(values)
+                .map(|(is_selected, value)| is_selected.expr() * value),
+        );
+
+        Self { one_hot, selected }
+    }
+
+    pub(crate) fn expr(&self) -> Expression<F> {
+        self.selected.clone()
+    }
+
+    pub(crate) fn assign(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        index: usize,
+    ) -> Result<(), Error> {
+        for (i, is_selected) in self.one_hot.iter().enumerate() {
+            is_selected.assign(region, offset, Value::known(F::from((i == index) as u64)))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::*, *};
+    use eth_types::{ToScalar, Word};
+    use halo2_proofs::{halo2curves::bn256::Fr, plonk::Error};
+
+    const VALUES: [u64; 4] = [7, 11, 13, 17];
+
+    #[derive(Clone)]
+    /// SelectByIndexTestContainer: require(value == VALUES[index])
+    struct SelectByIndexTestContainer<F> {
+        select_gadget: SelectByIndexGadget<F, 4>,
+        index: Cell<F>,
+        value: Cell<F>,
+    }
+
+    impl<F: Field> MathGadgetContainer<F> for SelectByIndexTestContainer<F> {
+        fn configure_gadget_container(cb: &mut EVMConstraintBuilder<F>) -> Self {
+            let index = cb.query_cell();
+            let value = cb.query_cell();
+            let select_gadget =
+                SelectByIndexGadget::construct(cb, index.expr(), VALUES.map(|value| value.expr()));
+            cb.require_equal("value == VALUES[index]", value.expr(), select_gadget.expr());
+
+            SelectByIndexTestContainer {
+                select_gadget,
+                index,
+                value,
+            }
+        }
+
+        fn assign_gadget_container(
+            &self,
+            witnesses: &[Word],
+            region: &mut CachedRegion<'_, '_, F>,
+        ) -> Result<(), Error> {
+            let offset = 0;
+            self.index.assign(
+                region,
+                offset,
+                Value::known(witnesses[0].to_scalar().unwrap()),
+            )?;
+            self.value.assign(
+                region,
+                offset,
+                Value::known(witnesses[1].to_scalar().unwrap()),
+            )?;
+            self.select_gadget
+                .assign(region, offset, witnesses[0].as_usize())
+        }
+    }
+
+    #[test]
+    fn test_select_by_index() {
+        for (index, value) in VALUES.into_iter().enumerate() {
+            try_test!(
+                SelectByIndexTestContainer<Fr>,
+                [Word::from(index), Word::from(value)],
+                true,
+            );
+        }
+    }
+
+    #[test]
+    fn test_select_by_index_wrong_value() {
+        try_test!(
+            SelectByIndexTestContainer<Fr>,
+            [Word::from(1), Word::from(VALUES[2])],
+            false,
+        );
+    }
+
+    #[test]
+    fn test_select_by_index_out_of_range() {
+        try_test!(
+            SelectByIndexTestContainer<Fr>,
+            [Word::from(4), Word::from(0)],
+            false,
+        );
+    }
+}
@@ -284,6 +284,18 @@ pub(crate) fn test_math_gadget_container<F: Field, G: MathGadgetContainer<F>>(
     }
 }
 
+/// Same as [`test_math_gadget_container`], but the gadget is expected to reject
+/// the witnesses already during assignment, so the circuit can't even be
+/// synthesized.
+pub(crate) fn test_math_gadget_container_assign_error<F: Field, G: MathGadgetContainer<F>>(
+    witnesses: Vec<Word>,
+) {
+    const K: usize = 12;
+    let circuit = UnitTestMathGadgetBaseCircuit::<G>::new(K, witnesses);
+
+    assert!(MockProver::<F>::run(K as u32, &circuit, vec![]).is_err());
+}
+
 /// A simple macro for less code & better readability
 macro_rules! try_test {
     ($base_class:ty, $witnesses:expr, $expect_success:expr $(,)?) => {{
@@ -291,5 +303,14 @@ macro_rules! try_test {
     }};
 }
 
+/// Like `try_test`, but expects the assignment of the witnesses to fail
+macro_rules! try_assign_error {
+    ($base_class:ty, $witnesses:expr $(,)?) => {{
+        test_math_gadget_container_assign_error::<Fr, $base_class>($witnesses.to_vec())
+    }};
+}
+
+#[cfg(test)]
+pub(crate) use try_assign_error;
 #[cfg(test)]
 pub(crate) use try_test;
@@ -0,0 +1,157 @@
+use crate::{
+    evm_circuit::{
+        param::N_BYTES_ACCOUNT_ADDRESS,
+        util::{
+            self,
+            constraint_builder::{ConstrainBuilderCommon, EVMConstraintBuilder},
+            from_bytes, sum, CachedRegion,
+        },
+    },
+    util::{Expr, Field},
+};
+use eth_types::{ToLittleEndian, Word};
+use halo2_proofs::plonk::{Error, Expression};
+
+/// Truncates a 256-bit word to an address by taking its low 20 bytes.
+/// The high 12 bytes are still part of the word (so the full value can be
+/// popped from the stack), but they are ignored by the derived address, as
+/// the EVM does. In `strict` mode they are required to be zero instead,
+/// rejecting "dirty" addresses. Opcodes taking an address operand, such as
+/// BALANCE or the CALL family, must use the non-strict mode, since a word with
+/// dirty high bytes is a valid operand for them.
+#[derive(Clone, Debug)]
+pub(crate) struct WordToAddressGadget<F> {
+    word: util::Word<F>,
+}
+
+impl<F: Field> WordToAddressGadget<F> {
+    pub(crate) fn construct(cb: &mut EVMConstraintBuilder<F>, strict: bool) -> Self {
+        let word = cb.query_word_rlc();
+        if strict {
+            cb.require_zero(
+                "high bytes of address word are zero",
+                sum::expr(&word.cells[N_BYTES_ACCOUNT_ADDRESS..]),
+            );
+        }
+
+        Self { word }
+    }
+
+    pub(crate) fn assign(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        word: Word,
+    ) -> Result<(), Error> {
+        self.word.assign(region, offset, Some(word.to_le_bytes()))
+    }
+
+    /// Address made of the low 20 bytes of the word.
+    pub(crate) fn address(&self) -> Expression<F> {
+        from_bytes::expr(&self.word.cells[..N_BYTES_ACCOUNT_ADDRESS])
+    }
+}
+
+impl<F: Field> Expr<F> for WordToAddressGadget<F> {
+    /// RLC of the full 256-bit word.
+    fn expr(&self) -> Expression<F> {
+        self.word.expr()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::*, *};
+    use crate::evm_circuit::util::Cell;
+    use eth_types::{Address, ToScalar, ToWord};
+    use halo2_proofs::{circuit::Value, halo2curves::bn256::Fr};
+
+    #[derive(Clone)]
+    /// WordToAddressGadgetContainer: require(address == word & (2^160 - 1)),
+    /// and require(word < 2^160) when STRICT
+    struct WordToAddressGadgetContainer<F, const STRICT: bool> {
+        word_to_address_gadget: WordToAddressGadget<F>,
+        address: Cell<F>,
+    }
+
+    impl<F: Field, const STRICT: bool> MathGadgetContainer<F>
+        for WordToAddressGadgetContainer<F, STRICT>
+    {
+        fn configure_gadget_container(cb: &mut EVMConstraintBuilder<F>) -> Self {
+            let word_to_address_gadget = WordToAddressGadget::<F>::construct(cb, STRICT);
+            let address = cb.query_cell();
+            cb.require_equal(
+                "address is the low 20 bytes of the word",
+                word_to_address_gadget.address(),
+                address.expr(),
+            );
+            WordToAddressGadgetContainer {
+                word_to_address_gadget,
+                address,
+            }
+        }
+
+        fn assign_gadget_container(
+            &self,
+            witnesses: &[Word],
+            region: &mut CachedRegion<'_, '_, F>,
+        ) -> Result<(), Error> {
+            let offset = 0;
+            let word = witnesses[0];
+            let address = Address::from_slice(&witnesses[1].to_be_bytes()[12..]);
+            self.word_to_address_gadget.assign(region, offset, word)?;
+            self.address
+                .assign(region, offset, Value::known(address.to_scalar().unwrap()))?;
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_word_to_address_low_bytes() {
+        let address = Address::repeat_byte(0xab);
+        try_test!(
+            WordToAddressGadgetContainer<Fr, false>,
+            [address.to_word(), address.to_word()],
+            true,
+        );
+    }
+
+    #[test]
+    fn test_word_to_address_ignores_high_bytes() {
+        let address = Address::repeat_byte(0xab);
+        let word = (Word::from(0xdeadbeefu64) << 160) | address.to_word();
+        try_test!(
+            WordToAddressGadgetContainer<Fr, false>,
+            [word, address.to_word()],
+            true,
+        );
+    }
+
+    #[test]
+    fn test_word_to_address_unexpected() {
+        let address = Address::repeat_byte(0xab);
+        let word = (Word::from(0xdeadbeefu64) << 160) | address.to_word();
+        try_test!(
+            WordToAddressGadgetContainer<Fr, false>,
+            [word, Address::repeat_byte(0xcd).to_word()],
+            false,
+        );
+    }
+
+    #[test]
+    fn test_word_to_address_strict() {
+        let address = Address::repeat_byte(0xab);
+        try_test!(
+            WordToAddressGadgetContainer<Fr, true>,
+            [address.to_word(), address.to_word()],
+            true,
+        );
+        let word = (Word::from(0xdeadbeefu64) << 160) | address.to_word();
+        try_test!(
+            WordToAddressGadgetContainer<Fr, true>,
+            [word, address.to_word()],
+            false,
+        );
+    }
+}
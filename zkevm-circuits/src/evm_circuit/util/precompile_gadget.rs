@@ -24,6 +24,8 @@ impl<F: Field> PrecompileGadget<F> {
         // for root call we do not need to constraint output and return
         output_bytes_rlc: Option<Expression<F>>,
         return_bytes_rlc: Option<Expression<F>>,
+        // length of the output bytes, only constrained for the identity precompile
+        output_bytes_len: Option<Expression<F>>,
     ) -> Self {
         let address = BinaryNumberGadget::construct(cb, callee_address.expr());
 
@@ -50,8 +52,8 @@ impl<F: Field> PrecompileGadget<F> {
         constrain_next_state!(cb, Bn128Pairing, PrecompileBn256Pairing);
         constrain_next_state!(cb, Blake2F, PrecompileBlake2f);
 
-        // Without constraining the next step's state, only constrain the first two Phase2 cells,
-        // i.e. RLC(input_bytes) and RLC(return_bytes)
+        // Without constraining the next step's state, only constrain the first three Phase2 cells,
+        // i.e. RLC(input_bytes), RLC(output_bytes) and RLC(return_bytes)
         // We only check these constraints if there was no OOG error in the precompile call.
         let is_oog_err = cb
             .next
@@ -81,6 +83,17 @@ impl<F: Field> PrecompileGadget<F> {
                     return_bytes_rlc.expr(),
                 );
             }
+            // The identity precompile allocates len(output_bytes) as its first Phase1 cell.
+            if let Some(output_bytes_len) = output_bytes_len {
+                let next_output_bytes_len = cb.query_cell();
+                cb.condition(address.value_equals(PrecompileCalls::Identity), |cb| {
+                    cb.require_equal(
+                        "equality: len(output_bytes)",
+                        next_output_bytes_len.expr(),
+                        output_bytes_len.expr(),
+                    );
+                });
+            }
         });
 
         Self { address }
@@ -109,6 +109,21 @@ fn exp_circuit_multiple() {
     ]);
 }
 
+#[test]
+fn exp_circuit_events() {
+    let code = gen_code_multiple(vec![(3.into(), 4.into()), (2.into(), 10.into())]);
+    let builder = gen_data(code);
+    let block = block_convert::<Fr>(&builder.block, &builder.code_db).unwrap();
+
+    assert_eq!(
+        block.exp_events(),
+        vec![
+            (Word::from(3), Word::from(4), Word::from(81)),
+            (Word::from(2), Word::from(10), Word::from(1024)),
+        ]
+    );
+}
+
 #[test]
 fn variadic_size_check() {
     let k = 20;
@@ -428,6 +428,29 @@ pub struct SubcircuitRowUsage {
     pub row_num_total: usize,
 }
 
+/// Difference in row usage of each sub circuit between two blocks
+#[derive(Clone, Default, Debug)]
+pub struct RowDiff {
+    /// Subcircuit name and row difference (without padding), in the order of
+    /// [`SuperCircuit::min_num_rows_block_subcircuits`]
+    pub subcircuits: Vec<(String, isize)>,
+}
+
+impl RowDiff {
+    /// Row difference of the subcircuit `name`, if it's estimated
+    pub fn get(&self, name: &str) -> Option<isize> {
+        self.subcircuits
+            .iter()
+            .find(|(subcircuit, _)| subcircuit == name)
+            .map(|(_, diff)| *diff)
+    }
+
+    /// Whether no subcircuit changed its row usage
+    pub fn is_zero(&self) -> bool {
+        self.subcircuits.iter().all(|(_, diff)| *diff == 0)
+    }
+}
+
 /// The Super Circuit contains all the zkEVM circuits
 #[derive(Clone, Debug)]
 pub struct SuperCircuit<
@@ -561,6 +584,23 @@ impl<
         }
         row_usage_details
     }
+
+    /// Return the row usage of each subcircuit for `block_b` minus the one for
+    /// `block_a`, e.g. to see how a change affects circuit size for the same
+    /// workload
+    pub fn row_utilization(block_a: &Block<Fr>, block_b: &Block<Fr>) -> RowDiff {
+        let rows_a = Self::min_num_rows_block_subcircuits(block_a);
+        let rows_b = Self::min_num_rows_block_subcircuits(block_b);
+        let subcircuits = rows_a
+            .into_iter()
+            .zip_eq(rows_b)
+            .map(|(a, b)| {
+                debug_assert_eq!(a.name, b.name);
+                (a.name, b.row_num_real as isize - a.row_num_real as isize)
+            })
+            .collect();
+        RowDiff { subcircuits }
+    }
 }
 
 // Eventhough the SuperCircuit is not a subcircuit we implement the SubCircuit
@@ -249,6 +249,50 @@ pub(crate) fn block_2tx() -> GethData {
 
 const TEST_MOCK_RANDOMNESS: u64 = 0x100;
 
+fn witness_block(geth_data: GethData, circuits_params: CircuitsParams) -> Block<Fr> {
+    let mut builder = BlockData::new_from_geth_data_with_params(geth_data.clone(), circuits_params)
+        .new_circuit_input_builder();
+    builder
+        .handle_block(&geth_data.eth_block, &geth_data.geth_traces)
+        .unwrap();
+    block_convert(&builder.block, &builder.code_db).unwrap()
+}
+
+#[test]
+fn super_circuit_row_utilization() {
+    let circuits_params = CircuitsParams {
+        max_txs: 2,
+        max_calldata: 256,
+        max_rws: 256,
+        max_copy_rows: 256,
+        max_mpt_rows: 2049,
+        max_poseidon_rows: 512,
+        max_exp_steps: 256,
+        max_bytecode: 512,
+        max_evm_rows: 0,
+        max_keccak_rows: 0,
+        max_inner_blocks: 1,
+        max_rlp_rows: 500,
+        ..Default::default()
+    };
+    let block_1tx = witness_block(block_1tx(), circuits_params);
+    let block_2tx = witness_block(block_2tx(), circuits_params);
+
+    type TestSuperCircuit = SuperCircuit<Fr, 2, 256, 1, TEST_MOCK_RANDOMNESS>;
+
+    let diff = TestSuperCircuit::row_utilization(&block_1tx, &block_1tx);
+    assert!(!diff.subcircuits.is_empty());
+    assert!(diff.is_zero());
+
+    // The extra transaction takes more steps, rws and hashing.
+    let diff = TestSuperCircuit::row_utilization(&block_1tx, &block_2tx);
+    for name in ["evm", "state", "keccak"] {
+        assert!(diff.get(name).unwrap() > 0, "{name} row diff {diff:?}");
+    }
+    let reverse = TestSuperCircuit::row_utilization(&block_2tx, &block_1tx);
+    assert_eq!(reverse.get("evm"), diff.get("evm").map(|rows| -rows));
+}
+
 // High memory usage test.  Run in serial with:
 // `cargo test [...] serial_ -- --ignored --test-threads 1`
 #[ignore]
@@ -11,7 +11,7 @@ use crate::{
     util::{build_tx_log_address, rlc_be_bytes, Challenges, Field},
     witness::{
         Block, BlockContexts, Bytecode, MptUpdateRow, MptUpdates, RlpFsmWitnessGen, Rw, RwMap,
-        RwRow, Transaction,
+        RwRow, RwTablePadding, Transaction,
     },
 };
 use bus_mapping::{
@@ -735,6 +735,25 @@ impl RwTable {
         )
     }
 
+    /// Assign the `RwTable` from a `RwMap`, with the padding rows placed and
+    /// tagged as configured in `padding`. Only the default padding matches
+    /// the layout of the State Circuit.
+    pub fn load_with_padding<F: Field>(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        rws: &[Rw],
+        n_rows: usize,
+        padding: RwTablePadding,
+        challenges: Value<F>,
+    ) -> Result<(), Error> {
+        layouter.assign_region(
+            || "rw table",
+            |mut region| {
+                self.load_with_region_padded(&mut region, rws, n_rows, padding, challenges)
+            },
+        )
+    }
+
     pub(crate) fn load_with_region<F: Field>(
         &self,
         region: &mut Region<'_, F>,
@@ -742,9 +761,24 @@ impl RwTable {
         n_rows: usize,
         challenges: Value<F>,
     ) -> Result<(), Error> {
-        let (rows, _) = RwMap::table_assignments_prepad(rws, n_rows);
+        self.load_with_region_padded(region, rws, n_rows, RwTablePadding::default(), challenges)
+    }
+
+    fn load_with_region_padded<F: Field>(
+        &self,
+        region: &mut Region<'_, F>,
+        rws: &[Rw],
+        n_rows: usize,
+        padding: RwTablePadding,
+        challenges: Value<F>,
+    ) -> Result<(), Error> {
+        let (rows, _) = RwMap::table_assignments_padded(rws, n_rows, padding);
         for (offset, row) in rows.iter().enumerate() {
-            self.assign(region, offset, &row.table_assignment(challenges))?;
+            let mut assignment = row.table_assignment(challenges);
+            if matches!(row, Rw::Start { .. }) {
+                assignment.tag = Value::known(F::from(padding.tag as u64));
+            }
+            self.assign(region, offset, &assignment)?;
         }
         Ok(())
     }
@@ -1817,7 +1851,7 @@ impl CopyTable {
 
             challenges
                 .keccak_input()
-                .map(|keccak_input| rlc::value(values.iter().rev(), keccak_input))
+                .map(|keccak_input| rlc::value_be(&values, keccak_input))
         } else {
             Value::known(F::zero())
         };
@@ -4,21 +4,30 @@ use crate::{
     copy_circuit::CopyCircuit,
     evm_circuit::EvmCircuit,
     state_circuit::StateCircuit,
-    util::{log2_ceil, SubCircuit},
+    util::{log2_ceil, Challenges, SubCircuit},
     witness::{Block, Rw},
 };
 use bus_mapping::{circuit_input_builder::CircuitsParams, mock::BlockData};
-use eth_types::geth_types::GethData;
+use eth_types::{geth_types::GethData, Word};
 
 use halo2_proofs::{
     circuit::Value,
-    dev::{unwrap_value, MockProver},
-    halo2curves::bn256::Fr,
+    dev::{unwrap_value, CellValue, MockProver},
+    halo2curves::{bn256::Fr, group::ff::PrimeField},
+    plonk::Circuit,
 };
 use mock::TestContext;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter},
+    path::{Path, PathBuf},
+};
 
 #[cfg(feature = "scroll")]
 use bus_mapping::circuit_input_builder::CircuitInputBuilder;
+#[cfg(feature = "debug-plot")]
+use halo2_proofs::dev::{FailureLocation, VerifyFailure};
 
 #[cfg(test)]
 #[ctor::ctor]
@@ -27,6 +36,17 @@ fn init_env_logger() {
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("error")).init();
 }
 
+/// A circuit checked by the [`CircuitTestBuilder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestCircuit {
+    /// The EVM circuit
+    Evm,
+    /// The State circuit
+    State,
+    /// The Copy circuit
+    Copy,
+}
+
 #[allow(clippy::type_complexity)]
 /// Struct used to easily generate tests for EVM &| State circuits being able to
 /// customize all of the steps involved in the testing itself.
@@ -86,6 +106,8 @@ pub struct CircuitTestBuilder<const NACC: usize, const NTX: usize> {
     state_checks: Option<Box<dyn Fn(MockProver<Fr>, &Vec<usize>, &Vec<usize>)>>,
     copy_checks: Option<Box<dyn Fn(MockProver<Fr>, &Vec<usize>, &Vec<usize>)>>,
     block_modifiers: Vec<Box<dyn Fn(&mut Block<Fr>)>>,
+    dump_dir: Option<PathBuf>,
+    fixed_challenges: Option<Challenges<u64>>,
 }
 
 impl<const NACC: usize, const NTX: usize> CircuitTestBuilder<NACC, NTX> {
@@ -114,6 +136,8 @@ impl<const NACC: usize, const NTX: usize> CircuitTestBuilder<NACC, NTX> {
                 ), Ok(()));
             })),
             block_modifiers: vec![],
+            dump_dir: None,
+            fixed_challenges: None,
         }
     }
 
@@ -196,6 +220,45 @@ impl<const NACC: usize, const NTX: usize> CircuitTestBuilder<NACC, NTX> {
         self.block_modifiers.push(modifier);
         self
     }
+
+    /// Dump the [`AssignmentDump`] of each circuit checked by the builder into
+    /// `dir`, as `evm.json`, `state.json` and `copy.json`, once its witness
+    /// has been synthesized and before the checks run.
+    pub fn dump_assignments(mut self, dir: impl AsRef<Path>) -> Self {
+        self.dump_dir = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Only build and check the given circuits, skipping the MockProver runs
+    /// of the others. This resets the checks of the skipped circuits, so it
+    /// must come after any custom `*_checks` call.
+    ///
+    /// The witness block is still fully generated, and the circuits can't be
+    /// isolated from the tables they look up into: the EVM circuit still
+    /// assigns every table it looks up (rw, tx, bytecode, copy, keccak, exp,
+    /// ...) from the block, and the Copy circuit its tx, rw and bytecode
+    /// tables. Only the synthesis and verification of the skipped circuits
+    /// are saved.
+    pub fn only_circuits(mut self, circuits: &[TestCircuit]) -> Self {
+        if !circuits.contains(&TestCircuit::Evm) {
+            self.evm_checks = None;
+        }
+        if !circuits.contains(&TestCircuit::State) {
+            self.state_checks = None;
+        }
+        if !circuits.contains(&TestCircuit::Copy) {
+            self.copy_checks = None;
+        }
+        self
+    }
+
+    /// Fix the challenges of the circuits to the given constants instead of
+    /// the ones drawn by the `MockProver`, so that the RLC values are the same
+    /// across runs.
+    pub fn with_fixed_challenges(mut self, challenges: Challenges<u64>) -> Self {
+        self.fixed_challenges = Some(challenges);
+        self
+    }
 }
 
 impl<const NACC: usize, const NTX: usize> CircuitTestBuilder<NACC, NTX> {
@@ -267,7 +330,8 @@ impl<const NACC: usize, const NTX: usize> CircuitTestBuilder<NACC, NTX> {
             let (active_gate_rows, active_lookup_rows) = EvmCircuit::<Fr>::get_active_rows(&block);
 
             let circuit = EvmCircuit::get_test_cicuit_from_block(block.clone());
-            let prover = MockProver::<Fr>::run(k, &circuit, vec![]).unwrap();
+            let prover = self.mock_prover(k, &circuit, vec![]);
+            self.dump(&prover, "evm.json");
 
             evm_checks(prover, &active_gate_rows, &active_lookup_rows)
         }
@@ -279,7 +343,8 @@ impl<const NACC: usize, const NTX: usize> CircuitTestBuilder<NACC, NTX> {
             assert!(k <= 20);
             let state_circuit = StateCircuit::<Fr>::new(block.rws.clone(), rows_needed);
             let instance = state_circuit.instance();
-            let prover = MockProver::<Fr>::run(k, &state_circuit, instance).unwrap();
+            let prover = self.mock_prover(k, &state_circuit, instance);
+            self.dump(&prover, "state.json");
             // Skip verification of Start rows to accelerate testing
             let non_start_rows_len = state_circuit
                 .rows
@@ -299,12 +364,95 @@ impl<const NACC: usize, const NTX: usize> CircuitTestBuilder<NACC, NTX> {
             let k = k1.max(k2);
             let copy_circuit = CopyCircuit::<Fr>::new_from_block(&block);
             let instance = copy_circuit.instance();
-            let prover = MockProver::<Fr>::run(k, &copy_circuit, instance).unwrap();
+            let prover = self.mock_prover(k, &copy_circuit, instance);
+            self.dump(&prover, "copy.json");
             let rows = (0..active_rows).collect();
 
             copy_checks(prover, &rows, &rows);
         }
     }
+
+    fn mock_prover<C: Circuit<Fr>>(
+        &self,
+        k: u32,
+        circuit: &C,
+        instance: Vec<Vec<Fr>>,
+    ) -> MockProver<Fr> {
+        let run = || MockProver::<Fr>::run(k, circuit, instance).unwrap();
+        match self.fixed_challenges {
+            Some(challenges) => crate::util::with_fixed_challenges(challenges, run),
+            None => run(),
+        }
+    }
+
+    fn dump(&self, prover: &MockProver<Fr>, file_name: &str) {
+        if let Some(dir) = &self.dump_dir {
+            AssignmentDump::new(prover)
+                .save(dir.join(file_name))
+                .expect("could not dump assignments");
+        }
+    }
+}
+
+/// Fixed and advice column values assigned during the synthesis of a
+/// [`MockProver`], serializable to JSON so that an external tool can check the
+/// constraints independently. Cells are indexed as `[column][row]`, and
+/// unassigned or blinding cells are `None`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssignmentDump {
+    /// Values of the fixed columns
+    pub fixed: Vec<Vec<Option<Word>>>,
+    /// Values of the advice columns
+    pub advice: Vec<Vec<Option<Word>>>,
+}
+
+impl AssignmentDump {
+    /// Capture the assignments of `prover`.
+    pub fn new(prover: &MockProver<Fr>) -> Self {
+        let columns = |columns: &Vec<Vec<CellValue<Fr>>>| {
+            columns
+                .iter()
+                .map(|column| {
+                    column
+                        .iter()
+                        .map(|cell| match cell {
+                            CellValue::Assigned(value) => {
+                                Some(Word::from_little_endian(value.to_repr().as_ref()))
+                            }
+                            _ => None,
+                        })
+                        .collect()
+                })
+                .collect()
+        };
+
+        Self {
+            fixed: columns(prover.fixed()),
+            advice: columns(prover.advice()),
+        }
+    }
+
+    /// Number of rows of the columns.
+    pub fn num_rows(&self) -> usize {
+        self.fixed
+            .iter()
+            .chain(self.advice.iter())
+            .map(Vec::len)
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Write the dump to `path` as JSON.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer(writer, self).map_err(io::Error::from)
+    }
+
+    /// Read back a dump written by [`AssignmentDump::save`].
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let reader = BufReader::new(File::open(path)?);
+        serde_json::from_reader(reader).map_err(io::Error::from)
+    }
 }
 
 /// Escape the type safety of Value in tests.
@@ -315,3 +463,275 @@ pub fn escape_value<T>(v: Value<T>) -> Option<T> {
         Some(unwrap_value(v))
     }
 }
+
+/// Render the cells involved in a [`VerifyFailure`] of `prover` as an ASCII
+/// grid of cell names and values, e.g. to print it when a test fails.
+///
+/// The cells of a constraint failure are the queried cells recorded in the
+/// failure. For failures located outside of any region, the fixed cells of
+/// the offending row are added.
+#[cfg(feature = "debug-plot")]
+pub fn debug_dump_region(prover: &MockProver<Fr>, failure: &VerifyFailure) -> String {
+    let (title, mut cells, location) = match failure {
+        VerifyFailure::ConstraintNotSatisfied {
+            constraint,
+            location,
+            cell_values,
+        } => (
+            format!("{constraint} is not satisfied {location}"),
+            cell_values
+                .iter()
+                .map(|(cell, value)| (cell.to_string(), value.clone()))
+                .collect::<Vec<_>>(),
+            Some(location),
+        ),
+        VerifyFailure::Lookup { location, .. } | VerifyFailure::Permutation { location, .. } => {
+            (failure.to_string(), Vec::new(), Some(location))
+        }
+        _ => (failure.to_string(), Vec::new(), None),
+    };
+    if let Some(FailureLocation::OutsideRegion { row }) = location {
+        for (index, column) in prover.fixed().iter().enumerate() {
+            if let Some(value) = column.get(*row) {
+                cells.push((format!("fixed[{index}]@{row}"), format!("{value:?}")));
+            }
+        }
+    }
+
+    let name_width = cells
+        .iter()
+        .map(|(name, _)| name.len())
+        .chain(["cell".len()])
+        .max()
+        .unwrap();
+    let value_width = cells
+        .iter()
+        .map(|(_, value)| value.len())
+        .chain(["value".len()])
+        .max()
+        .unwrap();
+    let border = format!(
+        "+-{}-+-{}-+",
+        "-".repeat(name_width),
+        "-".repeat(value_width)
+    );
+    let mut grid = vec![
+        title,
+        border.clone(),
+        format!("| {:<name_width$} | {:<value_width$} |", "cell", "value"),
+        border.clone(),
+    ];
+    for (name, value) in cells {
+        grid.push(format!("| {name:<name_width$} | {value:<value_width$} |"));
+    }
+    grid.push(border);
+    grid.join("\n")
+}
+
+#[cfg(all(test, feature = "debug-plot"))]
+mod debug_plot_tests {
+    use super::*;
+    use halo2_proofs::{
+        circuit::{Layouter, SimpleFloorPlanner},
+        plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Selector},
+        poly::Rotation,
+    };
+
+    #[derive(Default)]
+    struct EqualCircuit {
+        lhs: u64,
+        rhs: u64,
+    }
+
+    impl Circuit<Fr> for EqualCircuit {
+        type Config = (Selector, Column<Advice>, Column<Advice>);
+        type FloorPlanner = SimpleFloorPlanner;
+        #[cfg(feature = "circuit-params")]
+        type Params = ();
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let q_enable = meta.selector();
+            let lhs = meta.advice_column();
+            let rhs = meta.advice_column();
+            meta.create_gate("lhs == rhs", |meta| {
+                let q_enable = meta.query_selector(q_enable);
+                let lhs = meta.query_advice(lhs, Rotation::cur());
+                let rhs = meta.query_advice(rhs, Rotation::cur());
+                vec![q_enable * (lhs - rhs)]
+            });
+            (q_enable, lhs, rhs)
+        }
+
+        fn synthesize(
+            &self,
+            (q_enable, lhs, rhs): Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            layouter.assign_region(
+                || "equal",
+                |mut region| {
+                    region.name_column(|| "lhs_column", lhs);
+                    region.name_column(|| "rhs_column", rhs);
+                    q_enable.enable(&mut region, 0)?;
+                    region.assign_advice(|| "lhs", lhs, 0, || Value::known(Fr::from(self.lhs)))?;
+                    region.assign_advice(|| "rhs", rhs, 0, || Value::known(Fr::from(self.rhs)))?;
+                    Ok(())
+                },
+            )
+        }
+    }
+
+    #[test]
+    fn debug_dump_region_shows_failing_cells() {
+        let circuit = EqualCircuit { lhs: 1, rhs: 2 };
+        let prover = MockProver::<Fr>::run(4, &circuit, vec![]).unwrap();
+        let failures = prover.verify().unwrap_err();
+        assert_eq!(failures.len(), 1);
+
+        let dump = debug_dump_region(&prover, &failures[0]);
+        assert!(dump.contains("lhs == rhs"), "{dump}");
+        assert!(dump.contains("| cell"), "{dump}");
+        // One row per queried cell, with its assigned value.
+        assert_eq!(
+            dump.lines().filter(|line| line.starts_with("| ")).count(),
+            3
+        );
+        assert!(dump.contains("| 1 "), "{dump}");
+        assert!(dump.contains("| 0x2 "), "{dump}");
+    }
+}
+
+#[cfg(test)]
+mod assignment_dump_tests {
+    use super::*;
+    use eth_types::bytecode;
+
+    fn tiny_block() -> Block<Fr> {
+        let code = bytecode! {
+            PUSH1(0x01)
+            PUSH1(0x02)
+            ADD
+            STOP
+        };
+        let block: GethData = TestContext::<2, 1>::simple_ctx_with_bytecode(code)
+            .unwrap()
+            .into();
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+        crate::witness::block_convert(&builder.block, &builder.code_db).unwrap()
+    }
+
+    #[test]
+    fn dump_assignments_of_tiny_block() {
+        let block = tiny_block();
+
+        let dir = std::env::temp_dir().join("zkevm_assignment_dump_test");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        CircuitTestBuilder::<2, 1>::new_from_block(block.clone())
+            .evm_checks(None)
+            .copy_checks(None)
+            .dump_assignments(&dir)
+            .run();
+        // Only the circuits that are checked get dumped.
+        assert!(!dir.join("evm.json").exists());
+        assert!(!dir.join("copy.json").exists());
+        let dump = AssignmentDump::load(dir.join("state.json")).unwrap();
+
+        // Synthesize the same state circuit to compare with.
+        let (_, rows_needed) = StateCircuit::<Fr>::min_num_rows_block(&block);
+        let k = log2_ceil(rows_needed + 64);
+        let state_circuit = StateCircuit::<Fr>::new(block.rws.clone(), rows_needed);
+        let instance = state_circuit.instance();
+        let prover = MockProver::<Fr>::run(k, &state_circuit, instance).unwrap();
+
+        assert_eq!(dump.num_rows(), 1 << k);
+        assert_eq!(dump.fixed.len(), prover.fixed().len());
+        assert_eq!(dump.advice.len(), prover.advice().len());
+        assert!(dump.advice.iter().flatten().any(Option::is_some));
+        assert_eq!(dump, AssignmentDump::new(&prover));
+    }
+
+    #[test]
+    fn fixed_challenges_give_identical_rlc_assignments() {
+        let block = tiny_block();
+        let state_dump = |name: &str, challenges: Challenges<u64>| {
+            let dir = std::env::temp_dir().join(name);
+            let _ = std::fs::remove_dir_all(&dir);
+            std::fs::create_dir_all(&dir).unwrap();
+            CircuitTestBuilder::<2, 1>::new_from_block(block.clone())
+                .evm_checks(None)
+                .copy_checks(None)
+                .with_fixed_challenges(challenges)
+                .dump_assignments(&dir)
+                .run();
+            AssignmentDump::load(dir.join("state.json")).unwrap()
+        };
+
+        let challenges = Challenges::mock(0x100, 0x101, 0x102);
+        let dump = state_dump("zkevm_fixed_challenges_test_0", challenges);
+        assert_eq!(
+            dump,
+            state_dump("zkevm_fixed_challenges_test_1", challenges)
+        );
+        // The RLC cells, e.g. of the code hashes, depend on the challenges.
+        assert_ne!(
+            dump,
+            state_dump(
+                "zkevm_fixed_challenges_test_2",
+                Challenges::mock(0x200, 0x201, 0x202)
+            )
+        );
+    }
+}
+
+#[cfg(test)]
+mod only_circuits_tests {
+    use super::*;
+    use eth_types::bytecode;
+    use std::{cell::Cell, rc::Rc};
+
+    #[test]
+    fn only_evm_circuit() {
+        let code = bytecode! {
+            PUSH1(0x00)
+            PUSH1(0x20)
+            SHA3
+            PUSH1(0x00)
+            MSTORE
+            STOP
+        };
+        let ctx = TestContext::<2, 1>::simple_ctx_with_bytecode(code).unwrap();
+
+        let evm_checked = Rc::new(Cell::new(false));
+        CircuitTestBuilder::new_from_test_ctx(ctx)
+            .evm_checks(Some(Box::new({
+                let evm_checked = evm_checked.clone();
+                move |prover, gate_rows, lookup_rows| {
+                    assert_eq!(
+                        prover.verify_at_rows_par(
+                            gate_rows.iter().cloned(),
+                            lookup_rows.iter().cloned()
+                        ),
+                        Ok(())
+                    );
+                    evm_checked.set(true);
+                }
+            })))
+            .state_checks(Some(Box::new(|_, _, _| {
+                panic!("state circuit should be skipped")
+            })))
+            .copy_checks(Some(Box::new(|_, _, _| {
+                panic!("copy circuit should be skipped")
+            })))
+            .only_circuits(&[TestCircuit::Evm])
+            .run();
+        assert!(evm_checked.get());
+    }
+}
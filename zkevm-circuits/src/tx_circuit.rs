@@ -375,7 +375,9 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
         meta.enable_equality(pow_of_rand);
         meta.enable_equality(tx_table.chunk_txbytes_hash_rlc);
 
-        // TODO: add lookup to SignVerify table for sv_address
+        // Sender recovered by the sig circuit, looked up in the sig table along
+        // with the TxSignHash and signature of the tx table, and constrained to
+        // be the CallerAddress.
         let sv_address = meta.advice_column();
         meta.enable_equality(tx_table.value);
 
@@ -171,6 +171,27 @@ fn run<F: Field>(
     max_txs: usize,
     max_calldata: usize,
     start_l1_queue_index: u64,
+) -> Result<(), Vec<VerifyFailure>> {
+    let signatures = get_sign_data(&txs, max_txs, chain_id as usize).unwrap();
+    run_with_sign_data(
+        txs,
+        signatures,
+        chain_id,
+        max_txs,
+        max_calldata,
+        start_l1_queue_index,
+    )
+}
+
+/// Like [`run`], with the signatures verified by the sig circuit given
+/// rather than taken from `txs`.
+fn run_with_sign_data<F: Field>(
+    txs: Vec<Transaction>,
+    signatures: Vec<SignData>,
+    chain_id: u64,
+    max_txs: usize,
+    max_calldata: usize,
+    start_l1_queue_index: u64,
 ) -> Result<(), Vec<VerifyFailure>> {
     let active_row_num = TxCircuit::<F>::min_num_rows(max_txs, max_calldata);
 
@@ -178,7 +199,7 @@ fn run<F: Field>(
     let circuit = TxCircuitTester::<F> {
         sig_circuit: SigCircuit {
             max_verif: max_txs,
-            signatures: get_sign_data(&txs, max_txs, chain_id as usize).unwrap(),
+            signatures,
             _marker: PhantomData,
         },
         tx_circuit: TxCircuit::new(max_txs, max_calldata, chain_id, start_l1_queue_index, txs),
@@ -282,6 +303,44 @@ fn tx_circuit_1tx_1max_tx() {
     );
 }
 
+#[test]
+#[cfg(feature = "scroll")]
+fn tx_circuit_tx_from_raw() {
+    const MAX_TXS: usize = 1;
+    const MAX_CALLDATA: usize = 32;
+
+    let mock_tx = mock::CORRECT_MOCK_TXS[0].clone();
+    let expected: Transaction = mock_tx.clone().into();
+
+    let tx = Transaction::from_raw(
+        mock_tx.nonce.as_u64(),
+        mock_tx.gas.as_u64(),
+        mock_tx.gas_price.unwrap_or_default(),
+        mock_tx.to.as_ref().map(|to| to.address()),
+        mock_tx.value,
+        mock_tx.input.to_vec(),
+        mock_tx.v.unwrap().as_u64(),
+        mock_tx.r.unwrap(),
+        mock_tx.s.unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(tx.tx_type, TxType::Eip155);
+    assert_eq!(tx.chain_id, mock::MOCK_CHAIN_ID);
+    assert_eq!(tx.caller_address, expected.caller_address);
+    assert_eq!(tx.call_data_length, mock_tx.input.len());
+    assert_eq!(tx.call_data_gas_cost, expected.call_data_gas_cost);
+    assert_eq!(tx.tx_data_gas_cost, expected.tx_data_gas_cost);
+    assert_eq!(tx.rlp_unsigned, expected.rlp_unsigned);
+    assert_eq!(tx.rlp_signed, expected.rlp_signed);
+    assert_eq!(tx.hash, H256(keccak256(&tx.rlp_signed)));
+
+    assert_eq!(
+        run::<Fr>(vec![tx], mock::MOCK_CHAIN_ID, MAX_TXS, MAX_CALLDATA, 0),
+        Ok(())
+    );
+}
+
 #[test]
 #[cfg(feature = "scroll")]
 fn tx_circuit_1tx_2max_tx() {
@@ -330,6 +389,59 @@ fn tx_circuit_bad_address() {
     .is_err(),);
 }
 
+#[test]
+fn tx_signature_recovers_sender() {
+    let tx = build_pre_eip155_tx();
+
+    let sign_data = tx.sign_data().expect("signature of a signed tx is valid");
+    assert_eq!(sign_data.get_addr(), tx.caller_address);
+}
+
+#[test]
+#[cfg(feature = "scroll")]
+fn tx_circuit_bad_chain_id_in_v() {
+    const MAX_TXS: usize = 1;
+    const MAX_CALLDATA: usize = 32;
+
+    let mut tx: Transaction = mock::CORRECT_MOCK_TXS[0].clone().into();
+    // The `v` of an EIP-155 tx signed for the next chain id. Its parity is
+    // unchanged, so the same sender is recovered.
+    tx.v += 2;
+
+    let failures = run::<Fr>(vec![tx], mock::MOCK_CHAIN_ID, MAX_TXS, MAX_CALLDATA, 0)
+        .expect_err("v not matching the chain id should not verify");
+    assert!(failures.iter().any(|failure| failure
+        .to_string()
+        .contains("V - (chain_id * 2 + 35) Є {0, 1}")));
+}
+
+#[test]
+#[cfg(feature = "scroll")]
+fn tx_circuit_sig_table_disagrees_with_tx_table() {
+    const MAX_TXS: usize = 1;
+    const MAX_CALLDATA: usize = 320;
+
+    let tx = build_pre_eip155_tx();
+    // A valid signature of another tx: its row of the sig table has another
+    // message hash and recovered address than the tx table.
+    let other_tx: Transaction = mock::CORRECT_MOCK_TXS[1].clone().into();
+    let signatures = get_sign_data(&[other_tx], MAX_TXS, mock::MOCK_CHAIN_ID as usize).unwrap();
+    assert_ne!(signatures[0].get_addr(), tx.caller_address);
+
+    let failures = run_with_sign_data::<Fr>(
+        vec![tx],
+        signatures,
+        mock::MOCK_CHAIN_ID,
+        MAX_TXS,
+        MAX_CALLDATA,
+        0,
+    )
+    .expect_err("sig table not matching the tx table should not verify");
+    assert!(failures
+        .iter()
+        .any(|failure| failure.to_string().contains("Sig table lookup")));
+}
+
 #[test]
 #[cfg(feature = "scroll")]
 fn tx_circuit_to_is_zero() {
@@ -1,4 +1,6 @@
 //! Common utility traits and functions.
+#[cfg(any(feature = "test", test))]
+use std::cell::Cell;
 use std::collections::BTreeSet;
 
 use bus_mapping::evm::OpcodeId;
@@ -91,6 +93,29 @@ impl MockChallenges {
     }
 }
 
+#[cfg(any(feature = "test", test))]
+thread_local! {
+    /// Constants replacing the challenges, see [`with_fixed_challenges`].
+    static FIXED_CHALLENGES: Cell<Option<Challenges<u64>>> = Cell::new(None);
+}
+
+/// Run `f` with the challenges of the circuits configured and synthesized in
+/// it fixed to the given constants, instead of the values drawn by the prover,
+/// so that RLC values are deterministic. Only meant for debugging with the
+/// `MockProver`, as it makes the circuits unsound, hence only built for tests.
+#[cfg(any(feature = "test", test))]
+pub(crate) fn with_fixed_challenges<R>(challenges: Challenges<u64>, f: impl FnOnce() -> R) -> R {
+    struct Reset(Option<Challenges<u64>>);
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            FIXED_CHALLENGES.with(|fixed| fixed.set(self.0));
+        }
+    }
+
+    let _reset = Reset(FIXED_CHALLENGES.with(|fixed| fixed.replace(Some(challenges))));
+    f()
+}
+
 impl Challenges {
     /// Construct `Challenges` by allocating challenges in specific phases.
     pub fn construct<F: Field>(meta: &mut ConstraintSystem<F>) -> Self {
@@ -110,6 +135,14 @@ impl Challenges {
 
     /// Returns `Expression` of challenges from `ConstraintSystem`.
     pub fn exprs<F: Field>(&self, meta: &mut ConstraintSystem<F>) -> Challenges<Expression<F>> {
+        #[cfg(any(feature = "test", test))]
+        if let Some(fixed) = FIXED_CHALLENGES.with(Cell::get) {
+            return Challenges {
+                evm_word: Expression::Constant(F::from(fixed.evm_word)),
+                keccak_input: Expression::Constant(F::from(fixed.keccak_input)),
+                lookup_input: Expression::Constant(F::from(fixed.lookup_input)),
+            };
+        }
         let [evm_word, keccak_input, lookup_input] = query_expression(meta, |meta| {
             [self.evm_word, self.keccak_input, self.lookup_input]
                 .map(|challenge| meta.query_challenge(challenge))
@@ -123,6 +156,14 @@ impl Challenges {
 
     /// Returns `Value` of challenges from `Layouter`.
     pub fn values<F: Field>(&self, layouter: &impl Layouter<F>) -> Challenges<Value<F>> {
+        #[cfg(any(feature = "test", test))]
+        if let Some(fixed) = FIXED_CHALLENGES.with(Cell::get) {
+            return Challenges {
+                evm_word: Value::known(F::from(fixed.evm_word)),
+                keccak_input: Value::known(F::from(fixed.keccak_input)),
+                lookup_input: Value::known(F::from(fixed.lookup_input)),
+            };
+        }
         Challenges {
             evm_word: layouter.get_challenge(self.evm_word),
             keccak_input: layouter.get_challenge(self.keccak_input),
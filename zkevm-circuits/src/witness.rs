@@ -27,7 +27,7 @@ pub use rlp_fsm::{
 };
 
 mod rw;
-pub use rw::{Rw, RwMap, RwRow};
+pub use rw::{Rw, RwMap, RwPaddingPlacement, RwRow, RwTablePadding};
 
 mod step;
 pub use step::ExecStep;
@@ -14,6 +14,7 @@ use bus_mapping::{
         self, BigModExp, CircuitsParams, CopyEvent, EcAddOp, EcMulOp, EcPairingOp, ExpEvent,
         PrecompileEvents, SHA256,
     },
+    operation::OperationContainer,
     Error,
 };
 use eth_types::{sign_types::SignData, Address, ToLittleEndian, ToScalar, Word, U256};
@@ -74,6 +75,9 @@ pub struct Block<F> {
     pub start_l1_queue_index: u64,
     /// IO to/from precompile calls.
     pub precompile_events: PrecompileEvents,
+    /// User-defined tags for downstream tooling, ignored by witness
+    /// generation.
+    pub metadata: HashMap<String, String>,
 }
 
 /// ...
@@ -143,6 +147,46 @@ impl<F: Field> Block<F> {
         self.precompile_events.get_sha256_events()
     }
 
+    /// Build a block holding only the RW operations of `container`, e.g. one
+    /// deserialized from a minimal reproduction, so it can be replayed into
+    /// the state circuit. All other witness data is left empty and the state
+    /// roots are mocked.
+    pub fn from_operations(container: &OperationContainer) -> Result<Self, Error> {
+        let rws = RwMap::from(container);
+        rws.check_value()?;
+        let max_rws = rws.0.values().map(Vec::len).sum::<usize>() + 1;
+        let mpt_updates = MptUpdates::mock_from(&rws.table_assignments());
+        Ok(Self {
+            rws,
+            mpt_updates,
+            circuits_params: CircuitsParams {
+                max_rws,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+    }
+
+    /// Attach a user-defined tag to the block. Metadata is never read by the
+    /// circuits.
+    pub fn set_metadata(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.metadata.insert(key.into(), value.into());
+    }
+
+    /// Get the metadata attached to the block under `key`.
+    pub fn get_metadata(&self, key: &str) -> Option<&str> {
+        self.metadata.get(key).map(String::as_str)
+    }
+
+    /// Get `(base, exponent, result)` of all exponentiation events fed to the
+    /// exp circuit, in the order they were generated.
+    pub fn exp_events(&self) -> Vec<(Word, Word, Word)> {
+        self.exp_events
+            .iter()
+            .map(|event| (event.base, event.exponent, event.exponentiation))
+            .collect()
+    }
+
     pub(crate) fn print_evm_circuit_row_usage(&self) {
         let mut num_rows = 0;
         let mut counter = HashMap::new();
@@ -575,6 +619,7 @@ pub fn block_convert<F: Field>(
         chain_id,
         start_l1_queue_index: block.start_l1_queue_index,
         precompile_events: block.precompile_events.clone(),
+        metadata: HashMap::new(),
     })
 }
 
@@ -605,3 +650,84 @@ pub fn block_mocking_apply_mpt<F: Field>(block: &mut Block<F>) {
     block.state_root = Some(block.mpt_updates.new_root());
     block.prev_state_root = block.mpt_updates.old_root();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{copy_circuit::CopyCircuit, evm_circuit::EvmCircuit, state_circuit::StateCircuit};
+    use bus_mapping::mock::BlockData;
+    use eth_types::{bytecode, geth_types::GethData};
+    use halo2_proofs::halo2curves::bn256::Fr;
+    use mock::TestContext;
+
+    #[test]
+    fn operations_round_trip() {
+        let code = bytecode! {
+            PUSH1(0x2a)
+            PUSH1(0x00)
+            SSTORE
+            PUSH1(0x00)
+            SLOAD
+            PUSH1(0x00)
+            MSTORE
+            STOP
+        };
+        let block: GethData = TestContext::<2, 1>::simple_ctx_with_bytecode(code)
+            .unwrap()
+            .into();
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let serialized = serde_json::to_string(&builder.block.container).unwrap();
+        let container: OperationContainer = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(container, builder.block.container);
+
+        let replayed = Block::<Fr>::from_operations(&container).unwrap();
+        let block = block_convert::<Fr>(&builder.block, &builder.code_db).unwrap();
+        assert_eq!(
+            replayed.rws.table_assignments(),
+            block.rws.table_assignments()
+        );
+    }
+
+    #[test]
+    fn metadata_does_not_affect_rows() {
+        let code = bytecode! {
+            PUSH1(0x20)
+            PUSH1(0x00)
+            SHA3
+            STOP
+        };
+        let block: GethData = TestContext::<2, 1>::simple_ctx_with_bytecode(code)
+            .unwrap()
+            .into();
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+        let block = block_convert::<Fr>(&builder.block, &builder.code_db).unwrap();
+        assert!(block.metadata.is_empty());
+
+        let mut tagged = block.clone();
+        tagged.set_metadata("batch", "42");
+        tagged.set_metadata("source", "replay");
+        assert_eq!(tagged.get_metadata("batch"), Some("42"));
+        assert_eq!(tagged.get_metadata("source"), Some("replay"));
+        assert_eq!(tagged.get_metadata("missing"), None);
+
+        assert_eq!(
+            EvmCircuit::<Fr>::min_num_rows_block(&tagged),
+            EvmCircuit::<Fr>::min_num_rows_block(&block)
+        );
+        assert_eq!(
+            StateCircuit::<Fr>::min_num_rows_block(&tagged),
+            StateCircuit::<Fr>::min_num_rows_block(&block)
+        );
+        assert_eq!(
+            CopyCircuit::<Fr>::min_num_rows_block(&tagged),
+            CopyCircuit::<Fr>::min_num_rows_block(&block)
+        );
+    }
+}
@@ -103,6 +103,9 @@ pub struct MptUpdates {
     /// The detailed mpt witness
     pub smt_traces: Vec<SMTTrace>,
     pub(crate) proof_types: Vec<MPTProofType>,
+    /// Proofs of the accounts touched by this block, after this block, if
+    /// requested with [`MptUpdates::collect_account_proofs`]
+    account_proofs: Option<BTreeMap<Address, Vec<Vec<u8>>>>,
 }
 
 /// The field element encoding of an MPT update, which is used by the MptTable
@@ -122,6 +125,24 @@ impl MptUpdates {
         self.new_root
     }
 
+    /// Collect the proofs of the accounts touched by this block, see
+    /// [`MptUpdates::account_proof`], when the state roots are filled. They
+    /// aren't needed by the circuits, so they're only generated on request.
+    pub fn collect_account_proofs(&mut self) {
+        self.account_proofs.get_or_insert_with(BTreeMap::new);
+    }
+
+    /// Proof of the account at `address` in the zktrie after this block, in
+    /// the format of the `accountProof` of `eth_getProof` on Scroll.  Only
+    /// the accounts touched by the block have one, once the state roots are
+    /// filled after a call to [`MptUpdates::collect_account_proofs`].
+    pub fn account_proof(&self, address: &Address) -> Option<&[Vec<u8>]> {
+        self.account_proofs
+            .as_ref()?
+            .get(address)
+            .map(Vec::as_slice)
+    }
+
     pub(crate) fn get(&self, row: &Rw) -> Option<MptUpdate> {
         key(row).map(|key| {
             self.updates
@@ -255,6 +276,15 @@ impl MptUpdates {
             self.smt_traces.push(smt_trace);
             self.proof_types.push(proof_tip);
         }
+        if let Some(account_proofs) = self.account_proofs.as_mut() {
+            account_proofs.clear();
+            for key in self.updates.keys() {
+                let (Key::Account { address, .. } | Key::AccountStorage { address, .. }) = key;
+                account_proofs
+                    .entry(*address)
+                    .or_insert_with(|| wit_gen.account_proof(*address));
+            }
+        }
         log::debug!(
             "mpt update roots (after zktrie) {:#x} {:#x}",
             self.old_root,
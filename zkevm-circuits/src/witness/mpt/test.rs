@@ -986,3 +986,69 @@ fn empty_account_storage_write() {
         serde_json::to_string_pretty(&updates.smt_traces.last().unwrap()).unwrap()
     );
 }
+
+#[test]
+fn account_proof_of_touched_account() {
+    use crate::witness::{block_convert, block_mocking_apply_mpt};
+    use bus_mapping::mock::BlockData;
+    use eth_types::geth_types::GethData;
+    use halo2_proofs::halo2curves::bn256::Fr;
+    use mock::{eth, TestContext, MOCK_ACCOUNTS, MOCK_WALLETS};
+    use mpt_zktrie::ZkTrieNode;
+
+    let receiver = MOCK_ACCOUNTS[0];
+    let block: GethData = TestContext::<2, 1>::new(
+        None,
+        |accs| {
+            accs[0].address(MOCK_WALLETS[0].address()).balance(eth(10));
+            accs[1].address(receiver).balance(eth(1));
+        },
+        |mut txs, accs| {
+            txs[0]
+                .from(MOCK_WALLETS[0].clone())
+                .to(accs[1].address)
+                .value(eth(2));
+        },
+        |block, _tx| block,
+    )
+    .unwrap()
+    .into();
+    let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+    builder
+        .handle_block(&block.eth_block, &block.geth_traces)
+        .unwrap();
+    let mut block = block_convert::<Fr>(&builder.block, &builder.code_db).unwrap();
+    // The proofs are only generated on request.
+    let mut block_without_proofs = block.clone();
+    block_mocking_apply_mpt(&mut block_without_proofs);
+    assert!(block_without_proofs
+        .mpt_updates
+        .account_proof(&receiver)
+        .is_none());
+
+    block.mpt_updates.collect_account_proofs();
+    block_mocking_apply_mpt(&mut block);
+
+    let proof = block
+        .mpt_updates
+        .account_proof(&receiver)
+        .expect("receiver is touched by the block");
+    // The proof starts from the state root after the block...
+    let root = ZkTrieNode::parse(&proof[0]).expect("wrong proof bytes");
+    assert_eq!(
+        U256::from_big_endian(root.node_hash().as_slice()),
+        block.mpt_updates.new_root()
+    );
+    // ...and leads to the account as the block left it.
+    let (_, account) = ZktrieState::parse_account_from_proofs(std::iter::once((
+        &receiver,
+        proof.iter().map(Vec::as_slice),
+    )))
+    .next()
+    .unwrap()
+    .unwrap();
+    assert_eq!(account.balance, eth(3));
+    assert_eq!(account.balance, builder.sdb.get_balance(&receiver));
+
+    assert!(block.mpt_updates.account_proof(&MOCK_ACCOUNTS[1]).is_none());
+}
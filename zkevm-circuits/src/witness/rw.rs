@@ -23,6 +23,35 @@ use super::MptUpdates;
 const ERR_MSG_FIRST: &str = "first access reads don't change value";
 const ERR_MSG_NON_FIRST: &str = "non-first access reads don't change value";
 
+/// Where the padding rows of the RW table are placed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RwPaddingPlacement {
+    /// Before the rws, which is what the State Circuit expects
+    #[default]
+    Start,
+    /// After the rws
+    End,
+}
+
+/// How the RW table is padded to its target length. The padding rows are
+/// `Rw::Start` rows, assigned in the RW table with `tag`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RwTablePadding {
+    /// Placement of the padding rows
+    pub placement: RwPaddingPlacement,
+    /// Tag of the padding rows in the RW table
+    pub tag: RwTableTag,
+}
+
+impl Default for RwTablePadding {
+    fn default() -> Self {
+        Self {
+            placement: RwPaddingPlacement::Start,
+            tag: RwTableTag::Start,
+        }
+    }
+}
+
 /// Rw constainer for a witness block
 #[derive(Debug, Default, Clone)]
 pub struct RwMap(pub HashMap<RwTableTag, Vec<Rw>>);
@@ -173,6 +202,14 @@ impl RwMap {
     }
     /// Prepad Rw::Start rows to target length
     pub fn table_assignments_prepad(rows: &[Rw], target_len: usize) -> (Vec<Rw>, usize) {
+        Self::table_assignments_padded(rows, target_len, RwTablePadding::default())
+    }
+    /// Pad Rw::Start rows to target length, placed as configured in `padding`
+    pub fn table_assignments_padded(
+        rows: &[Rw],
+        target_len: usize,
+        padding: RwTablePadding,
+    ) -> (Vec<Rw>, usize) {
         // Remove Start rows as we will add them from scratch.
         let rows: Vec<Rw> = rows
             .iter()
@@ -180,8 +217,12 @@ impl RwMap {
             .cloned()
             .collect();
         let padding_length = Self::padding_len(rows.len(), target_len);
-        let padding = (1..=padding_length).map(|rw_counter| Rw::Start { rw_counter });
-        (padding.chain(rows).collect(), padding_length)
+        let padding_rows = (1..=padding_length).map(|rw_counter| Rw::Start { rw_counter });
+        let rows = match padding.placement {
+            RwPaddingPlacement::Start => padding_rows.chain(rows).collect(),
+            RwPaddingPlacement::End => rows.into_iter().chain(padding_rows).collect(),
+        };
+        (rows, padding_length)
     }
     /// Build Rws for assignment
     #[inline(always)]
@@ -1078,3 +1119,72 @@ impl From<&operation::OperationContainer> for RwMap {
         Self(rws)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stack_rws(n: usize) -> Vec<Rw> {
+        (1..=n)
+            .map(|rw_counter| Rw::Stack {
+                rw_counter,
+                is_write: true,
+                call_id: 1,
+                stack_pointer: 1023,
+                value: Word::from(rw_counter),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rw_table_padding_placement() {
+        let rws = stack_rws(3);
+        let is_padding = |rw: &Rw| matches!(rw, Rw::Start { .. });
+
+        let (rows, padding_length) = RwMap::table_assignments_padded(
+            &rws,
+            8,
+            RwTablePadding {
+                placement: RwPaddingPlacement::Start,
+                ..Default::default()
+            },
+        );
+        assert_eq!(padding_length, 5);
+        assert_eq!(rows.len(), 8);
+        assert!(rows[..5].iter().all(is_padding));
+        assert_eq!(rows[5..], rws[..]);
+        // Same as the default prepadding used by the State Circuit
+        assert_eq!(RwMap::table_assignments_prepad(&rws, 8), (rows, 5));
+
+        let (rows, padding_length) = RwMap::table_assignments_padded(
+            &rws,
+            8,
+            RwTablePadding {
+                placement: RwPaddingPlacement::End,
+                tag: RwTableTag::Stack,
+            },
+        );
+        assert_eq!(padding_length, 5);
+        assert_eq!(rows.len(), 8);
+        assert_eq!(rows[..3], rws[..]);
+        assert!(rows[3..].iter().all(is_padding));
+    }
+
+    #[test]
+    fn rw_table_padding_existing_start_rows() {
+        // Start rows already in the input are replaced, and a target length
+        // that is too small still gets one padding row.
+        let rws = [vec![Rw::Start { rw_counter: 1 }], stack_rws(3)].concat();
+        let (rows, padding_length) = RwMap::table_assignments_padded(
+            &rws,
+            2,
+            RwTablePadding {
+                placement: RwPaddingPlacement::End,
+                ..Default::default()
+            },
+        );
+        assert_eq!(padding_length, 1);
+        assert_eq!(rows[..3], rws[1..]);
+        assert_eq!(rows[3], Rw::Start { rw_counter: 1 });
+    }
+}
@@ -131,6 +131,77 @@ impl Transaction {
         }
     }
 
+    /// Build a legacy (pre-eip155 or eip155) tx from its raw signed fields,
+    /// without going through a full block. The tx type and chain id are
+    /// derived from `v`, the caller is recovered from the signature and the
+    /// rlp bytes, hash and gas costs are computed from the fields. The tx is
+    /// given id 1 in block 1; callers feeding several txs must fix up `id`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_raw(
+        nonce: u64,
+        gas: u64,
+        gas_price: Word,
+        to: Option<Address>,
+        value: Word,
+        data: Vec<u8>,
+        v: u64,
+        r: Word,
+        s: Word,
+    ) -> Result<Self, Error> {
+        let (tx_type, chain_id) = match v {
+            27 | 28 => (PreEip155, 0),
+            v if v >= 35 => (TxType::Eip155, (v - 35) / 2),
+            _ => return Err(Error::Signature),
+        };
+        let sig = Signature { r, s, v };
+
+        let mut legacy_tx = TransactionRequest::new()
+            .nonce(nonce)
+            .gas(gas)
+            .gas_price(gas_price)
+            .value(value)
+            .data(data.clone());
+        if let Some(to) = to {
+            legacy_tx = legacy_tx.to(to);
+        }
+        let rlp_unsigned = if tx_type.is_eip155() {
+            legacy_tx = legacy_tx.chain_id(chain_id);
+            legacy_tx.rlp().to_vec()
+        } else {
+            legacy_tx.rlp_unsigned().to_vec()
+        };
+        let rlp_signed = legacy_tx.rlp_signed(&sig).to_vec();
+
+        let mut tx = Self {
+            block_number: 1,
+            id: 1,
+            hash: H256(keccak256(&rlp_signed)),
+            tx_type,
+            nonce,
+            gas,
+            gas_price,
+            max_fee_per_gas: gas_price,
+            max_priority_fee_per_gas: gas_price,
+            callee_address: to,
+            is_create: to.is_none(),
+            value,
+            call_data_length: data.len(),
+            call_data_gas_cost: tx_data_gas_cost(&data),
+            call_data: data,
+            tx_data_gas_cost: tx_data_gas_cost(&rlp_signed),
+            chain_id,
+            rlp_unsigned,
+            rlp_signed,
+            v,
+            r,
+            s,
+            ..Default::default()
+        };
+        tx.caller_address = tx.sign_data()?.get_addr();
+
+        Ok(tx)
+    }
+
     /// Return whether the transaction is included in the chunk txbytes (not l1Msg and not padding)
     pub fn is_chunk_l2_tx(&self) -> bool {
         self.tx_type != TxType::L1Msg && !self.caller_address.is_zero()